@@ -2,6 +2,9 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use color_eyre::owo_colors::OwoColorize;
+{%- if websocket == true and compression == true %}
+use websocket_trait::compression::CompressionStats;
+{%- endif %}
 
 /// Stores and analyzes benchmark results with dynamic string-based keys.
 ///
@@ -36,6 +39,11 @@ pub struct BenchmarkResults {
     /// Using HashMap allows benchmarks to be added dynamically at runtime
     /// without modifying the struct definition.
     timings: HashMap<String, Vec<u128>>,
+    {%- if websocket == true and compression == true %}
+
+    /// Compression totals of the messages encoded during the run, if recorded.
+    compression: Option<CompressionStats>,
+    {%- endif %}
 }
 
 impl BenchmarkResults {
@@ -52,6 +60,9 @@ impl BenchmarkResults {
         Self {
             iteration,
             timings: HashMap::new(),
+            {%- if websocket == true and compression == true %}
+            compression: None,
+            {%- endif %}
         }
     }
 
@@ -79,6 +90,21 @@ impl BenchmarkResults {
             .push(duration.as_millis());
     }
 
+    {%- if websocket == true and compression == true %}
+
+    /// Records the compression totals of the run, printed with the summary.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let before = CompressionStats::current();
+    /// // ... run the benchmarks ...
+    /// results.set_compression(CompressionStats::current().since(&before));
+    /// ```
+    pub fn set_compression(&mut self, compression: CompressionStats) {
+        self.compression = Some(compression);
+    }
+    {%- endif %}
+
     /// Prints a formatted, colorized summary of all benchmark results.
     ///
     /// Benchmarks are displayed in alphabetical order for consistency.
@@ -116,7 +142,33 @@ impl BenchmarkResults {
                 self.print_stats(key, timings);
             }
         }
+        {%- if websocket == true and compression == true %}
+
+        if let Some(compression) = &self.compression {
+            Self::print_compression(compression);
+        }
+        {%- endif %}
+    }
+    {%- if websocket == true and compression == true %}
+
+    /// Prints the bytes saved by compression.
+    ///
+    /// # Output Format
+    /// ```text
+    /// compression: messages=40, compressed=2, original=5210B, sent=1894B, saved=3316B
+    /// ```
+    fn print_compression(compression: &CompressionStats) {
+        println!(
+            "{}: messages={}, compressed={}, original={}B, sent={}B, saved={}B",
+            "compression".bright_cyan().bold(),
+            compression.messages,
+            compression.compressed,
+            compression.original_bytes,
+            compression.encoded_bytes,
+            compression.bytes_saved().to_string().green()
+        );
     }
+    {%- endif %}
 
     /// Prints colorized statistics for a single benchmark.
     ///
@@ -5,6 +5,9 @@ use color_eyre::{Result, eyre::eyre};
 use e2e_tests::{AppWorld, ConsoleLog};
 use fantoccini::Locator;
 use tokio::time::Instant;
+{%- if websocket == true and compression == true %}
+use websocket_trait::compression::CompressionStats;
+{%- endif %}
 
 use super::benchmark_result::BenchmarkResults;
 
@@ -19,6 +22,9 @@ use super::benchmark_result::BenchmarkResults;
 ///
 /// # Benchmark Types
 /// - **WebSocket mode**: Measures connect/disconnect handshake timings
+{%- if compression == true %}
+///   and the round trip of a compressed 4 KiB echo
+{%- endif %}
 /// - **Default mode**: Measures button click and state update latency
 pub struct Benchmarks {
     /// Test world providing `WebDriver` browser automation and the backing
@@ -68,10 +74,19 @@ impl Benchmarks {
     /// This method:
     /// 1. Navigates to the homepage
     /// 2. Runs each iteration:
+    {%- if compression == true %}
+    ///    - WebSocket mode: connect + echo + disconnect
+    {%- else %}
     ///    - WebSocket mode: connect + disconnect
+    {%- endif %}
     ///    - Default mode: button click
     /// 3. Prints per-iteration timings
+    {%- if compression == true %}
+    /// 4. Returns aggregate results (and, in WebSocket mode, the bytes saved
+    ///    by compressing server responses)
+    {%- else %}
     /// 4. Returns aggregate results
+    {%- endif %}
     ///
     /// # Returns
     /// `BenchmarkResults` containing all timing data and statistics
@@ -88,6 +103,11 @@ impl Benchmarks {
     /// ```
     pub async fn start(mut self) -> Result<BenchmarkResults> {
         let mut results = BenchmarkResults::new(self.iteration);
+        {%- if websocket == true and compression == true %}
+
+        // Compression counters are per process, and the server runs in this one
+        let compression = CompressionStats::current();
+        {%- endif %}
 
         // Navigate to homepage once before starting benchmarks
         self.navigate_home().await?;
@@ -106,6 +126,13 @@ impl Benchmarks {
             let connect_time = self.benchmark_connect().await?;
             results.add_timing("connect", connect_time);
             println!("{} {}ms", "Connect:".green(), connect_time.as_millis());
+            {%- if compression == true %}
+
+            // Benchmark echo: Click button -> Wait for the 4 KiB echo -> Record time
+            let echo_time = self.benchmark_echo().await?;
+            results.add_timing("echo", echo_time);
+            println!("{} {}ms", "Echo:".green(), echo_time.as_millis());
+            {%- endif %}
 
             // Benchmark disconnect: Click button -> Wait for closure -> Record time
             let disconnect_time = self.benchmark_disconnect().await?;
@@ -124,6 +151,10 @@ impl Benchmarks {
             println!("{} {}ms", "Update num:".green(), time.as_millis());
             {%- endif %}
         }
+        {%- if websocket == true and compression == true %}
+
+        results.set_compression(CompressionStats::current().since(&compression));
+        {%- endif %}
 
         Ok(results)
     }
@@ -184,6 +215,42 @@ impl Benchmarks {
         Ok(elapsed)
     }
 
+    {%- if compression == true %}
+
+    /// Benchmarks the round trip of a message above the compression threshold.
+    ///
+    /// Measures the time from clicking "Send 4 KiB" button until the
+    /// echoed text arrives (confirmed by console log). Both directions are
+    /// deflated, so this includes compressing and decompressing twice.
+    ///
+    /// # Returns
+    /// Duration from button click to echo reception
+    ///
+    /// # Errors
+    /// - Button not found or has wrong text
+    /// - Echo doesn't arrive within 5 seconds
+    pub async fn benchmark_echo(&mut self) -> Result<Duration> {
+        let button = self.find_button_with_text("Send 4 KiB").await?;
+
+        let start = Instant::now();
+        button.click().await?;
+
+        let expected = vec![ConsoleLog::new(
+            "log",
+            "Received: FrontendResponse::Echo (4096 bytes)",
+        )];
+
+        self.wait_for_logs(&expected, Duration::from_secs(5))
+            .await
+            .map_err(|e| eyre!("Echo failed: {e}"))?;
+
+        let elapsed = start.elapsed();
+        self.clear_console_logs().await?;
+
+        Ok(elapsed)
+    }
+    {%- endif %}
+
     /// Benchmarks the WebSocket disconnect operation.
     ///
     /// Measures the time from clicking "Disconnect" button until
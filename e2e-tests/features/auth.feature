@@ -0,0 +1,35 @@
+{%- raw %}@{% endraw %}auth
+@auth
+Feature: Authentication
+
+  Scenario: Anonymous visitors are redirected to the login page
+    Given Goto /account
+    Then I am on "/login?next=/account"
+    Then I see an "h1" with text "Sign in"
+
+  Scenario: A wrong password is rejected
+    Given Goto /login
+    When I sign in as "demo" with password "wrong-password"
+    Then I see the sign in error "Invalid username or password"
+    Then I am on "/login"
+
+  Scenario: Signing in opens the requested page, signing out leaves it
+    Given Goto /account
+    Then I am on "/login?next=/account"
+    When I sign in as "demo" with password "demo-password"
+    Then I am on "/account"
+    Then I see that I am signed in as "demo"
+    When I sign out
+    Then I am on "/login?next=/account"
+
+  Scenario Outline: Signing in never leaves the site
+    Given Goto /login?next=<next>
+    When I sign in as "demo" with password "demo-password"
+    Then I am on "/"
+
+    Examples:
+      | next                 |
+      | //example.com        |
+      | /%5Cexample.com      |
+      | /%09/example.com     |
+      | https://example.com  |
@@ -0,0 +1,47 @@
+{%- raw %}@{% endraw %}chat
+@chat
+Feature: Chat room
+
+  # The room is shared by concurrent scenarios: each one uses its own users
+
+  Scenario: Messages are broadcast to every member
+    Given "alice" joins the chat
+    And "bob" joins the chat
+    Then "alice" sees "bob" online
+
+    When "alice" sends "Hello Bob!"
+    Then "bob" sees the message "Hello Bob!" from "alice"
+    And "alice" sees the message "Hello Bob!" from "alice"
+
+    When "bob" sends "Hi Alice!"
+    Then "alice" sees the message "Hi Alice!" from "bob"
+
+  Scenario: Members see who is typing
+    Given "carol" joins the chat
+    And "dave" joins the chat
+    When "carol" types "Are you th"
+    Then "dave" sees "carol" typing
+
+    When "carol" sends "ere?"
+    Then "dave" sees the message "Are you there?" from "carol"
+    And "dave" no longer sees "carol" typing
+
+  Scenario: Late members receive the history
+    Given "erin" joins the chat
+    When "erin" sends "Anyone here?"
+    And "frank" joins the chat
+    Then "frank" sees the message "Anyone here?" from "erin"
+
+  Scenario: Leaving the room updates the members
+    Given "grace" joins the chat
+    And "heidi" joins the chat
+    Then "grace" sees "heidi" online
+
+    When "heidi" leaves the chat
+    Then "grace" no longer sees "heidi" online
+
+  Scenario: Nicknames are unique
+    Given "ivan" joins the chat
+    And "judy" opens the chat
+    When "judy" joins as "Ivan"
+    Then "judy" sees the error "Nickname Ivan is taken"
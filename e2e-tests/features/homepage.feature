@@ -1,4 +1,4 @@
-{% if websocket == "yes" -%}
+{% if websocket == true -%}
 {%- raw %}@{% endraw %}homepage
 @homepage
 Feature: Homepage UI
@@ -18,6 +18,56 @@ Feature: Homepage UI
     Then I should see the following console logs:
         | WebSocket Closed: code: 1005, reason: | error |
         | error: error reaching server to call server function: WebSocket Closed: code: 1005, reason: | error |
+
+  Scenario: Connecting twice opens a single connection
+    Given Goto /
+    Then I see a button with "Connect"
+    When I double-click the button labeled "Connect"
+    Then I should see the following console logs:
+        | Received: FrontendResponse::HandshakeResponse | log |
+
+    Then the button label changes to "Disconnect"
+
+  Scenario: Reconnecting after a disconnect
+    Given Goto /
+    When I click the button labeled "Connect"
+    Then I should see the following console logs:
+        | Received: FrontendResponse::HandshakeResponse | log |
+
+    When I click the button labeled "Disconnect"
+    Then the button label changes to "Connect"
+    Then I should see the following console logs:
+        | WebSocket Closed: code: 1005, reason: | error |
+        | error: error reaching server to call server function: WebSocket Closed: code: 1005, reason: | error |
+
+    When I click the button labeled "Connect"
+    Then I should see the following console logs:
+        | Received: FrontendResponse::HandshakeResponse | log |
+
+    Then the button label changes to "Disconnect"
+
+  Scenario: Connecting over the SSE transport
+    Given Goto /?transport=sse
+    Then I see a button with "Connect"
+    When I click the button labeled "Connect"
+    Then I should see the following console logs:
+        | Received: FrontendResponse::HandshakeResponse | log |
+
+    Then the button label changes to "Disconnect"
+
+    When I click the button labeled "Disconnect"
+    Then the button label changes to "Connect"
+{%- if compression == true %}
+
+  Scenario: Echoing a message above the compression threshold
+    Given Goto /
+    When I click the button labeled "Connect"
+    Then the button label changes to "Disconnect"
+
+    When I click the button labeled "Send 4 KiB"
+    Then I should see the following console logs:
+        | Received: FrontendResponse::Echo (4096 bytes) | log |
+{%- endif %}
 {% else -%}
 {%- raw %}@{% endraw %}homepage
 @homepage
@@ -0,0 +1,43 @@
+{%- raw %}@{% endraw %}http_status
+@http_status
+Feature: HTTP status codes
+
+  Scenario: Known pages respond with 200
+    When I request /
+    Then the response status is 200
+
+  Scenario: Unknown paths respond with the not found page
+    When I request /does-not-exist
+    Then the response status is 404
+    Then the response contains "Page not found"
+    Then the response contains "/does-not-exist"
+
+  Scenario: Client errors respond with their status and message
+    When I request /status/403
+    Then the response status is 403
+    Then the response contains "Forbidden"
+    Then the response contains "Example of a 403 error"
+
+  Scenario: Server errors respond with their status, without their message
+    When I request /status/503
+    Then the response status is 503
+    Then the response contains "Service Unavailable"
+    Then the response does not contain "Example of a 503 error"
+
+  Scenario: Invalid parameters respond with 400
+    When I request /status/teapot
+    Then the response status is 400
+    Then the response contains "teapot is not an error status"
+
+  Scenario: Invalid server function arguments respond with 400
+    When I post "theme=purple" to the server function set_theme
+    Then the response status is 400
+    Then the response contains "purple"
+
+  Scenario: Missing server function arguments respond with 400
+    When I post "" to the server function set_theme
+    Then the response status is 400
+
+  Scenario: The not found page is shown in the browser
+    Given Goto /does-not-exist
+    Then I see an "h1" with text "404"
@@ -0,0 +1,46 @@
+{%- raw %}@{% endraw %}i18n
+@i18n
+Feature: Internationalization
+
+  Scenario: The locale is negotiated from Accept-Language
+    When I request / with the header "Accept-Language: de-CH, de;q=0.9, en;q=0.8"
+    Then the response is in "de"
+    {%- if websocket == true %}
+    Then the response contains "Verbinden"
+    {%- else %}
+    Then the response contains "Willkommen bei Leptos!"
+    {%- endif %}
+
+  Scenario: Unsupported languages fall back to English
+    When I request / with the header "Accept-Language: fr-FR, fr;q=0.9"
+    Then the response is in "en"
+
+  Scenario: The locale cookie overrides Accept-Language
+    When I request / with the cookie "locale=en"
+    Then the response is in "en"
+
+  Scenario: The route prefix overrides the cookie
+    When I request /de/ with the cookie "locale=en"
+    Then the response is in "de"
+
+  Scenario: Prefixed pages are translated in the browser
+    Given Goto /de/
+    Then the page is in "de"
+    {%- if websocket == true %}
+    Then I see a button with "Verbinden"
+    {%- else %}
+    Then I see an "h1" with text "Willkommen bei Leptos!"
+    {%- endif %}
+
+  Scenario: The chosen language is kept after a reload
+    Given Goto /
+    When I choose the "Deutsch" language
+    Then the page is in "de"
+    When I reload the page
+    Then the page is in "de"
+
+  Scenario: Choosing a language on a prefixed page moves to its prefix
+    Given Goto /de/
+    When I choose the "English" language
+    Then the current path is "/en/"
+    Then the page is in "en"
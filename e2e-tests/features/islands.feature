@@ -0,0 +1,19 @@
+{%- raw %}@{% endraw %}islands
+@islands
+Feature: Islands
+
+  Scenario: Only the demo of the home page is hydrated
+    Given Goto /
+    Then the page has 1 island
+    {%- if websocket == true %}
+    When I click the button labeled "Connect"
+    Then the button label changes to "Disconnect"
+    {%- else %}
+    When I click the button labeled "Click Me: 0"
+    Then the button label changes to "Click Me: 1"
+    {%- endif %}
+
+  Scenario: Pages without demo ship no islands
+    Given Goto /about
+    Then the page has 0 islands
+    Then I see an "h1" with text "About"
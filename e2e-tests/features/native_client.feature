@@ -0,0 +1,23 @@
+{%- raw %}@{% endraw %}native_client
+@native_client
+Feature: Native websocket client
+
+  Scenario: A native client talks to the websocket server function
+    Given a native websocket client is connected
+    When the native client sends a handshake
+    Then the native client receives a handshake response
+
+    When the native client sends a ping with id 7
+    Then the native client receives a pong with id 7
+{%- if compression == true %}
+
+    When the native client sends an echo of 4096 bytes
+    Then the native client receives an echo of 4096 bytes
+{%- endif %}
+
+    When the native client closes the connection
+
+  Scenario: An outdated client is asked to upgrade
+    Given a native websocket client is connected
+    When the native client sends a handshake with an outdated protocol version
+    Then the native client is asked to upgrade
@@ -0,0 +1,33 @@
+{%- raw %}@{% endraw %}pwa
+@pwa
+Feature: Progressive Web App
+
+  Scenario: The web app manifest describes the site
+    When I request /manifest.webmanifest
+    Then the response status is 200
+    Then the response header "Content-Type" contains "manifest+json"
+    Then the response contains "standalone"
+    Then the response contains "/icon.svg"
+
+  Scenario: The service worker controls the whole site
+    When I request /sw.js
+    Then the response status is 200
+    Then the response header "Service-Worker-Allowed" contains "/"
+    Then the response header "Cache-Control" contains "no-cache"
+    Then the response contains "/offline"
+    Then the response contains "/pkg/"
+
+  Scenario: Pages link the manifest
+    When I request /
+    Then the response contains "manifest.webmanifest"
+
+  Scenario: The offline page is pre-rendered
+    When I request /offline
+    Then the response status is 200
+    Then the response contains "noindex"
+
+  Scenario: The service worker is installed in the browser
+    Given Goto /
+    Then the service worker is active
+    When I reload the page
+    Then the service worker controls the page
@@ -0,0 +1,14 @@
+{%- raw %}@{% endraw %}recording
+@recording
+Feature: Session recording
+
+  Scenario: A recorded session replays identically
+    Given a recorded session with a handshake and a ping with id 7
+    When the recorded session is replayed
+    Then the replayed responses match the recording
+
+  Scenario: A replay reports responses that changed
+    Given a recorded session with a handshake and a ping with id 7
+    When the recording expects a pong with id 8
+    And the recorded session is replayed
+    Then the replay reports a pong with id 7 instead of id 8
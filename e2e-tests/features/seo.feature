@@ -0,0 +1,35 @@
+{%- raw %}@{% endraw %}seo
+@seo
+Feature: Search engine metadata
+
+  Scenario: Pages render their metadata
+    When I request /
+    Then the response status is 200
+    Then the response contains "og:title"
+    Then the response contains "og:description"
+    Then the response contains "canonical"
+
+  Scenario: The sitemap lists the static pages
+    When I request /sitemap.xml
+    Then the response status is 200
+    Then the response contains "<urlset"
+    Then the response contains "/</loc>"
+    {%- if database == true %}
+    Then the response contains "/todos</loc>"
+    {%- endif %}
+    {%- if i18n == true %}
+    Then the response contains "/de"
+    {%- endif %}
+    Then the response does not contain "/status/"
+    {%- if auth == true %}
+    Then the response does not contain "/login"
+    {%- endif %}
+    {%- if pwa == true %}
+    Then the response does not contain "/offline"
+    {%- endif %}
+
+  Scenario: The robots file points to the sitemap
+    When I request /robots.txt
+    Then the response status is 200
+    Then the response contains "Sitemap:"
+    Then the response contains "/sitemap.xml"
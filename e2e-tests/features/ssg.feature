@@ -0,0 +1,19 @@
+{%- raw %}@{% endraw %}ssg
+@ssg
+Feature: Static pages
+
+  Scenario: Static pages are served with cache headers
+    When I request /about
+    Then the response status is 200
+    Then the response contains "pre-rendered"
+    Then the response header "Cache-Control" contains "max-age=60"
+    Then the response header "Last-Modified" contains "GMT"
+
+  Scenario: Dynamic pages are not cached
+    When I request /
+    Then the response status is 200
+    Then the response has no "Cache-Control" header
+
+  Scenario: Static pages hydrate in the browser
+    Given Goto /about
+    Then I see an "h1" with text "About"
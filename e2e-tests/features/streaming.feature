@@ -0,0 +1,49 @@
+{%- raw %}@{% endraw %}streaming
+@streaming
+Feature: Streaming SSR modes
+
+  # The summary takes 1s to load, and the report 2.5s
+
+  Scenario: Out-of-order streaming sends the fallbacks first
+    When I request /streaming
+    Then the response status is 200
+    Then the response arrives in 3 parts
+    Then part 1 of the response contains "Loading summary..."
+    Then part 1 of the response contains "Loading report..."
+    Then part 2 of the response contains "5 reports available"
+    Then part 3 of the response contains "Report 1"
+
+  Scenario: In-order streaming stops at the first pending fragment
+    When I request /streaming/in-order
+    Then the response status is 200
+    Then the response arrives in 3 parts
+    Then part 1 of the response contains "ssr-modes"
+    Then part 1 of the response does not contain "Loading summary..."
+    Then part 2 of the response contains "5 reports available"
+    Then part 2 of the response does not contain "Loading report..."
+    Then part 3 of the response contains "Report 1"
+
+  Scenario: Partially-blocked streaming waits for the blocking resources
+    When I request /streaming/partially-blocked
+    Then the response status is 200
+    Then the response arrives in 2 parts
+    Then part 1 of the response contains "5 reports available"
+    Then part 1 of the response contains "Loading report..."
+    Then part 2 of the response contains "Report 1"
+
+  Scenario: Async rendering sends the whole page at once
+    When I request /streaming/async
+    Then the response status is 200
+    Then the response arrives in 1 part
+    Then the response contains "5 reports available"
+    Then the response contains "Report 1"
+    Then the response does not contain "Loading report..."
+{%- if islands == false %}
+
+  Scenario: Transitions keep the previous data while loading
+    Given Goto /streaming
+    Then I see the report "Report 1"
+    When I follow the link "Next report"
+    Then I still see the report "Report 1"
+    Then I see the report "Report 2"
+{%- endif %}
@@ -0,0 +1,25 @@
+{%- raw %}@{% endraw %}theme
+@theme
+Feature: Color theme
+
+  Scenario: The system theme is used by default
+    Given Goto /
+    Then the page uses the "system" theme
+
+  Scenario: Choosing a theme switches the page
+    Given Goto /
+    When I choose the "Dark" theme
+    Then the page uses the "dark" theme
+    When I choose the "Light" theme
+    Then the page uses the "light" theme
+
+  Scenario: The chosen theme is kept after a reload
+    Given Goto /
+    When I choose the "Dark" theme
+    Then the page uses the "dark" theme
+    When I reload the page
+    Then the page uses the "dark" theme
+
+  Scenario: The server renders the theme of the cookie
+    When I request / with the cookie "theme=dark"
+    Then the response renders the "dark" theme
@@ -0,0 +1,44 @@
+{%- raw %}@{% endraw %}todos
+@todos
+Feature: Todos
+
+  Scenario: The list starts empty
+    Given Goto /todos
+    Then I see a "p.empty" with text "Nothing to do"
+
+  Scenario: Adding todos lists them in order
+    Given Goto /todos
+    When I add the todo "Buy milk"
+    When I add the todo "Walk the dog"
+    Then I see the todos:
+      | Buy milk     |
+      | Walk the dog |
+
+  Scenario: Completing a todo
+    Given Goto /todos
+    When I add the todo "Buy milk"
+    When I mark the todo "Buy milk" as done
+    Then the todo "Buy milk" is completed
+    When I mark the todo "Buy milk" as not done
+    Then the todo "Buy milk" is not completed
+
+  Scenario: Deleting a todo
+    Given Goto /todos
+    When I add the todo "Buy milk"
+    When I add the todo "Walk the dog"
+    When I delete the todo "Buy milk"
+    Then I see the todos:
+      | Walk the dog |
+
+  Scenario: Todos are kept across page loads
+    Given Goto /todos
+    When I add the todo "Buy milk"
+    Given Goto /todos
+    Then I see the todos:
+      | Buy milk |
+    Then the todo "Buy milk" is not completed
+
+  Scenario: A blank title is rejected
+    Given Goto /todos
+    When I submit the title "   "
+    Then I see the title error "Title can not be empty"
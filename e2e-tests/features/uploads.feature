@@ -0,0 +1,43 @@
+{%- raw %}@{% endraw %}uploads
+@uploads
+Feature: File uploads
+
+  Scenario: The upload page is rendered
+    When I request /upload
+    Then the response status is 200
+    Then the response contains "uploader"
+
+  Scenario: Missing files are not found
+    When I request /files/does-not-exist.png
+    Then the response status is 404
+
+  Scenario: Keys cannot leave the storage directory
+    When I request /files/..%2Fsecret.txt
+    Then the response status is 404
+
+  Scenario: An uploaded file is listed and downloaded
+    Given Goto /upload
+    When I upload the file "hello.txt" containing "Hello, uploads!"
+    Then the upload progress reaches 100%
+    Then I see the file "hello.txt" in the list
+    When I download the file "hello.txt"
+    Then the response status is 200
+    Then the response header "Content-Type" contains "text/plain"
+    Then the response header "X-Content-Type-Options" contains "nosniff"
+    Then the response header "Content-Disposition" contains "hello.txt"
+    Then the response contains "Hello, uploads!"
+
+  Scenario: Files must match their type
+    Given Goto /upload
+    When I upload the file "fake.png" containing "not an image"
+    Then I see the upload error "The content of the file is not image/png"
+
+  Scenario: Only some types are accepted
+    Given Goto /upload
+    When I upload the file "drawing.svg" containing "<svg xmlns='http://www.w3.org/2000/svg'></svg>"
+    Then I see the upload error "Files of type image/svg+xml are not accepted"
+
+  Scenario: Files over the size limit are rejected
+    Given Goto /upload
+    When I upload a file of 11 MB
+    Then I see the upload error "File exceeds the limit of 10 MB"
@@ -93,6 +93,47 @@ pub async fn click_button_with_label(world: &mut AppWorld, label: String) -> Res
     Ok(())
 }
 
+/// Step: When I double-click the button labeled "text"
+///
+/// Clicks a button twice within the same task, before the page can react
+/// to the first click (e.g. re-render the button or update its label).
+///
+/// # Example
+/// ```gherkin
+/// When I double-click the button labeled "Connect"
+/// ```
+#[when(regex = r#"I double-click the button labeled "(.*)""#)]
+pub async fn double_click_button_with_label(world: &mut AppWorld, label: String) -> Result<()> {
+    let button_text = world.find(Locator::Css("button")).await?.text().await?;
+
+    // Verify button has expected label before clicking
+    assert_eq!(button_text, label);
+
+    world
+        .execute(
+            "const button = document.querySelector('button'); button.click(); button.click();",
+            vec![],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Step: When I follow the link "text"
+///
+/// Clicks the link with this exact text.
+///
+/// # Example
+/// ```gherkin
+/// When I follow the link "Next report"
+/// ```
+#[when(regex = r#"^I follow the link "([^"]+)"$"#)]
+pub async fn follow_link(world: &mut AppWorld, text: String) -> Result<()> {
+    world.find(Locator::LinkText(&text)).await?.click().await?;
+
+    Ok(())
+}
+
 /// Step: Then the button label changes to "text"
 ///
 /// Verifies that the button text has changed to a new value.
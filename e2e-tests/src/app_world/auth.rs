@@ -0,0 +1,152 @@
+//! Authentication steps.
+//!
+//! Every scenario has its own browser session, hence its own session
+//! cookie. The steps sign in as the `demo` user, added by debug builds of
+//! the server.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use cucumber::{then, when};
+use fantoccini::Locator;
+
+use super::AppWorld;
+
+/// Maximum time to wait for the page to reflect a sign in or sign out.
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(3);
+
+impl AppWorld {
+    /// Returns the path and query of the current page, e.g. `/login?next=/account`.
+    async fn current_path(&self) -> Result<String> {
+        let url = self.webdriver.client.current_url().await?;
+
+        Ok(match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        })
+    }
+}
+
+/// Step: When I sign in as "username" with password "password"
+///
+/// Fills and submits the login form of the current page.
+///
+/// # Example
+/// ```gherkin
+/// When I sign in as "demo" with password "demo-password"
+/// ```
+#[when(regex = r#"^I sign in as "([^"]+)" with password "([^"]+)"$"#)]
+async fn sign_in(world: &mut AppWorld, username: String, password: String) -> Result<()> {
+    world
+        .find(Locator::Css("form.login input[name='username']"))
+        .await
+        .context("No login form")?
+        .send_keys(&username)
+        .await?;
+    world
+        .find(Locator::Css("form.login input[name='password']"))
+        .await?
+        .send_keys(&password)
+        .await?;
+    world
+        .find(Locator::Css("form.login button[type='submit']"))
+        .await?
+        .click()
+        .await?;
+
+    Ok(())
+}
+
+/// Step: When I sign out
+///
+/// Submits the logout form of the current page.
+#[when("I sign out")]
+async fn sign_out(world: &mut AppWorld) -> Result<()> {
+    world
+        .find(Locator::Css("form.logout button[type='submit']"))
+        .await
+        .context("No logout form")?
+        .click()
+        .await?;
+
+    Ok(())
+}
+
+/// Step: Then I am on "path"
+///
+/// Waits until the browser shows `path`, query included.
+///
+/// # Example
+/// ```gherkin
+/// Then I am on "/login?next=/account"
+/// ```
+#[then(regex = r#"^I am on "([^"]+)"$"#)]
+async fn i_am_on(world: &mut AppWorld, expected: String) -> Result<()> {
+    let tick_duration = UPDATE_TIMEOUT / 5;
+    let mut latest_path = String::new();
+
+    for _ in 0..5 {
+        latest_path = world.current_path().await?;
+        if latest_path == expected {
+            return Ok(());
+        }
+
+        tokio::time::sleep(tick_duration).await;
+    }
+
+    Err(anyhow!(
+        "Timed out waiting for {expected}, still on {latest_path}"
+    ))
+}
+
+/// Step: Then I see the sign in error "message"
+///
+/// # Example
+/// ```gherkin
+/// Then I see the sign in error "Invalid username or password"
+/// ```
+#[then(regex = r#"^I see the sign in error "([^"]+)"$"#)]
+async fn i_see_sign_in_error(world: &mut AppWorld, expected: String) -> Result<()> {
+    let tick_duration = UPDATE_TIMEOUT / 5;
+    let mut latest_error = None;
+
+    for _ in 0..5 {
+        if let Ok(element) = world.find(Locator::Css("p.error")).await {
+            let error = element.text().await?;
+            if error == expected {
+                return Ok(());
+            }
+            latest_error = Some(error);
+        }
+
+        tokio::time::sleep(tick_duration).await;
+    }
+
+    Err(anyhow!(
+        "Timed out waiting for the error {expected:?}, latest: {latest_error:?}"
+    ))
+}
+
+/// Step: Then I see that I am signed in as "username"
+#[then(regex = r#"^I see that I am signed in as "([^"]+)"$"#)]
+async fn i_see_signed_in_as(world: &mut AppWorld, username: String) -> Result<()> {
+    let expected = format!("Signed in as {username}");
+    let tick_duration = UPDATE_TIMEOUT / 5;
+    let mut latest_text = None;
+
+    for _ in 0..5 {
+        if let Ok(element) = world.find(Locator::Css("p.user")).await {
+            let text = element.text().await?;
+            if text == expected {
+                return Ok(());
+            }
+            latest_text = Some(text);
+        }
+
+        tokio::time::sleep(tick_duration).await;
+    }
+
+    Err(anyhow!(
+        "Timed out waiting for {expected:?}, latest: {latest_text:?}"
+    ))
+}
@@ -0,0 +1,263 @@
+//! Chat page steps.
+//!
+//! Each user of a scenario gets their own browser session, opened on the
+//! scenario's server, so messages and typing notifications can be checked
+//! from both sides.
+//!
+//! The chat room lives in the server process, shared by the servers of
+//! concurrent scenarios: each scenario uses its own user names, and the
+//! steps check for them rather than for exact lists.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use cucumber::{given, then, when};
+use fantoccini::{Client, Locator};
+
+use super::AppWorld;
+use crate::WebDriver;
+
+/// Maximum time to wait for the page to reflect a change.
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(3);
+
+impl AppWorld {
+    /// Returns the browser session of a user.
+    ///
+    /// # Errors
+    /// Returns an error if the user did not open the chat yet.
+    fn browser(&self, user: &str) -> Result<&Client> {
+        self.browsers
+            .get(user)
+            .map(|webdriver| &webdriver.client)
+            .ok_or_else(|| anyhow!("{user} has no browser: open the chat first"))
+    }
+}
+
+/// Waits until the texts of the elements matching `css` satisfy `expected`.
+///
+/// # Errors
+/// Returns an error with the latest texts if they still do not match after
+/// `UPDATE_TIMEOUT`.
+async fn wait_for_texts(
+    client: &Client,
+    css: &str,
+    expected: impl Fn(&[String]) -> bool,
+) -> Result<()> {
+    let tick_duration = UPDATE_TIMEOUT / 5;
+    let mut latest_texts = Vec::new();
+
+    for _ in 0..5 {
+        let mut texts = Vec::new();
+        for element in client.find_all(Locator::Css(css)).await? {
+            texts.push(element.text().await?);
+        }
+
+        if expected(&texts) {
+            return Ok(());
+        }
+
+        latest_texts = texts;
+
+        tokio::time::sleep(tick_duration).await;
+    }
+
+    Err(anyhow!(
+        "Timed out waiting for {css}\n\nLatest texts:\n{latest_texts:#?}"
+    ))
+}
+
+/// Fills an input of the page and optionally submits its form.
+async fn fill(client: &Client, name: &str, text: &str, submit: bool) -> Result<()> {
+    let input = client
+        .find(Locator::Css(&format!("input[name='{name}']")))
+        .await
+        .context(format!("No {name} input"))?;
+    input.send_keys(text).await?;
+
+    if submit {
+        input.send_keys("\u{E007}").await?; // Enter
+    }
+
+    Ok(())
+}
+
+/// Nicknames listed by the members line, e.g. `Online: alice, bob`.
+fn members(text: &str) -> Vec<&str> {
+    text.trim_start_matches("Online:")
+        .split(',')
+        .map(str::trim)
+        .collect()
+}
+
+/// Nicknames listed by the typing indicator, e.g. `alice, bob are typing...`.
+fn typing_nicknames(text: &str) -> Vec<&str> {
+    text.trim_end_matches(" is typing...")
+        .trim_end_matches(" are typing...")
+        .split(',')
+        .map(str::trim)
+        .filter(|nickname| !nickname.is_empty())
+        .collect()
+}
+
+/// Step: Given "user" opens the chat
+///
+/// Opens a browser session for the user on `/chat`.
+///
+/// # Example
+/// ```gherkin
+/// Given "alice" opens the chat
+/// ```
+#[given(regex = r#"^"([^"]+)" opens the chat$"#)]
+async fn open_chat(world: &mut AppWorld, user: String) -> Result<()> {
+    let url = format!("http://127.0.0.1:{}/chat", world.leptos_server_port()?);
+    let webdriver = WebDriver::new().await?;
+
+    webdriver
+        .client
+        .goto(&url)
+        .await
+        .context(format!("Failed to navigate to {url}"))?;
+    world.browsers.insert(user, webdriver);
+
+    Ok(())
+}
+
+/// Step: When "user" joins as "nickname"
+#[when(regex = r#"^"([^"]+)" joins as "([^"]+)"$"#)]
+async fn join_as(world: &mut AppWorld, user: String, nickname: String) -> Result<()> {
+    fill(world.browser(&user)?, "nickname", &nickname, true).await
+}
+
+/// Step: Given "user" joins the chat
+///
+/// Opens the chat and joins under the user name, waiting for the room.
+///
+/// # Example
+/// ```gherkin
+/// Given "alice" joins the chat
+/// ```
+#[given(regex = r#"^"([^"]+)" joins the chat$"#)]
+async fn join_chat(world: &mut AppWorld, user: String) -> Result<()> {
+    open_chat(world, user.clone()).await?;
+
+    let client = world.browser(&user)?;
+    fill(client, "nickname", &user, true).await?;
+    wait_for_texts(client, "p.members", |texts| {
+        texts
+            .iter()
+            .any(|text| members(text).contains(&user.as_str()))
+    })
+    .await
+}
+
+/// Step: When "user" types "text"
+///
+/// Types into the message input without sending.
+#[when(regex = r#"^"([^"]+)" types "([^"]+)"$"#)]
+async fn type_message(world: &mut AppWorld, user: String, text: String) -> Result<()> {
+    fill(world.browser(&user)?, "message", &text, false).await
+}
+
+/// Step: When "user" sends "text"
+#[when(regex = r#"^"([^"]+)" sends "([^"]+)"$"#)]
+async fn send_message(world: &mut AppWorld, user: String, text: String) -> Result<()> {
+    fill(world.browser(&user)?, "message", &text, true).await
+}
+
+/// Step: When "user" leaves the chat
+///
+/// Closes the user's browser session, ending their connection.
+#[when(regex = r#"^"([^"]+)" leaves the chat$"#)]
+async fn leave_chat(world: &mut AppWorld, user: String) -> Result<()> {
+    let webdriver = world
+        .browsers
+        .remove(&user)
+        .ok_or_else(|| anyhow!("{user} has no browser: open the chat first"))?;
+    webdriver.client.close().await?;
+
+    Ok(())
+}
+
+/// Step: Then "user" sees the message "text" from "author"
+///
+/// # Example
+/// ```gherkin
+/// Then "bob" sees the message "Hello!" from "alice"
+/// ```
+#[then(regex = r#"^"([^"]+)" sees the message "([^"]+)" from "([^"]+)"$"#)]
+async fn sees_message(
+    world: &mut AppWorld,
+    user: String,
+    text: String,
+    author: String,
+) -> Result<()> {
+    let expected = format!("{author}: {text}");
+
+    wait_for_texts(world.browser(&user)?, "li.message", |texts| {
+        texts.contains(&expected)
+    })
+    .await
+}
+
+/// Step: Then "user" sees "other" typing
+///
+/// # Example
+/// ```gherkin
+/// Then "bob" sees "alice" typing
+/// ```
+#[then(regex = r#"^"([^"]+)" sees "([^"]+)" typing$"#)]
+async fn sees_typing(world: &mut AppWorld, user: String, other: String) -> Result<()> {
+    wait_for_texts(world.browser(&user)?, "p.typing", |texts| {
+        texts
+            .iter()
+            .any(|text| typing_nicknames(text).contains(&other.as_str()))
+    })
+    .await
+}
+
+/// Step: Then "user" no longer sees "other" typing
+#[then(regex = r#"^"([^"]+)" no longer sees "([^"]+)" typing$"#)]
+async fn no_longer_sees_typing(world: &mut AppWorld, user: String, other: String) -> Result<()> {
+    wait_for_texts(world.browser(&user)?, "p.typing", |texts| {
+        texts
+            .iter()
+            .all(|text| !typing_nicknames(text).contains(&other.as_str()))
+    })
+    .await
+}
+
+/// Step: Then "user" sees "other" online
+///
+/// # Example
+/// ```gherkin
+/// Then "alice" sees "bob" online
+/// ```
+#[then(regex = r#"^"([^"]+)" sees "([^"]+)" online$"#)]
+async fn sees_online(world: &mut AppWorld, user: String, other: String) -> Result<()> {
+    wait_for_texts(world.browser(&user)?, "p.members", |texts| {
+        texts
+            .iter()
+            .any(|text| members(text).contains(&other.as_str()))
+    })
+    .await
+}
+
+/// Step: Then "user" no longer sees "other" online
+#[then(regex = r#"^"([^"]+)" no longer sees "([^"]+)" online$"#)]
+async fn no_longer_sees_online(world: &mut AppWorld, user: String, other: String) -> Result<()> {
+    wait_for_texts(world.browser(&user)?, "p.members", |texts| {
+        texts
+            .iter()
+            .all(|text| !members(text).contains(&other.as_str()))
+    })
+    .await
+}
+
+/// Step: Then "user" sees the error "text"
+#[then(regex = r#"^"([^"]+)" sees the error "([^"]+)"$"#)]
+async fn sees_error(world: &mut AppWorld, user: String, error: String) -> Result<()> {
+    wait_for_texts(world.browser(&user)?, "p.error", |texts| {
+        texts.contains(&error)
+    })
+    .await
+}
@@ -2,13 +2,23 @@
 //!
 //! Provides the test context with WebDriver automation capabilities.
 
+{% if websocket == true -%}
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+{% endif -%}
 use anyhow::{Context, Result};
 use cucumber::World;
 use fantoccini::Locator;
 use fantoccini::elements::Element;
 use serde_json::Value;
 
-use crate::{LeptosServer, WebDriver};
+{% if websocket == true -%}
+use websocket_trait::server::ReplayReport;
+
+use super::native_client::RkyvNativeClient;
+{% endif -%}
+use crate::{HttpResponse, LeptosServer, WebDriver};
 
 /// Cucumber World for browser-based testing.
 ///
@@ -28,6 +38,23 @@ pub struct AppWorld {
     /// and used by [`AppWorld::goto_path`] to build navigation URLs via gets
     /// its own isolated server instance.
     leptos_server: LeptosServer,
+
+    /// Response of the last request sent by the HTTP steps, if any.
+    pub response: Option<HttpResponse>,
+{%- if websocket == true %}
+
+    /// Native websocket client opened by the native client steps, if any.
+    pub native_client: Option<RkyvNativeClient>,
+
+    /// Session log written by the recording steps, if any.
+    pub recording: Option<PathBuf>,
+
+    /// Outcome of the last replay of `recording`.
+    pub replay_report: Option<ReplayReport>,
+
+    /// Extra browser sessions opened by the chat steps, by user name.
+    pub browsers: HashMap<String, WebDriver>,
+{%- endif %}
 }
 
 impl AppWorld {
@@ -60,6 +87,13 @@ impl AppWorld {
         Ok(Self {
             webdriver,
             leptos_server,
+            response: None,
+            {%- if websocket == true %}
+            native_client: None,
+            recording: None,
+            replay_report: None,
+            browsers: HashMap::new(),
+            {%- endif %}
         })
     }
 
@@ -201,7 +235,7 @@ impl AppWorld {
     /// # Errors
     /// Returns an error if the server hasn't finished starting (or was
     /// already stopped), matching [`LeptosServer::get_port`].
-    fn leptos_server_port(&self) -> Result<u16> {
+    pub(super) fn leptos_server_port(&self) -> Result<u16> {
         self.leptos_server.get_port()
     }
 }
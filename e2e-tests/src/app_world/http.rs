@@ -0,0 +1,230 @@
+//! HTTP response steps.
+//!
+//! Requests are sent outside of the browser, which does not expose status
+//! codes.
+
+use anyhow::{Context, Result, bail};
+use app::theme::SetTheme;
+use cucumber::{then, when};
+use leptos::server_fn::ServerFn;
+
+use super::AppWorld;
+use crate::{http_get, http_get_with_headers, http_post_form};
+
+/// Step: When I request /path
+///
+/// Sends `GET /path` to the scenario's server and keeps the response.
+///
+/// # Example
+/// ```gherkin
+/// When I request /does-not-exist
+/// ```
+#[when(regex = r"^I request (/\S*)$")]
+async fn request(world: &mut AppWorld, path: String) -> Result<()> {
+    let response = http_get(world.leptos_server_port()?, &path).await?;
+    world.response = Some(response);
+
+    Ok(())
+}
+
+/// Step: When I request /path with the cookie "name=value"
+///
+/// # Example
+/// ```gherkin
+/// When I request / with the cookie "theme=dark"
+/// ```
+#[when(regex = r#"^I request (/\S*) with the cookie "([^"]+)"$"#)]
+async fn request_with_cookie(world: &mut AppWorld, path: String, cookie: String) -> Result<()> {
+    let port = world.leptos_server_port()?;
+    let response = http_get_with_headers(port, &path, &[("Cookie", &cookie)]).await?;
+    world.response = Some(response);
+
+    Ok(())
+}
+
+/// Step: When I request /path with the header "Name: value"
+///
+/// # Example
+/// ```gherkin
+/// When I request / with the header "Accept-Language: de"
+/// ```
+#[when(regex = r#"^I request (/\S*) with the header "([^:"]+): ([^"]+)"$"#)]
+async fn request_with_header(
+    world: &mut AppWorld,
+    path: String,
+    name: String,
+    value: String,
+) -> Result<()> {
+    let port = world.leptos_server_port()?;
+    let response = http_get_with_headers(port, &path, &[(&name, &value)]).await?;
+    world.response = Some(response);
+
+    Ok(())
+}
+
+/// Step: When I post "arguments" to the server function name
+///
+/// Calls a server function with URL-encoded arguments, like its client.
+///
+/// # Example
+/// ```gherkin
+/// When I post "theme=dark" to the server function set_theme
+/// ```
+#[when(regex = r#"^I post "([^"]*)" to the server function (\w+)$"#)]
+async fn post_server_fn(world: &mut AppWorld, arguments: String, name: String) -> Result<()> {
+    let path = match name.as_str() {
+        "set_theme" => SetTheme::PATH,
+        _ => bail!("Unknown server function {name}"),
+    };
+
+    let response = http_post_form(world.leptos_server_port()?, path, &arguments).await?;
+    world.response = Some(response);
+
+    Ok(())
+}
+
+/// Step: Then the response status is 404
+#[then(regex = r"^the response status is (\d{3})$")]
+async fn response_status(world: &mut AppWorld, status: u16) -> Result<()> {
+    let response = world.response.as_ref().context("No request sent")?;
+
+    assert_eq!(
+        response.status, status,
+        "Unexpected status, body:\n{}",
+        response.body
+    );
+
+    Ok(())
+}
+
+/// Step: Then the response contains "text"
+#[then(regex = r#"^the response contains "([^"]+)"$"#)]
+async fn response_contains(world: &mut AppWorld, text: String) -> Result<()> {
+    let response = world.response.as_ref().context("No request sent")?;
+
+    assert!(
+        response.body.contains(&text),
+        "{text:?} not found in the response:\n{}",
+        response.body
+    );
+
+    Ok(())
+}
+
+/// Step: Then the response does not contain "text"
+#[then(regex = r#"^the response does not contain "([^"]+)"$"#)]
+async fn response_does_not_contain(world: &mut AppWorld, text: String) -> Result<()> {
+    let response = world.response.as_ref().context("No request sent")?;
+
+    assert!(
+        !response.body.contains(&text),
+        "{text:?} found in the response:\n{}",
+        response.body
+    );
+
+    Ok(())
+}
+
+/// Step: Then the response header "Name" contains "text"
+///
+/// # Example
+/// ```gherkin
+/// Then the response header "Cache-Control" contains "max-age"
+/// ```
+#[then(regex = r#"^the response header "([^"]+)" contains "([^"]+)"$"#)]
+async fn response_header_contains(world: &mut AppWorld, name: String, text: String) -> Result<()> {
+    let response = world.response.as_ref().context("No request sent")?;
+    let value = response
+        .header(&name)
+        .with_context(|| format!("No {name} header in {:?}", response.headers))?;
+
+    assert!(
+        value.contains(&text),
+        "{text:?} not found in the {name} header: {value:?}"
+    );
+
+    Ok(())
+}
+
+/// Step: Then the response has no "Name" header
+#[then(regex = r#"^the response has no "([^"]+)" header$"#)]
+async fn response_has_no_header(world: &mut AppWorld, name: String) -> Result<()> {
+    let response = world.response.as_ref().context("No request sent")?;
+
+    assert!(
+        response.header(&name).is_none(),
+        "Unexpected {name} header in {:?}",
+        response.headers
+    );
+
+    Ok(())
+}
+
+/// Step: Then the response arrives in 3 parts
+///
+/// Counts the parts of a streamed body: the server sends a new part after
+/// waiting for a resource, e.g. below a `<Suspense>`.
+///
+/// # Example
+/// ```gherkin
+/// Then the response arrives in 1 part
+/// ```
+#[then(regex = r"^the response arrives in (\d+) parts?$")]
+async fn response_arrives_in_parts(world: &mut AppWorld, expected: usize) -> Result<()> {
+    let response = world.response.as_ref().context("No request sent")?;
+
+    assert_eq!(
+        response.parts.len(),
+        expected,
+        "Unexpected number of parts:\n{:#?}",
+        response.parts
+    );
+
+    Ok(())
+}
+
+/// Step: Then part 1 of the response contains "text"
+///
+/// # Example
+/// ```gherkin
+/// Then part 2 of the response contains "Report 1"
+/// ```
+#[then(regex = r#"^part (\d+) of the response contains "([^"]+)"$"#)]
+async fn response_part_contains(world: &mut AppWorld, index: usize, text: String) -> Result<()> {
+    let part = response_part(world, index)?;
+
+    assert!(
+        part.contains(&text),
+        "{text:?} not found in part {index} of the response:\n{part}"
+    );
+
+    Ok(())
+}
+
+/// Step: Then part 1 of the response does not contain "text"
+#[then(regex = r#"^part (\d+) of the response does not contain "([^"]+)"$"#)]
+async fn response_part_does_not_contain(
+    world: &mut AppWorld,
+    index: usize,
+    text: String,
+) -> Result<()> {
+    let part = response_part(world, index)?;
+
+    assert!(
+        !part.contains(&text),
+        "{text:?} found in part {index} of the response:\n{part}"
+    );
+
+    Ok(())
+}
+
+/// Returns a part of the response, counted from 1.
+fn response_part(world: &AppWorld, index: usize) -> Result<&str> {
+    let response = world.response.as_ref().context("No request sent")?;
+
+    index
+        .checked_sub(1)
+        .and_then(|index| response.parts.get(index))
+        .map(String::as_str)
+        .with_context(|| format!("No part {index} in {:#?}", response.parts))
+}
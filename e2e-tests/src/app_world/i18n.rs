@@ -0,0 +1,122 @@
+//! Internationalization steps.
+//!
+//! Every scenario has its own browser session, hence its own locale cookie.
+//! The browser asks for English, so unprefixed pages start in English.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use cucumber::{then, when};
+use fantoccini::Locator;
+
+use super::AppWorld;
+
+/// Maximum time to wait for the page to switch locale.
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Step: When I choose the "Deutsch" language
+///
+/// Clicks the button of the locale switcher with this label.
+///
+/// # Example
+/// ```gherkin
+/// When I choose the "English" language
+/// ```
+#[when(regex = r#"^I choose the "([^"]+)" language$"#)]
+async fn choose_language(world: &mut AppWorld, label: String) -> Result<()> {
+    for button in world
+        .webdriver
+        .client
+        .find_all(Locator::Css("form.locale-switcher button"))
+        .await?
+    {
+        if button.text().await? == label {
+            button.click().await?;
+            return Ok(());
+        }
+    }
+
+    Err(anyhow!("No {label:?} button in the locale switcher"))
+}
+
+/// Step: Then the response is in "de"
+///
+/// Checks the `lang` attribute of `<html>` in the response of the server.
+#[then(regex = r#"^the response is in "([^"]+)"$"#)]
+async fn response_is_in(world: &mut AppWorld, expected: String) -> Result<()> {
+    let response = world.response.as_ref().context("No request sent")?;
+    let attribute = format!("lang=\"{expected}\"");
+
+    // Other attributes, e.g. `data-theme`, are added to the tag too
+    let html_tag = response
+        .body
+        .split_once("<html")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map(|(tag, _)| tag)
+        .context("No <html> tag in the response")?;
+
+    assert!(
+        html_tag.contains(&attribute),
+        "{attribute:?} not found in <html{html_tag}>"
+    );
+
+    Ok(())
+}
+
+/// Step: Then the page is in "de"
+///
+/// Waits for the `lang` attribute of `<html>` to match.
+#[then(regex = r#"^the page is in "([^"]+)"$"#)]
+async fn page_is_in(world: &mut AppWorld, expected: String) -> Result<()> {
+    let tick_duration = UPDATE_TIMEOUT / 5;
+    let mut latest = None;
+
+    for _ in 0..5 {
+        let lang = world
+            .webdriver
+            .client
+            .find(Locator::Css("html"))
+            .await?
+            .attr("lang")
+            .await?;
+
+        if lang.as_deref() == Some(&expected) {
+            return Ok(());
+        }
+
+        latest = lang;
+
+        tokio::time::sleep(tick_duration).await;
+    }
+
+    Err(anyhow!(
+        "Timed out waiting for the page to be in {expected}, latest lang: {latest:?}"
+    ))
+}
+
+/// Step: Then the current path is "/en/"
+#[then(regex = r#"^the current path is "([^"]+)"$"#)]
+async fn current_path_is(world: &mut AppWorld, expected: String) -> Result<()> {
+    let tick_duration = UPDATE_TIMEOUT / 5;
+    let mut latest = String::new();
+
+    for _ in 0..5 {
+        latest = world
+            .webdriver
+            .client
+            .current_url()
+            .await?
+            .path()
+            .to_string();
+
+        if latest == expected {
+            return Ok(());
+        }
+
+        tokio::time::sleep(tick_duration).await;
+    }
+
+    Err(anyhow!(
+        "Timed out waiting for {expected}, latest path: {latest}"
+    ))
+}
@@ -0,0 +1,29 @@
+//! Islands steps.
+
+use anyhow::Result;
+use cucumber::then;
+use fantoccini::Locator;
+
+use super::AppWorld;
+
+/// Step: Then the page has 1 island
+///
+/// Counts the `<leptos-island>` elements, the only parts of the page
+/// hydrated in islands mode.
+///
+/// # Example
+/// ```gherkin
+/// Then the page has 0 islands
+/// ```
+#[then(regex = r"^the page has (\d+) islands?$")]
+async fn page_has_islands(world: &mut AppWorld, expected: usize) -> Result<()> {
+    let islands = world
+        .webdriver
+        .client
+        .find_all(Locator::Css("leptos-island"))
+        .await?;
+
+    assert_eq!(islands.len(), expected, "Unexpected number of islands");
+
+    Ok(())
+}
@@ -4,8 +4,38 @@
 //! for browser automation.
 
 mod action;
+{%- if auth == true %}
+mod auth;
+{%- endif %}
+{%- if websocket == true %}
+mod chat;
+{%- endif %}
 mod console_log;
 mod core;
+mod http;
+{%- if i18n == true %}
+mod i18n;
+{%- endif %}
+{%- if islands == true %}
+mod islands;
+{%- endif %}
+{%- if websocket == true %}
+mod native_client;
+{%- endif %}
+{%- if pwa == true %}
+mod pwa;
+{%- endif %}
+{%- if websocket == true %}
+mod recording;
+{%- endif %}
+mod streaming;
+mod theme;
+{%- if database == true %}
+mod todos;
+{%- endif %}
+{%- if uploads == true %}
+mod uploads;
+{%- endif %}
 
 pub use console_log::ConsoleLog;
 pub use core::AppWorld;
@@ -0,0 +1,180 @@
+//! Native websocket client steps.
+//!
+//! Talks to the `rkyv_websocket` server function of the scenario's server
+//! without the browser, using the same `Request`/`Response` types as the app.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use app::pages::home::ws::RKYV_WEBSOCKET_PATH;
+use app::pages::home::ws::message::{Encoding, Request, Response};
+use cucumber::{given, then, when};
+use uuid::Uuid;
+use websocket_trait::native::NativeWebSocketClient;
+use websocket_trait::protocol::VersionedProtocol;
+
+use super::AppWorld;
+
+/// Native client for the `rkyv_websocket` server function.
+pub type RkyvNativeClient = NativeWebSocketClient<Request, Response, Encoding>;
+
+/// Maximum time to wait for a response from the server.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(3);
+
+impl AppWorld {
+    /// Returns the connected native client.
+    ///
+    /// # Errors
+    /// Returns an error if no native client step connected one yet.
+    fn native_client(&mut self) -> Result<&mut RkyvNativeClient> {
+        self.native_client
+            .as_mut()
+            .ok_or_else(|| anyhow!("no native client: connect one first"))
+    }
+
+    /// Waits for the next response received by the native client.
+    ///
+    /// # Errors
+    /// - No response within `RESPONSE_TIMEOUT`
+    /// - The server closed the connection or sent an error
+    async fn native_response(&mut self) -> Result<Response> {
+        let response = tokio::time::timeout(RESPONSE_TIMEOUT, self.native_client()?.recv())
+            .await
+            .context("Timed out waiting for a response")?
+            .ok_or_else(|| anyhow!("connection closed by the server"))??;
+
+        Ok(response)
+    }
+}
+
+/// Step: Given a native websocket client is connected
+///
+/// Opens a native connection to the scenario's `rkyv_websocket` server function.
+#[given("a native websocket client is connected")]
+async fn connect_native_client(world: &mut AppWorld) -> Result<()> {
+    let server_url = format!("http://127.0.0.1:{}", world.leptos_server_port()?);
+    let client = RkyvNativeClient::connect(&server_url, RKYV_WEBSOCKET_PATH).await?;
+
+    world.native_client = Some(client);
+
+    Ok(())
+}
+
+/// Step: When the native client sends a handshake
+#[when("the native client sends a handshake")]
+async fn send_handshake(world: &mut AppWorld) -> Result<()> {
+    let request = Request::Handshake {
+        uuid: Uuid::new_v4(),
+        version: Request::VERSION,
+    };
+    world.native_client()?.send(&request).await?;
+
+    Ok(())
+}
+
+/// Step: When the native client sends a handshake with an outdated protocol version
+///
+/// Simulates a tab still running the bundle of a previous deploy.
+#[when("the native client sends a handshake with an outdated protocol version")]
+async fn send_outdated_handshake(world: &mut AppWorld) -> Result<()> {
+    let request = Request::Handshake {
+        uuid: Uuid::new_v4(),
+        version: Request::VERSION.wrapping_add(1),
+    };
+    world.native_client()?.send(&request).await?;
+
+    Ok(())
+}
+
+/// Step: Then the native client receives a handshake response
+#[then("the native client receives a handshake response")]
+async fn receive_handshake_response(world: &mut AppWorld) -> Result<()> {
+    let response = world.native_response().await?;
+
+    assert!(
+        matches!(response, Response::HandshakeResponse),
+        "Expected HandshakeResponse, got {response:?}"
+    );
+
+    Ok(())
+}
+
+/// Step: Then the native client is asked to upgrade
+#[then("the native client is asked to upgrade")]
+async fn receive_upgrade_required(world: &mut AppWorld) -> Result<()> {
+    let response = world.native_response().await?;
+
+    assert!(
+        matches!(response, Response::UpgradeRequired),
+        "Expected UpgradeRequired, got {response:?}"
+    );
+
+    Ok(())
+}
+
+/// Step: When the native client sends a ping with id N
+///
+/// # Example
+/// ```gherkin
+/// When the native client sends a ping with id 7
+/// ```
+#[when(regex = r"^the native client sends a ping with id (\d+)$")]
+async fn send_ping(world: &mut AppWorld, id: u32) -> Result<()> {
+    world.native_client()?.send(&Request::Ping { id }).await?;
+
+    Ok(())
+}
+
+/// Step: Then the native client receives a pong with id N
+#[then(regex = r"^the native client receives a pong with id (\d+)$")]
+async fn receive_pong(world: &mut AppWorld, id: u32) -> Result<()> {
+    let response = world.native_response().await?;
+
+    assert!(
+        matches!(response, Response::Pong { id: pong_id } if pong_id == id),
+        "Expected Pong with id {id}, got {response:?}"
+    );
+
+    Ok(())
+}
+{%- if compression == true %}
+
+/// Step: When the native client sends an echo of N bytes
+///
+/// # Example
+/// ```gherkin
+/// When the native client sends an echo of 4096 bytes
+/// ```
+#[when(regex = r"^the native client sends an echo of (\d+) bytes$")]
+async fn send_echo(world: &mut AppWorld, size: usize) -> Result<()> {
+    world.native_client()?.send(&Request::echo(size)).await?;
+
+    Ok(())
+}
+
+/// Step: Then the native client receives an echo of N bytes
+#[then(regex = r"^the native client receives an echo of (\d+) bytes$")]
+async fn receive_echo(world: &mut AppWorld, size: usize) -> Result<()> {
+    let response = world.native_response().await?;
+
+    assert!(
+        matches!(&response, Response::Echo { text } if text.len() == size),
+        "Expected Echo of {size} bytes, got {response:?}"
+    );
+
+    Ok(())
+}
+{%- endif %}
+
+/// Step: When the native client closes the connection
+#[when("the native client closes the connection")]
+async fn close_native_client(world: &mut AppWorld) -> Result<()> {
+    let client = world
+        .native_client
+        .take()
+        .ok_or_else(|| anyhow!("no native client: connect one first"))?;
+
+    client.close().await?;
+
+    Ok(())
+}
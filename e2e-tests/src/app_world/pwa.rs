@@ -0,0 +1,53 @@
+//! Progressive Web App steps.
+
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use cucumber::then;
+use serde_json::Value;
+
+use super::AppWorld;
+
+/// Maximum time to wait for the service worker to install and activate.
+const ACTIVATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Step: Then the service worker is active
+///
+/// Waits for the worker registered by `/pwa.js` to precache the assets and
+/// activate.
+#[then("the service worker is active")]
+async fn service_worker_active(world: &mut AppWorld) -> Result<()> {
+    wait_for(
+        world,
+        "return navigator.serviceWorker.getRegistration()\
+            .then((registration) => Boolean(registration && registration.active));",
+    )
+    .await
+    .map_err(|_| anyhow!("Timed out waiting for the service worker to activate"))
+}
+
+/// Step: Then the service worker controls the page
+///
+/// A page is controlled by the worker active when it was loaded, so this
+/// needs a reload after the first visit.
+#[then("the service worker controls the page")]
+async fn service_worker_controls_page(world: &mut AppWorld) -> Result<()> {
+    wait_for(world, "return Boolean(navigator.serviceWorker.controller);")
+        .await
+        .map_err(|_| anyhow!("The page is not controlled by the service worker"))
+}
+
+/// Polls `script` until it returns `true`.
+async fn wait_for(world: &mut AppWorld, script: &str) -> Result<()> {
+    let tick_duration = ACTIVATION_TIMEOUT / 20;
+
+    for _ in 0..20 {
+        if world.execute(script, vec![]).await? == Value::Bool(true) {
+            return Ok(());
+        }
+
+        tokio::time::sleep(tick_duration).await;
+    }
+
+    Err(anyhow!("Timed out"))
+}
@@ -0,0 +1,135 @@
+//! Session recording and replay steps.
+//!
+//! Records a session of the app's websocket handler to a temporary directory,
+//! then replays it with the generated `rkyv_websocket_replay`.
+
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use app::pages::home::ws::handler::RkyvWebSocketMessage;
+use app::pages::home::ws::message::{Encoding, Request, Response, rkyv_websocket_replay};
+use cucumber::{given, then, when};
+use futures::channel::mpsc;
+use uuid::Uuid;
+use websocket_trait::protocol::VersionedProtocol;
+use websocket_trait::server::GenericWebsocketBackend;
+
+use super::AppWorld;
+
+impl AppWorld {
+    /// Returns the session log written by the recording steps.
+    ///
+    /// # Errors
+    /// Returns an error if no session was recorded yet.
+    fn recording(&self) -> Result<&Path> {
+        self.recording
+            .as_deref()
+            .ok_or_else(|| anyhow!("no recording: record a session first"))
+    }
+}
+
+/// Step: Given a recorded session with a handshake and a ping with id N
+///
+/// Serves a handshake and a ping through a recording backend, like the
+/// `rkyv_websocket` server function does when `WEBSOCKET_RECORD_DIR` is set.
+#[given(regex = r"^a recorded session with a handshake and a ping with id (\d+)$")]
+async fn record_session(world: &mut AppWorld, id: u32) -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("websocket-recordings-{}", Uuid::new_v4()));
+
+    let (request_tx, request_rx) = mpsc::unbounded();
+    request_tx.unbounded_send(Ok(Request::Handshake {
+        uuid: Uuid::new_v4(),
+        version: Request::VERSION,
+    }))?;
+    request_tx.unbounded_send(Ok(Request::Ping { id }))?;
+    drop(request_tx);
+
+    let (tx, _rx) = mpsc::unbounded();
+    GenericWebsocketBackend::new(request_rx.into(), tx, RkyvWebSocketMessage)
+        .with_version_check()
+        .with_recorder::<Encoding>(&dir)
+        .serve()
+        .await;
+
+    let log = std::fs::read_dir(&dir)?
+        .next()
+        .ok_or_else(|| anyhow!("no session log in {}", dir.display()))??;
+    world.recording = Some(log.path());
+
+    Ok(())
+}
+
+/// Step: When the recording expects a pong with id N
+///
+/// Edits the recorded responses, simulating a handler whose behavior changed
+/// since the recording.
+#[when(regex = r"^the recording expects a pong with id (\d+)$")]
+async fn edit_recorded_pong(world: &mut AppWorld, id: u32) -> Result<()> {
+    let path = world.recording()?;
+    let log = std::fs::read_to_string(path)?;
+
+    let edited = log
+        .lines()
+        .map(|line| match line.split_once(" < Pong") {
+            Some((timestamp, _)) => format!("{timestamp} < {:?}", Response::Pong { id }),
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(path, edited)?;
+
+    Ok(())
+}
+
+/// Step: When the recorded session is replayed
+#[when("the recorded session is replayed")]
+async fn replay_session(world: &mut AppWorld) -> Result<()> {
+    let report = rkyv_websocket_replay(world.recording()?).await?;
+    world.replay_report = Some(report);
+
+    Ok(())
+}
+
+/// Step: Then the replayed responses match the recording
+#[then("the replayed responses match the recording")]
+async fn replay_matches(world: &mut AppWorld) -> Result<()> {
+    let report = world
+        .replay_report
+        .as_ref()
+        .ok_or_else(|| anyhow!("no replay: replay the session first"))?;
+
+    assert!(!report.expected.is_empty(), "Nothing was recorded");
+    assert!(
+        report.is_match(),
+        "Replay differs from the recording:\n{report}"
+    );
+
+    Ok(())
+}
+
+/// Step: Then the replay reports a pong with id N instead of id M
+///
+/// # Example
+/// ```gherkin
+/// Then the replay reports a pong with id 7 instead of id 8
+/// ```
+#[then(regex = r"^the replay reports a pong with id (\d+) instead of id (\d+)$")]
+async fn replay_differs(world: &mut AppWorld, actual: u32, expected: u32) -> Result<()> {
+    let report = world
+        .replay_report
+        .as_ref()
+        .ok_or_else(|| anyhow!("no replay: replay the session first"))?;
+    let diff = report.to_string();
+
+    assert!(
+        !report.is_match(),
+        "Replay unexpectedly matches the recording"
+    );
+    assert!(
+        diff.contains(&format!("- {:?}", Response::Pong { id: expected }))
+            && diff.contains(&format!("+ {:?}", Response::Pong { id: actual })),
+        "Unexpected replay report:\n{diff}"
+    );
+
+    Ok(())
+}
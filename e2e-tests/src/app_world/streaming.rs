@@ -0,0 +1,63 @@
+//! Streaming demo steps.
+
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use cucumber::then;
+use fantoccini::Locator;
+
+use super::AppWorld;
+
+/// Maximum time to wait for a report, loaded in 2.5s by the server.
+const REPORT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Step: Then I see the report "Report 2"
+///
+/// Waits for the heading of the report to show this title.
+#[then(regex = r#"^I see the report "([^"]+)"$"#)]
+async fn see_report(world: &mut AppWorld, expected: String) -> Result<()> {
+    let tick_duration = REPORT_TIMEOUT / 25;
+    let mut latest = None;
+
+    for _ in 0..25 {
+        let title = report_title(world).await?;
+        if title.as_deref() == Some(&expected) {
+            return Ok(());
+        }
+
+        latest = title;
+
+        tokio::time::sleep(tick_duration).await;
+    }
+
+    Err(anyhow!(
+        "Timed out waiting for {expected:?}, latest report: {latest:?}"
+    ))
+}
+
+/// Step: Then I still see the report "Report 1"
+///
+/// Checks right away that the previous report is shown while the next one
+/// loads, rather than the fallback of the `<Transition>`.
+#[then(regex = r#"^I still see the report "([^"]+)"$"#)]
+async fn still_see_report(world: &mut AppWorld, expected: String) -> Result<()> {
+    let title = report_title(world).await?;
+
+    assert_eq!(
+        title.as_deref(),
+        Some(expected.as_str()),
+        "Unexpected report"
+    );
+
+    Ok(())
+}
+
+/// Returns the title of the report on the page, if any.
+async fn report_title(world: &mut AppWorld) -> Result<Option<String>> {
+    let headings = world.webdriver.client.find_all(Locator::Css("h2")).await?;
+
+    match headings.first() {
+        Some(heading) => Ok(Some(heading.text().await?)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,115 @@
+//! Theme steps.
+//!
+//! Every scenario has its own browser session, hence its own theme cookie.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use cucumber::{then, when};
+use fantoccini::Locator;
+
+use super::AppWorld;
+
+/// Maximum time to wait for the page to switch theme.
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Step: When I choose the "Dark" theme
+///
+/// Clicks the button of the theme toggle with this label.
+///
+/// # Example
+/// ```gherkin
+/// When I choose the "Light" theme
+/// ```
+#[when(regex = r#"^I choose the "([^"]+)" theme$"#)]
+async fn choose_theme(world: &mut AppWorld, label: String) -> Result<()> {
+    for button in world
+        .webdriver
+        .client
+        .find_all(Locator::Css("form.theme-toggle button"))
+        .await?
+    {
+        if button.text().await? == label {
+            button.click().await?;
+            return Ok(());
+        }
+    }
+
+    Err(anyhow!("No {label:?} button in the theme toggle"))
+}
+
+/// Step: When I reload the page
+#[when("I reload the page")]
+async fn reload_page(world: &mut AppWorld) -> Result<()> {
+    world
+        .webdriver
+        .client
+        .refresh()
+        .await
+        .context("Failed to reload the page")?;
+
+    Ok(())
+}
+
+/// Step: Then the response renders the "dark" theme
+///
+/// Checks the `data-theme` attribute of `<html>` in the response of the
+/// server, i.e. before any script runs.
+#[then(regex = r#"^the response renders the "([^"]+)" theme$"#)]
+async fn response_renders_theme(world: &mut AppWorld, expected: String) -> Result<()> {
+    let response = world.response.as_ref().context("No request sent")?;
+    let attribute = format!("data-theme=\"{expected}\"");
+
+    assert!(
+        response.body.contains(&attribute),
+        "{attribute:?} not found in the response:\n{}",
+        response.body
+    );
+
+    Ok(())
+}
+
+/// Step: Then the page uses the "dark" theme
+///
+/// Checks the `data-theme` attribute of `<html>`, and that the toggle shows
+/// the same theme as pressed.
+///
+/// # Example
+/// ```gherkin
+/// Then the page uses the "system" theme
+/// ```
+#[then(regex = r#"^the page uses the "([^"]+)" theme$"#)]
+async fn page_uses_theme(world: &mut AppWorld, expected: String) -> Result<()> {
+    let tick_duration = UPDATE_TIMEOUT / 5;
+    let mut latest = (None, None);
+
+    for _ in 0..5 {
+        let client = &world.webdriver.client;
+        let theme = client
+            .find(Locator::Css("html"))
+            .await?
+            .attr("data-theme")
+            .await?;
+        let pressed = match client
+            .find(Locator::Css(
+                "form.theme-toggle button[aria-pressed='true']",
+            ))
+            .await
+        {
+            Ok(button) => button.attr("value").await?,
+            Err(_) => None,
+        };
+
+        if theme.as_deref() == Some(&expected) && pressed.as_deref() == Some(&expected) {
+            return Ok(());
+        }
+
+        latest = (theme, pressed);
+
+        tokio::time::sleep(tick_duration).await;
+    }
+
+    Err(anyhow!(
+        "Timed out waiting for the {expected} theme\n\nLatest data-theme and pressed button: {latest:?}"
+    ))
+}
@@ -0,0 +1,215 @@
+//! Todos page steps.
+//!
+//! Every scenario's server has its own in-memory database, so each
+//! scenario starts with an empty list.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use cucumber::{then, when};
+use fantoccini::Locator;
+
+use super::AppWorld;
+
+/// Maximum time to wait for the list to reflect a change.
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A todo as shown by the page.
+#[derive(Debug, PartialEq)]
+struct ListedTodo {
+    title: String,
+    completed: bool,
+}
+
+impl AppWorld {
+    /// Returns the todos listed by the page, in order.
+    async fn listed_todos(&mut self) -> Result<Vec<ListedTodo>> {
+        let mut todos = Vec::new();
+
+        for item in self
+            .webdriver
+            .client
+            .find_all(Locator::Css("li.todo"))
+            .await?
+        {
+            let title = item.find(Locator::Css("span.title")).await?.text().await?;
+            let class = item.attr("class").await?.unwrap_or_default();
+
+            todos.push(ListedTodo {
+                title,
+                completed: class.split_whitespace().any(|class| class == "completed"),
+            });
+        }
+
+        Ok(todos)
+    }
+
+    /// Waits until the listed todos satisfy `expected`.
+    ///
+    /// # Errors
+    /// Returns an error with the latest todos if they still do not match
+    /// after `UPDATE_TIMEOUT`.
+    async fn wait_for_todos(&mut self, expected: impl Fn(&[ListedTodo]) -> bool) -> Result<()> {
+        let tick_duration = UPDATE_TIMEOUT / 5;
+        let mut latest_todos = Vec::new();
+
+        for _ in 0..5 {
+            let todos = self.listed_todos().await?;
+            if expected(&todos) {
+                return Ok(());
+            }
+
+            latest_todos = todos;
+
+            tokio::time::sleep(tick_duration).await;
+        }
+
+        Err(anyhow!(
+            "Timed out waiting for the todos\n\nLatest todos:\n{latest_todos:#?}"
+        ))
+    }
+
+    /// Submits the form of a listed todo, e.g. `toggle` or `delete`.
+    async fn submit_todo_form(&mut self, title: &str, form: &str) -> Result<()> {
+        let xpath = format!(
+            "//li[contains(@class, 'todo')][span[@class = 'title' and text() = '{title}']]\
+             //form[contains(@class, '{form}')]//button"
+        );
+
+        self.find(Locator::XPath(&xpath))
+            .await
+            .context(format!("No {form} button for the todo {title:?}"))?
+            .click()
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Step: When I submit the title "title"
+///
+/// Fills and submits the new todo form, without waiting for the list.
+#[when(regex = r#"^I submit the title "([^"]*)"$"#)]
+async fn submit_title(world: &mut AppWorld, title: String) -> Result<()> {
+    let input = world
+        .find(Locator::Css("form.new-todo input[name='title']"))
+        .await
+        .context("No new todo form")?;
+    input.send_keys(&title).await?;
+    input.send_keys("\u{E007}").await?; // Enter
+
+    Ok(())
+}
+
+/// Step: When I add the todo "title"
+///
+/// Submits the new todo form and waits for the todo to be listed.
+///
+/// # Example
+/// ```gherkin
+/// When I add the todo "Buy milk"
+/// ```
+#[when(regex = r#"^I add the todo "([^"]+)"$"#)]
+async fn add_todo(world: &mut AppWorld, title: String) -> Result<()> {
+    submit_title(world, title.clone()).await?;
+
+    world
+        .wait_for_todos(|todos| todos.iter().any(|todo| todo.title == title))
+        .await
+}
+
+/// Step: When I mark the todo "title" as done
+///
+/// Also used to mark a completed todo as not done: both click its toggle button.
+#[when(regex = r#"^I mark the todo "([^"]+)" as (?:done|not done)$"#)]
+async fn toggle_todo(world: &mut AppWorld, title: String) -> Result<()> {
+    world.submit_todo_form(&title, "toggle").await
+}
+
+/// Step: When I delete the todo "title"
+#[when(regex = r#"^I delete the todo "([^"]+)"$"#)]
+async fn delete_todo(world: &mut AppWorld, title: String) -> Result<()> {
+    world.submit_todo_form(&title, "delete").await
+}
+
+/// Step: Then I see the todos:
+///
+/// Checks the listed titles, in order.
+///
+/// # Example
+/// ```gherkin
+/// Then I see the todos:
+///   | Buy milk     |
+///   | Walk the dog |
+/// ```
+#[then("I see the todos:")]
+async fn see_todos(world: &mut AppWorld, step: &cucumber::gherkin::Step) -> Result<()> {
+    let table = step
+        .table
+        .as_ref()
+        .ok_or_else(|| anyhow::Error::msg("Expected data table"))?;
+    let expected: Vec<&str> = table
+        .rows
+        .iter()
+        .filter_map(|row| row.first())
+        .map(String::as_str)
+        .collect();
+
+    world
+        .wait_for_todos(|todos| {
+            todos
+                .iter()
+                .map(|todo| todo.title.as_str())
+                .eq(expected.iter().copied())
+        })
+        .await
+}
+
+/// Step: Then the todo "title" is completed
+///
+/// # Example
+/// ```gherkin
+/// Then the todo "Buy milk" is completed
+/// Then the todo "Buy milk" is not completed
+/// ```
+#[then(regex = r#"^the todo "([^"]+)" is (not )?completed$"#)]
+async fn todo_is_completed(world: &mut AppWorld, title: String, not: String) -> Result<()> {
+    let completed = not.is_empty();
+
+    world
+        .wait_for_todos(|todos| {
+            todos.contains(&ListedTodo {
+                title: title.clone(),
+                completed,
+            })
+        })
+        .await
+}
+
+/// Step: Then I see the title error "message"
+///
+/// Checks the validation message shown next to the title input.
+#[then(regex = r#"^I see the title error "([^"]+)"$"#)]
+async fn see_title_error(world: &mut AppWorld, expected: String) -> Result<()> {
+    let tick_duration = UPDATE_TIMEOUT / 5;
+    let mut latest_error = None;
+
+    for _ in 0..5 {
+        if let Ok(element) = world
+            .find(Locator::Css("p.field-error[data-field='title']"))
+            .await
+        {
+            let error = element.text().await?;
+            if error == expected {
+                return Ok(());
+            }
+            latest_error = Some(error);
+        }
+
+        tokio::time::sleep(tick_duration).await;
+    }
+
+    Err(anyhow!(
+        "Timed out waiting for the error {expected:?}, latest: {latest_error:?}"
+    ))
+}
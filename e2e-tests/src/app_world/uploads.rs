@@ -0,0 +1,155 @@
+//! File upload steps.
+//!
+//! Files are written to a temporary directory, then picked through the file
+//! input of the upload page, as a user would.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use cucumber::{then, when};
+use fantoccini::Locator;
+
+use super::AppWorld;
+use crate::http_get;
+
+/// Maximum time to wait for an upload to complete or fail.
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+impl AppWorld {
+    /// Writes `content` to a file named `name`, apart for each scenario's
+    /// server, and returns its path.
+    fn upload_source(&self, name: &str, content: &[u8]) -> Result<PathBuf> {
+        let dir =
+            std::env::temp_dir().join(format!("upload-sources-{}", self.leptos_server_port()?));
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(name);
+        std::fs::write(&path, content)?;
+
+        Ok(path)
+    }
+
+    /// Picks the file at `path` and submits the upload form.
+    async fn upload(&mut self, path: PathBuf) -> Result<()> {
+        self.find(Locator::Css("form.uploader input[type='file']"))
+            .await
+            .context("No upload form")?
+            .send_keys(&path.display().to_string())
+            .await?;
+        self.find(Locator::Css("form.uploader button[type='submit']"))
+            .await?
+            .click()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Waits until the text of the first element matching `css` satisfies
+    /// `expected`.
+    ///
+    /// # Errors
+    /// Returns an error with the latest text if it still does not match
+    /// after `UPLOAD_TIMEOUT`.
+    async fn wait_for_text(&mut self, css: &str, expected: impl Fn(&str) -> bool) -> Result<()> {
+        let tick_duration = UPLOAD_TIMEOUT / 20;
+        let mut latest = None;
+
+        for _ in 0..20 {
+            if let Ok(element) = self.find(Locator::Css(css)).await {
+                let text = element.text().await?;
+                if expected(&text) {
+                    return Ok(());
+                }
+                latest = Some(text);
+            }
+
+            tokio::time::sleep(tick_duration).await;
+        }
+
+        Err(anyhow!(
+            "Timed out waiting for {css:?}, latest text: {latest:?}"
+        ))
+    }
+}
+
+/// Step: When I upload the file "name" containing "content"
+///
+/// # Example
+/// ```gherkin
+/// When I upload the file "hello.txt" containing "Hello, uploads!"
+/// ```
+#[when(regex = r#"^I upload the file "([^"]+)" containing "([^"]*)"$"#)]
+async fn upload_file(world: &mut AppWorld, name: String, content: String) -> Result<()> {
+    let path = world.upload_source(&name, content.as_bytes())?;
+
+    world.upload(path).await
+}
+
+/// Step: When I upload a file of N MB
+///
+/// Uploads a text file of this size, to check the size limit.
+#[when(regex = r"^I upload a file of (\d+) MB$")]
+async fn upload_file_of_size(world: &mut AppWorld, megabytes: usize) -> Result<()> {
+    let content = vec![b'a'; megabytes * 1024 * 1024];
+    let path = world.upload_source("large.txt", &content)?;
+
+    world.upload(path).await
+}
+
+/// Step: Then the upload progress reaches 100%
+#[then(regex = r"^the upload progress reaches (\d+)%$")]
+async fn progress_reaches(world: &mut AppWorld, percent: u64) -> Result<()> {
+    let expected = format!("{percent}%");
+
+    world
+        .wait_for_text("div.upload-progress span", |text| text == expected)
+        .await
+}
+
+/// Step: Then I see the file "name" in the list
+#[then(regex = r#"^I see the file "([^"]+)" in the list$"#)]
+async fn see_file(world: &mut AppWorld, name: String) -> Result<()> {
+    let tick_duration = UPLOAD_TIMEOUT / 20;
+
+    for _ in 0..20 {
+        if world.find(Locator::LinkText(&name)).await.is_ok() {
+            return Ok(());
+        }
+
+        tokio::time::sleep(tick_duration).await;
+    }
+
+    Err(anyhow!(
+        "Timed out waiting for the file {name:?} to be listed"
+    ))
+}
+
+/// Step: When I download the file "name"
+///
+/// Requests the link of the listed file outside of the browser, and keeps
+/// the response.
+#[when(regex = r#"^I download the file "([^"]+)"$"#)]
+async fn download_file(world: &mut AppWorld, name: String) -> Result<()> {
+    let href = world
+        .find(Locator::LinkText(&name))
+        .await
+        .context(format!("No link to the file {name:?}"))?
+        .attr("href")
+        .await?
+        .context(format!("The link to the file {name:?} has no href"))?;
+
+    let response = http_get(world.leptos_server_port()?, &href).await?;
+    world.response = Some(response);
+
+    Ok(())
+}
+
+/// Step: Then I see the upload error "message"
+///
+/// Checks the validation message shown next to the file input.
+#[then(regex = r#"^I see the upload error "([^"]+)"$"#)]
+async fn see_upload_error(world: &mut AppWorld, expected: String) -> Result<()> {
+    world
+        .wait_for_text("p.field-error[data-field='file']", |text| text == expected)
+        .await
+}
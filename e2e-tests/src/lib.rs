@@ -34,7 +34,7 @@ pub use trace::Trace;
 
 mod utils;
 pub use utils::ChromeDriver;
-use utils::{PortFinder, WebDriver};
+use utils::{HttpResponse, PortFinder, WebDriver, http_get, http_get_with_headers, http_post_form};
 
 mod run;
 pub use run::cucumber_test;
@@ -0,0 +1,166 @@
+//! Minimal HTTP client, for what the browser does not expose (e.g. status codes).
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Pause of the server after which the body received next starts a new part.
+///
+/// Half the shortest pause between the parts of the streaming demo (1s, see
+/// `SUMMARY_DELAY`), so a busy server may also stall that long within a part.
+const PART_GAP: Duration = Duration::from_millis(500);
+
+/// Response to an [`http_get`] or [`http_post_form`] request.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    /// The body, split where the server paused for more than [`PART_GAP`],
+    /// e.g. while a streamed page waits for its resources.
+    pub parts: Vec<String>,
+}
+
+impl HttpResponse {
+    /// Returns the value of a response header, ignoring the case of its name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Sends `GET path` to the local server listening on `port`.
+///
+/// Uses HTTP/1.0, so the server closes the connection after a plain
+/// (not chunked) body, even for streamed pages.
+///
+/// # Errors
+/// - The connection fails
+/// - The response has no valid status line
+///
+/// # Example
+/// ```ignore
+/// let response = http_get(3000, "/missing").await?;
+/// assert_eq!(response.status, 404);
+/// ```
+pub async fn http_get(port: u16, path: &str) -> Result<HttpResponse> {
+    http_get_with_headers(port, path, &[]).await
+}
+
+/// Sends `GET path` with extra request headers, e.g. a `Cookie`.
+///
+/// # Errors
+/// See [`http_get`].
+///
+/// # Example
+/// ```ignore
+/// let response = http_get_with_headers(3000, "/", &[("Cookie", "theme=dark")]).await?;
+/// ```
+pub async fn http_get_with_headers(
+    port: u16,
+    path: &str,
+    headers: &[(&str, &str)],
+) -> Result<HttpResponse> {
+    let mut request =
+        format!("GET {path} HTTP/1.0\r\nHost: 127.0.0.1:{port}\r\nAccept: text/html\r\n");
+    for (name, value) in headers {
+        request.push_str(&format!("{name}: {value}\r\n"));
+    }
+    request.push_str("\r\n");
+
+    send(port, &request).await
+}
+
+/// Sends `POST path` with a URL-encoded `body`, like the client of a server
+/// function (not an HTML form, which `leptos_axum` redirects).
+///
+/// # Errors
+/// See [`http_get`].
+///
+/// # Example
+/// ```ignore
+/// let response = http_post_form(3000, "/api/set_theme", "theme=dark").await?;
+/// ```
+pub async fn http_post_form(port: u16, path: &str, body: &str) -> Result<HttpResponse> {
+    let request = format!(
+        "POST {path} HTTP/1.0\r\nHost: 127.0.0.1:{port}\r\n\
+         Content-Type: application/x-www-form-urlencoded\r\n\
+         Content-Length: {}\r\n\r\n{body}",
+        body.len()
+    );
+
+    send(port, &request).await
+}
+
+/// Sends a raw HTTP/1.0 request and reads the response until the server
+/// closes the connection.
+async fn send(port: u16, request: &str) -> Result<HttpResponse> {
+    let mut stream = TcpStream::connect(("127.0.0.1", port))
+        .await
+        .context(format!("Failed to connect to port {port}"))?;
+    stream.write_all(request.as_bytes()).await?;
+
+    // Offsets in `raw` of the parts of the response
+    let mut raw = Vec::new();
+    let mut part_starts = Vec::new();
+    let mut buffer = [0; 8192];
+    let mut last_read: Option<Instant> = None;
+    loop {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        if last_read.is_none_or(|last_read| last_read.elapsed() > PART_GAP) {
+            part_starts.push(raw.len());
+        }
+        raw.extend_from_slice(&buffer[..read]);
+        last_read = Some(Instant::now());
+    }
+    let parts = split_parts(&raw, &part_starts);
+    let raw = String::from_utf8_lossy(&raw);
+    // e.g. `GET / HTTP/1.0`
+    let request_line = request.lines().next().unwrap_or_default();
+
+    // e.g. `HTTP/1.0 404 Not Found`
+    let status = raw
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| anyhow!("Invalid response to {request_line}:\n{raw}"))?;
+    let (head, body) = raw.split_once("\r\n\r\n").unwrap_or((&raw, ""));
+    let headers = head
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    Ok(HttpResponse {
+        status,
+        headers,
+        body: body.to_string(),
+        parts,
+    })
+}
+
+/// Splits the body of a raw response at `part_starts`, leaving out the
+/// status line and headers.
+fn split_parts(raw: &[u8], part_starts: &[usize]) -> Vec<String> {
+    let body_start = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .map_or(raw.len(), |head_len| head_len + 4);
+
+    part_starts
+        .iter()
+        .zip(part_starts.iter().skip(1).chain([&raw.len()]))
+        .map(|(&start, &end)| &raw[start.max(body_start)..end.max(body_start)])
+        .filter(|part| !part.is_empty())
+        .map(|part| String::from_utf8_lossy(part).into_owned())
+        .collect()
+}
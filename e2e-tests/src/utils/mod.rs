@@ -8,3 +8,6 @@ pub use webdriver::WebDriver;
 
 mod chrome_driver;
 pub use chrome_driver::ChromeDriver;
+
+mod http;
+pub use http::{HttpResponse, http_get, http_get_with_headers, http_post_form};
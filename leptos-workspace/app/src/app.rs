@@ -1,19 +1,132 @@
 use leptos::prelude::*;
-use leptos_meta::provide_meta_context;
+use leptos_meta::{Title, provide_meta_context};
+{% if i18n == true -%}
+use leptos_router::components::{ParentRoute, Route, Router, Routes};
+{% else -%}
 use leptos_router::components::{Route, Router, Routes};
-use leptos_router::{Lazy, path};
+{% endif -%}
+use leptos_router::{Lazy, MatchNestedRoutes, SsrMode, path};
 
+{% if auth == true -%}
+use crate::auth::{ProtectedRoute, provide_auth};
+{% endif -%}
+use crate::errors::{ErrorPage, NotFound};
+{% if i18n == true -%}
+use crate::i18n::{Locale, LocaleLayout, LocaleSwitcher, provide_i18n};
+{% endif -%}
 use crate::pages::*;
+{% if pwa == true -%}
+use crate::pwa::UpdatePrompt;
+{% endif -%}
+use crate::seo::SITE_NAME;
+use crate::ssg::static_page;
+use crate::theme::{ThemeHead, ThemeToggle, provide_theme};
 
 #[component]
 pub fn App() -> impl IntoView {
     provide_meta_context();
+    provide_theme();
+    {%- if i18n == true %}
+    provide_i18n();
+    {%- endif %}
+    {%- if auth == true %}
+    provide_auth();
+    {%- endif %}
 
+    // Errors thrown by a page are shown by `ErrorPage`, with their HTTP status
     view! {
+        <Title formatter=|title| format!("{title} | {SITE_NAME}") />
+        <ThemeHead />
         <Router>
-            <Routes fallback=|| "Page not found".into_view()>
-                <Route path=path!("") view={Lazy::<HomePage>::new()} />
-            </Routes>
+            <main>
+                <ErrorBoundary fallback=|errors| view! { <ErrorPage errors /> }>
+                    <Routes fallback=|| view! { <NotFound /> }>
+                        <AppRoutes />
+                        {%- if i18n == true %}
+                        <ParentRoute
+                            path=path!("en")
+                            view=|| view! { <LocaleLayout locale=Locale::En /> }
+                        >
+                            <AppRoutes />
+                        </ParentRoute>
+                        <ParentRoute
+                            path=path!("de")
+                            view=|| view! { <LocaleLayout locale=Locale::De /> }
+                        >
+                            <AppRoutes />
+                        </ParentRoute>
+                        {%- endif %}
+                    </Routes>
+                </ErrorBoundary>
+            </main>
+            <footer>
+                <ThemeToggle />
+                {%- if i18n == true %}
+                <LocaleSwitcher />
+                {%- endif %}
+                {%- if pwa == true %}
+                <UpdatePrompt />
+                {%- endif %}
+            </footer>
         </Router>
     }
 }
+
+{% if i18n == true -%}
+/// Routes of the pages, mounted at the root and under every locale prefix.
+{% else -%}
+/// Routes of the pages.
+{% endif -%}
+///
+/// `ssr` picks how a route is rendered on the server (default: `OutOfOrder`):
+///
+/// - `OutOfOrder` - Sends the page with the `Suspense` fallbacks, then each
+///   fragment as soon as its resources are loaded
+/// - `InOrder` - Sends the page up to the first pending `Suspense`, then the
+///   rest in order as it loads: no fallbacks, works without JavaScript
+/// - `PartiallyBlocked` - `OutOfOrder`, but waits for the blocking resources
+///   (`Resource::new_blocking`) before sending the page, e.g. for its `<title>`
+/// - `Async` - Waits for every resource, then sends the whole page: the status
+///   and headers set by the page are kept
+/// - `Static` - Pre-rendered when the server starts (see `crate::ssg`)
+#[component(transparent)]
+fn AppRoutes() -> impl MatchNestedRoutes + Clone {
+    view! {
+        <Route path=path!("") view={Lazy::<HomePage>::new()} />
+        <Route path=path!("about") view={Lazy::<AboutPage>::new()} ssr=static_page() />
+        {%- if websocket == true %}
+        <Route path=path!("chat") view={Lazy::<ChatPage>::new()} />
+        {%- endif %}
+        {%- if auth == true %}
+        <Route path=path!("login") view={Lazy::<LoginPage>::new()} />
+        <ProtectedRoute path=path!("account") view=AccountPage />
+        {%- endif %}
+        {%- if pwa == true %}
+        <Route path=path!("offline") view={Lazy::<OfflinePage>::new()} ssr=static_page() />
+        {%- endif %}
+        <Route path=path!("status/:code") view=StatusPage />
+        <Route path=path!("streaming") view={Lazy::<StreamingPage>::new()} ssr=SsrMode::OutOfOrder />
+        <Route
+            path=path!("streaming/in-order")
+            view={Lazy::<StreamingPage>::new()}
+            ssr=SsrMode::InOrder
+        />
+        <Route
+            path=path!("streaming/partially-blocked")
+            view={Lazy::<StreamingPage>::new()}
+            ssr=SsrMode::PartiallyBlocked
+        />
+        <Route
+            path=path!("streaming/async")
+            view={Lazy::<StreamingPage>::new()}
+            ssr=SsrMode::Async
+        />
+        {%- if database == true %}
+        <Route path=path!("todos") view={Lazy::<TodosPage>::new()} />
+        {%- endif %}
+        {%- if uploads == true %}
+        <Route path=path!("upload") view={Lazy::<UploadPage>::new()} />
+        {%- endif %}
+    }
+    .into_inner()
+}
@@ -0,0 +1,42 @@
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use super::server::{AuthState, MaybeUser};
+use super::user::User;
+use crate::errors::AppError;
+
+/// Signs in with a username and password.
+///
+/// Opens a session and sets its cookie on the response.
+///
+/// # Errors
+///
+/// * `AppError::Unauthorized` - Unknown username or wrong password
+/// * `AppError::Internal` - The session or user store failed
+#[server]
+pub async fn login(username: String, password: String) -> Result<User, AppError> {
+    let auth = AuthState::from_context()?;
+    let user = auth.login(&username, &password).await?;
+
+    tracing::info!("User signed in: {}", user.username);
+
+    Ok(user)
+}
+
+/// Signs out: closes the session, if any, and clears its cookie.
+#[server]
+pub async fn logout() -> Result<(), AppError> {
+    let auth = AuthState::from_context()?;
+    let headers: axum::http::HeaderMap = leptos_axum::extract().await?;
+    auth.logout(&headers).await?;
+
+    Ok(())
+}
+
+/// Returns the signed-in user, if any.
+#[server]
+pub async fn current_user() -> Result<Option<User>, AppError> {
+    let MaybeUser(user) = leptos_axum::extract().await?;
+
+    Ok(user)
+}
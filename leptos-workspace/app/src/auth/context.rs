@@ -0,0 +1,81 @@
+use leptos::prelude::*;
+
+use super::api::{Login, Logout, current_user};
+use super::user::User;
+use crate::errors::AppError;
+
+/// The signed-in user: `Ok(None)` for anonymous visitors.
+pub type UserResource = Resource<Result<Option<User>, AppError>>;
+
+/// Authentication state shared through context.
+#[derive(Clone, Copy)]
+pub struct Auth {
+    /// The signed-in user, refetched after every login and logout.
+    pub user: UserResource,
+
+    /// Dispatched by login forms (`<ActionForm action=auth.login>`).
+    pub login: ServerAction<Login>,
+
+    /// Dispatched by logout buttons.
+    pub logout: ServerAction<Logout>,
+}
+
+/// Creates the authentication state and provides it to every component
+/// below the caller, typically `App`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[component]
+/// pub fn App() -> impl IntoView {
+///     provide_auth();
+///     // ...
+/// }
+/// ```
+pub fn provide_auth() -> Auth {
+    let login = ServerAction::<Login>::new();
+    let logout = ServerAction::<Logout>::new();
+    let user = Resource::new(
+        move || (login.version().get(), logout.version().get()),
+        |_| current_user(),
+    );
+
+    let auth = Auth {
+        user,
+        login,
+        logout,
+    };
+    provide_context(auth);
+
+    auth
+}
+
+/// Returns the authentication state provided by `provide_auth()`.
+///
+/// # Panics
+///
+/// Panics if no ancestor called `provide_auth()`.
+pub fn use_auth() -> Auth {
+    expect_context::<Auth>()
+}
+
+/// Returns the signed-in user resource.
+///
+/// # Example
+///
+/// ```ignore
+/// let user = use_user();
+///
+/// view! {
+///     <Suspense>
+///         {move || user.get().and_then(Result::ok).flatten().map(|user| user.username)}
+///     </Suspense>
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if no ancestor called `provide_auth()`.
+pub fn use_user() -> UserResource {
+    use_auth().user
+}
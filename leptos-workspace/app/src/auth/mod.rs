@@ -0,0 +1,40 @@
+//! Authentication: users, sessions and protected routes.
+//!
+//! # Architecture
+//!
+//! - [`login`] / [`logout`] / [`current_user`] - Server functions managing the
+//!   session cookie
+//! - [`provide_auth`] / [`use_user`] - The signed-in user as a resource,
+//!   refetched after every login and logout
+//! - [`ProtectedRoute`] - Route redirecting anonymous users to [`LOGIN_PATH`]
+//! - [`server`] (SSR only) - Password hashing, pluggable session and user
+//!   stores, and the [`RequireUser`](server::RequireUser) extractor
+//!
+//! # Example
+//!
+//! ```ignore
+//! // In `App`
+//! provide_auth();
+//!
+//! view! {
+//!     <Routes fallback=|| view! { <NotFound /> }>
+//!         <Route path=path!("login") view={Lazy::<LoginPage>::new()} />
+//!         <ProtectedRoute path=path!("account") view=AccountPage />
+//!     </Routes>
+//! }
+//!
+//! // In a server function requiring a user
+//! let RequireUser(user) = leptos_axum::extract().await?;
+//! ```
+
+mod api;
+mod context;
+mod protected_route;
+#[cfg(feature = "ssr")]
+pub mod server;
+mod user;
+
+pub use api::{CurrentUser, Login, Logout, current_user, login, logout};
+pub use context::{Auth, UserResource, provide_auth, use_auth, use_user};
+pub use protected_route::{LOGIN_PATH, ProtectedRoute, is_local_path};
+pub use user::User;
@@ -0,0 +1,62 @@
+use leptos::prelude::*;
+use leptos_router::components::ProtectedRoute as GuardedRoute;
+use leptos_router::location::Url;
+use leptos_router::{MatchNestedRoutes, PossibleRouteMatch};
+
+use super::context::use_user;
+
+/// Path of the login page, where `ProtectedRoute` redirects anonymous users.
+pub const LOGIN_PATH: &str = "/login";
+
+/// Whether `path` is a path of this site, safe to redirect to after signing in.
+///
+/// Guards the `next` parameter of the login page against open redirects:
+/// browsers read `//host` and `/\host` as other sites, and drop tabs and
+/// newlines first, so `/<tab>/host` is one too.
+pub fn is_local_path(path: &str) -> bool {
+    path.starts_with('/')
+        && !path.starts_with("//")
+        && !path.chars().any(|c| c == '\\' || c.is_ascii_control())
+}
+
+/// Route only shown to signed-in users.
+///
+/// Anonymous visitors are redirected to `LOGIN_PATH`, with the requested
+/// path and query, percent-encoded, in the `next` query parameter. Nothing
+/// is shown while the user is loading.
+///
+/// Requires `provide_auth()` in an ancestor, e.g. `App`.
+///
+/// # Example
+///
+/// ```ignore
+/// <Routes fallback=|| view! { <NotFound /> }>
+///     <ProtectedRoute path=path!("account") view=AccountPage />
+/// </Routes>
+/// ```
+#[component(transparent)]
+pub fn ProtectedRoute<Segments, ViewFn, View>(
+    /// The path fragment that this route should match, e.g. `path!("account")`.
+    path: Segments,
+    /// The view for this route.
+    view: ViewFn,
+) -> impl MatchNestedRoutes + Clone + Send
+where
+    Segments: PossibleRouteMatch + Clone + Send + 'static,
+    ViewFn: Fn() -> View + Send + Clone + 'static,
+    View: IntoView + 'static,
+{
+    let user = use_user();
+    let condition = move || user.get().map(|user| matches!(user, Ok(Some(_))));
+    let redirect_path = || {
+        let location = leptos_router::hooks::use_location();
+        let mut next = location.pathname.get_untracked();
+        let search = location.search.get_untracked();
+        if !search.is_empty() {
+            next = format!("{next}?{search}");
+        }
+        format!("{LOGIN_PATH}?next={}", Url::escape(&next))
+    };
+
+    view! { <GuardedRoute path view condition redirect_path /> }.into_inner()
+}
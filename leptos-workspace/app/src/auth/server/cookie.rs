@@ -0,0 +1,42 @@
+//! Session cookie.
+
+use std::time::Duration;
+
+use axum::http::HeaderMap;
+use rand_core::{OsRng, RngCore};
+
+use crate::cookies::{SetCookie, get_cookie};
+
+/// Name of the cookie holding the session token.
+pub const SESSION_COOKIE: &str = "session";
+
+/// Returns a new random session token: 32 bytes, hex encoded.
+pub(super) fn new_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Returns the session token sent by the browser, if any.
+pub(super) fn session_token(headers: &HeaderMap) -> Option<&str> {
+    get_cookie(headers, SESSION_COOKIE).filter(|token| !token.is_empty())
+}
+
+/// Sets the session cookie on the response of the current server function.
+///
+/// `HttpOnly` keeps the token away from scripts.
+pub(super) fn set_session(token: &str, ttl: Duration) {
+    set_cookie(SetCookie::new(SESSION_COOKIE, token, ttl).http_only());
+}
+
+/// Expires the session cookie.
+pub(super) fn clear_session() {
+    set_cookie(SetCookie::expired(SESSION_COOKIE).http_only());
+}
+
+fn set_cookie(cookie: SetCookie) {
+    if let Err(e) = cookie.set() {
+        tracing::warn!("Invalid session cookie: {e}");
+    }
+}
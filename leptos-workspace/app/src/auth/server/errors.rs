@@ -0,0 +1,58 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use thiserror::Error;
+
+use crate::errors::AppError;
+
+#[derive(Debug, Error)]
+pub enum AuthError {
+    // ====== Credentials =====
+    #[error("Invalid username or password")]
+    InvalidCredentials,
+
+    #[error("Sign in required")]
+    Unauthorized,
+
+    #[error("Username {0} is taken")]
+    UsernameTaken(String),
+
+    // ====== Stores =====
+    #[error("Password hashing: {0}")]
+    PasswordHash(String),
+
+    #[error("Session store: {0}")]
+    SessionStore(String),
+
+    #[error("User store: {0}")]
+    UserStore(String),
+
+    // ====== Context =====
+    #[error("AuthState is not provided: add it with `leptos_routes_with_context`")]
+    MissingState,
+}
+
+/// Rejection of the `RequireUser` / `MaybeUser` extractors.
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            Self::InvalidCredentials | Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::UsernameTaken(_) => StatusCode::CONFLICT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        (status, self.to_string()).into_response()
+    }
+}
+
+/// Store and context errors are only logged: the client gets `AppError::Internal`.
+impl From<AuthError> for AppError {
+    fn from(error: AuthError) -> Self {
+        match error {
+            AuthError::InvalidCredentials | AuthError::Unauthorized => {
+                AppError::Unauthorized(error.to_string())
+            }
+            AuthError::UsernameTaken(_) => AppError::Conflict(error.to_string()),
+            error => AppError::internal(error),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Extractors of the signed-in user, for server functions.
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+
+use super::AuthState;
+use super::errors::AuthError;
+use crate::auth::User;
+
+/// The signed-in user; rejects anonymous requests with `AuthError::Unauthorized`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[server]
+/// pub async fn delete_account() -> Result<(), ServerFnError> {
+///     let RequireUser(user) = leptos_axum::extract().await?;
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RequireUser(pub User);
+
+/// The signed-in user, if any.
+#[derive(Debug, Clone)]
+pub struct MaybeUser(pub Option<User>);
+
+/// Reads `AuthState` from the Leptos context, so both only work inside
+/// server functions and SSR (e.g. through `leptos_axum::extract`).
+impl<S: Send + Sync> FromRequestParts<S> for MaybeUser {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let auth = AuthState::from_context()?;
+
+        Ok(Self(auth.user(&parts.headers).await?))
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for RequireUser {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let MaybeUser(user) = MaybeUser::from_request_parts(parts, state).await?;
+
+        user.map(Self).ok_or(AuthError::Unauthorized)
+    }
+}
@@ -0,0 +1,152 @@
+//! Server side of authentication (SSR only).
+//!
+//! [`AuthState`] bundles the stores and is provided to server functions
+//! through the Leptos context by the server:
+//!
+//! ```ignore
+//! let auth = AuthState::new(MemorySessionStore::default(), users);
+//!
+//! Router::new().leptos_routes_with_context(
+//!     &leptos_options,
+//!     routes,
+//!     move || provide_context(auth.clone()),
+//!     move || shell(leptos_options.clone()),
+//! )
+//! ```
+
+mod cookie;
+mod errors;
+mod extract;
+mod password;
+mod session;
+mod users;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use axum::http::HeaderMap;
+use leptos::prelude::use_context;
+
+pub use cookie::SESSION_COOKIE;
+pub use errors::AuthError;
+pub use extract::{MaybeUser, RequireUser};
+pub use password::{hash_password, verify_password};
+pub use session::{MemorySessionStore, Session, SessionStore};
+pub use users::{Account, MemoryUserStore, UserStore};
+
+use super::User;
+
+/// Future returned by the store traits.
+pub type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, AuthError>> + Send + 'a>>;
+
+/// Lifetime of a session, unless set with `with_session_ttl`.
+pub const DEFAULT_SESSION_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Stores backing authentication, cheap to clone.
+#[derive(Clone)]
+pub struct AuthState {
+    sessions: Arc<dyn SessionStore>,
+    users: Arc<dyn UserStore>,
+    session_ttl: Duration,
+}
+
+impl AuthState {
+    /// Creates the state from a session store and a user store.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let users = MemoryUserStore::default();
+    /// users.add("admin", &admin_password)?;
+    ///
+    /// let auth = AuthState::new(MemorySessionStore::default(), users);
+    /// ```
+    pub fn new(sessions: impl SessionStore, users: impl UserStore) -> Self {
+        Self {
+            sessions: Arc::new(sessions),
+            users: Arc::new(users),
+            session_ttl: DEFAULT_SESSION_TTL,
+        }
+    }
+
+    /// Sets how long a session lasts after login.
+    pub fn with_session_ttl(mut self, ttl: Duration) -> Self {
+        self.session_ttl = ttl;
+        self
+    }
+
+    /// Returns the state provided to the current server function.
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::MissingState` - The server did not provide it
+    pub fn from_context() -> Result<Self, AuthError> {
+        use_context::<Self>().ok_or(AuthError::MissingState)
+    }
+
+    /// Checks the credentials, opens a session and sets its cookie.
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::InvalidCredentials` - Unknown username or wrong password
+    /// * `AuthError::SessionStore` / `AuthError::UserStore` - A store failed
+    pub async fn login(&self, username: &str, password: &str) -> Result<User, AuthError> {
+        // Hashes even for unknown usernames, which would answer faster otherwise
+        let account = self.users.find(username).await?;
+        let hash = account
+            .as_ref()
+            .map(|account| account.password_hash.as_str());
+        if !password::verify_login(password, hash).await {
+            return Err(AuthError::InvalidCredentials);
+        }
+        let account = account.ok_or(AuthError::InvalidCredentials)?;
+
+        let token = cookie::new_token();
+        let session = Session {
+            user: account.user.clone(),
+            expires_at: SystemTime::now() + self.session_ttl,
+        };
+        self.sessions.insert(token.clone(), session).await?;
+        cookie::set_session(&token, self.session_ttl);
+
+        Ok(account.user)
+    }
+
+    /// Closes the session of the request, if any, and clears its cookie.
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::SessionStore` - The store failed
+    pub async fn logout(&self, headers: &HeaderMap) -> Result<(), AuthError> {
+        if let Some(token) = cookie::session_token(headers) {
+            self.sessions.remove(token).await?;
+        }
+        cookie::clear_session();
+
+        Ok(())
+    }
+
+    /// Returns the user of the request's session, if valid.
+    ///
+    /// Expired sessions are removed.
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::SessionStore` - The store failed
+    pub async fn user(&self, headers: &HeaderMap) -> Result<Option<User>, AuthError> {
+        let Some(token) = cookie::session_token(headers) else {
+            return Ok(None);
+        };
+
+        match self.sessions.get(token).await? {
+            Some(session) if session.expires_at > SystemTime::now() => Ok(Some(session.user)),
+            Some(_) => {
+                self.sessions.remove(token).await?;
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+}
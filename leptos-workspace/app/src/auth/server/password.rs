@@ -0,0 +1,83 @@
+//! Argon2 password hashing.
+
+use argon2::Argon2;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use rand_core::OsRng;
+
+use super::errors::AuthError;
+
+/// Hash of no account's password, with the parameters of `hash_password()`.
+///
+/// Checked when the username is unknown, so that it takes as long as a
+/// wrong password and the response time does not reveal which accounts exist.
+const DUMMY_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$pOyJ9bZt+mUJ/VFqLemYtg$4snB0W9ffiFCXG9sFlV06RS+Vmy2TGw4hJsUq0Fiz0k";
+
+/// Hashes a password with a random salt, in the PHC string format.
+///
+/// # Errors
+///
+/// * `AuthError::PasswordHash` - Hashing failed
+///
+/// # Example
+///
+/// ```ignore
+/// let hash = hash_password("correct horse battery staple")?;
+/// assert!(verify_password("correct horse battery staple", &hash));
+/// ```
+pub fn hash_password(password: &str) -> Result<String, AuthError> {
+    let salt = SaltString::generate(&mut OsRng);
+
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| AuthError::PasswordHash(e.to_string()))
+}
+
+/// Checks a password against a hash of `hash_password()`.
+///
+/// Malformed hashes never match. Blocks for the time of a hash: see
+/// `verify_login()` in async code.
+pub fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .and_then(|hash| Argon2::default().verify_password(password.as_bytes(), &hash))
+        .is_ok()
+}
+
+/// Checks the password of a login attempt on a blocking thread, against
+/// the hash of its account, or `DUMMY_HASH` without account (never matches).
+pub(super) async fn verify_login(password: &str, hash: Option<&str>) -> bool {
+    let known = hash.is_some();
+    let password = password.to_string();
+    let hash = hash.unwrap_or(DUMMY_HASH).to_string();
+
+    let verified = tokio::task::spawn_blocking(move || verify_password(&password, &hash))
+        .await
+        .unwrap_or(false);
+
+    known && verified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_the_hashed_password_only() {
+        let hash = hash_password("correct horse").unwrap();
+
+        assert!(verify_password("correct horse", &hash));
+        assert!(!verify_password("wrong horse", &hash));
+        assert!(!verify_password("correct horse", "not a hash"));
+    }
+
+    #[test]
+    fn dummy_hash_costs_as_much_as_a_real_one() {
+        let hash = hash_password("correct horse").unwrap();
+        let hash = PasswordHash::new(&hash).unwrap();
+        let dummy = PasswordHash::new(DUMMY_HASH).unwrap();
+
+        assert_eq!(dummy.algorithm, hash.algorithm);
+        assert_eq!(dummy.version, hash.version);
+        assert_eq!(dummy.params, hash.params);
+    }
+}
@@ -0,0 +1,85 @@
+//! Session stores.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::SystemTime;
+
+use super::StoreFuture;
+use crate::auth::User;
+
+/// A signed-in browser.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub user: User,
+
+    /// After this instant, the session is ignored and removed.
+    pub expires_at: SystemTime,
+}
+
+/// Storage of the open sessions, by token.
+///
+/// Implement it to keep sessions across restarts or share them between
+/// instances, e.g. in a database or Redis, and pass it to `AuthState::new`.
+///
+/// # Example
+///
+/// ```ignore
+/// impl SessionStore for RedisSessionStore {
+///     fn insert(&self, token: String, session: Session) -> StoreFuture<'_, ()> {
+///         Box::pin(async move {
+///             // SET session:{token} ... EXAT expires_at
+///             Ok(())
+///         })
+///     }
+///     // ...
+/// }
+/// ```
+pub trait SessionStore: Send + Sync + 'static {
+    /// Stores a new session.
+    fn insert(&self, token: String, session: Session) -> StoreFuture<'_, ()>;
+
+    /// Returns the session of a token, expired or not.
+    fn get<'a>(&'a self, token: &'a str) -> StoreFuture<'a, Option<Session>>;
+
+    /// Removes a session; unknown tokens are ignored.
+    fn remove<'a>(&'a self, token: &'a str) -> StoreFuture<'a, ()>;
+}
+
+/// In-memory session store: sessions are lost on restart.
+#[derive(Debug, Default)]
+pub struct MemorySessionStore {
+    sessions: Mutex<HashMap<String, Session>>,
+}
+
+impl SessionStore for MemorySessionStore {
+    fn insert(&self, token: String, session: Session) -> StoreFuture<'_, ()> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+
+        // Sessions of users who never signed out would pile up otherwise
+        let now = SystemTime::now();
+        sessions.retain(|_, session| session.expires_at > now);
+        sessions.insert(token, session);
+
+        Box::pin(async { Ok(()) })
+    }
+
+    fn get<'a>(&'a self, token: &'a str) -> StoreFuture<'a, Option<Session>> {
+        let session = self
+            .sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(token)
+            .cloned();
+
+        Box::pin(async move { Ok(session) })
+    }
+
+    fn remove<'a>(&'a self, token: &'a str) -> StoreFuture<'a, ()> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(token);
+
+        Box::pin(async { Ok(()) })
+    }
+}
@@ -0,0 +1,80 @@
+//! User stores.
+
+use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock};
+
+use super::StoreFuture;
+use super::errors::AuthError;
+use super::password::hash_password;
+use crate::auth::User;
+
+/// A user with their password hash, as stored.
+#[derive(Debug, Clone)]
+pub struct Account {
+    pub user: User,
+
+    /// PHC string of `hash_password()`.
+    pub password_hash: String,
+}
+
+/// Storage of the accounts allowed to sign in.
+///
+/// Implement it to read accounts from a database, and pass it to
+/// `AuthState::new`.
+pub trait UserStore: Send + Sync + 'static {
+    /// Returns the account of a username, if any.
+    fn find<'a>(&'a self, username: &'a str) -> StoreFuture<'a, Option<Account>>;
+}
+
+/// In-memory user store, filled with `add()`.
+#[derive(Debug, Default)]
+pub struct MemoryUserStore {
+    accounts: RwLock<HashMap<String, Account>>,
+}
+
+impl MemoryUserStore {
+    /// Adds an account, hashing its password.
+    ///
+    /// # Errors
+    ///
+    /// * `AuthError::UsernameTaken` - An account already uses this username
+    /// * `AuthError::PasswordHash` - Hashing failed
+    pub fn add(&self, username: &str, password: &str) -> Result<User, AuthError> {
+        let password_hash = hash_password(password)?;
+        let mut accounts = self
+            .accounts
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if accounts.contains_key(username) {
+            return Err(AuthError::UsernameTaken(username.to_string()));
+        }
+
+        let user = User {
+            id: accounts.len() as u64 + 1,
+            username: username.to_string(),
+        };
+        accounts.insert(
+            username.to_string(),
+            Account {
+                user: user.clone(),
+                password_hash,
+            },
+        );
+
+        Ok(user)
+    }
+}
+
+impl UserStore for MemoryUserStore {
+    fn find<'a>(&'a self, username: &'a str) -> StoreFuture<'a, Option<Account>> {
+        let account = self
+            .accounts
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(username)
+            .cloned();
+
+        Box::pin(async move { Ok(account) })
+    }
+}
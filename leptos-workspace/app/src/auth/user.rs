@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// A signed-in user, as seen by the app.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct User {
+    /// Unique and stable identifier.
+    pub id: u64,
+
+    pub username: String,
+}
@@ -0,0 +1,163 @@
+//! Cookies of the request, and `Set-Cookie` headers of the response (SSR only).
+//!
+//! # Example
+//!
+//! ```ignore
+//! // While rendering, or in a server function
+//! let theme = request_cookie(THEME_COOKIE).and_then(|value| value.parse().ok());
+//!
+//! // In a server function
+//! SetCookie::new(THEME_COOKIE, "dark", Duration::from_secs(3600))
+//!     .set()
+//!     .map_err(AppError::internal)?;
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use http::header::{COOKIE, InvalidHeaderValue, SET_COOKIE};
+use http::request::Parts;
+use http::{HeaderMap, HeaderValue};
+use leptos::prelude::use_context;
+use leptos_axum::ResponseOptions;
+
+/// Returns the value of the cookie `name` in the `Cookie` headers.
+pub fn get_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .flat_map(|header| header.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(cookie, _)| *cookie == name)
+        .map(|(_, value)| value)
+}
+
+/// Returns the value of the cookie `name` sent with the request being
+/// rendered or handled, if any.
+pub fn request_cookie(name: &str) -> Option<String> {
+    let parts = use_context::<Parts>()?;
+
+    get_cookie(&parts.headers, name).map(str::to_string)
+}
+
+/// Cookie set on the response being built, for the whole site.
+///
+/// `SameSite=Lax` keeps it out of cross-site form posts. Add `Secure` when
+/// serving over HTTPS only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetCookie<'a> {
+    name: &'a str,
+    value: &'a str,
+    max_age: Duration,
+    http_only: bool,
+}
+
+impl<'a> SetCookie<'a> {
+    /// Cookie kept by the browser for `max_age`.
+    pub fn new(name: &'a str, value: &'a str, max_age: Duration) -> Self {
+        Self {
+            name,
+            value,
+            max_age,
+            http_only: false,
+        }
+    }
+
+    /// Removes the cookie `name` from the browser.
+    pub fn expired(name: &'a str) -> Self {
+        Self::new(name, "", Duration::ZERO)
+    }
+
+    /// Hides the cookie from scripts, e.g. for a secret token.
+    pub fn http_only(mut self) -> Self {
+        self.http_only = true;
+        self
+    }
+
+    /// Appends the `Set-Cookie` header to the response.
+    ///
+    /// Only logs a warning outside of a request (no `ResponseOptions`).
+    ///
+    /// # Errors
+    ///
+    /// The name or value holds characters not allowed in a header.
+    pub fn set(&self) -> Result<(), InvalidHeaderValue> {
+        let value = HeaderValue::from_str(&self.to_string())?;
+
+        match use_context::<ResponseOptions>() {
+            Some(response) => response.append_header(SET_COOKIE, value),
+            None => tracing::warn!("No ResponseOptions: cookie {} not set", self.name),
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats the value of the `Set-Cookie` header.
+impl fmt::Display for SetCookie<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}; Path=/;", self.name, self.value)?;
+        if self.http_only {
+            f.write_str(" HttpOnly;")?;
+        }
+        write!(f, " SameSite=Lax; Max-Age={}", self.max_age.as_secs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(cookies: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for cookie in cookies {
+            headers.append(COOKIE, HeaderValue::from_str(cookie).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn finds_a_cookie_among_others() {
+        let headers = headers(&["theme=dark; locale=de", "session=abc"]);
+
+        assert_eq!(get_cookie(&headers, "theme"), Some("dark"));
+        assert_eq!(get_cookie(&headers, "locale"), Some("de"));
+        assert_eq!(get_cookie(&headers, "session"), Some("abc"));
+        assert_eq!(get_cookie(&headers, "missing"), None);
+    }
+
+    #[test]
+    fn names_match_exactly() {
+        let headers = headers(&["mytheme=light; theme_old=dark"]);
+
+        assert_eq!(get_cookie(&headers, "theme"), None);
+    }
+
+    #[test]
+    fn empty_values_are_kept() {
+        let headers = headers(&["session="]);
+
+        assert_eq!(get_cookie(&headers, "session"), Some(""));
+    }
+
+    #[test]
+    fn formats_the_set_cookie_header() {
+        let max_age = Duration::from_secs(3600);
+
+        assert_eq!(
+            SetCookie::new("theme", "dark", max_age).to_string(),
+            "theme=dark; Path=/; SameSite=Lax; Max-Age=3600"
+        );
+        assert_eq!(
+            SetCookie::new("session", "abc", max_age)
+                .http_only()
+                .to_string(),
+            "session=abc; Path=/; HttpOnly; SameSite=Lax; Max-Age=3600"
+        );
+        assert_eq!(
+            SetCookie::expired("session").http_only().to_string(),
+            "session=; Path=/; HttpOnly; SameSite=Lax; Max-Age=0"
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use thiserror::Error;
+
+use crate::errors::AppError;
+
+#[derive(Debug, Error)]
+pub enum DatabaseError {
+    // ====== SQLx =====
+    #[error("Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+
+    #[error("Migration: {0}")]
+    Migrate(#[from] sqlx::migrate::MigrateError),
+
+    // ====== Context =====
+    #[error("SqlitePool is not provided: add it with `leptos_routes_with_context`")]
+    MissingPool,
+}
+
+/// Details are only logged: the client gets `AppError::Internal`.
+impl From<DatabaseError> for AppError {
+    fn from(error: DatabaseError) -> Self {
+        AppError::internal(error)
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        AppError::internal(error)
+    }
+}
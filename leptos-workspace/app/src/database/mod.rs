@@ -0,0 +1,79 @@
+//! SQLite connection pool and migrations (SSR only).
+//!
+//! The server connects at startup and provides the pool to server functions
+//! through the Leptos context:
+//!
+//! ```ignore
+//! #[server]
+//! pub async fn count_todos() -> Result<i64, ServerFnError> {
+//!     let pool = use_pool()?;
+//!     let count = sqlx::query_scalar("SELECT COUNT(*) FROM todos")
+//!         .fetch_one(&pool)
+//!         .await?;
+//!
+//!     Ok(count)
+//! }
+//! ```
+//!
+//! Queries are checked at runtime (`sqlx::query`, not `sqlx::query!`), so
+//! building needs neither a database nor `DATABASE_URL`.
+
+mod errors;
+
+use std::str::FromStr;
+
+use leptos::prelude::use_context;
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+
+pub use errors::DatabaseError;
+pub use sqlx::SqlitePool;
+
+/// Database used when `DATABASE_URL` is not set, relative to the working directory.
+pub const DEFAULT_DATABASE_URL: &str = "sqlite://app.db";
+
+/// Migrations of `app/migrations`, embedded at compile time.
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Opens the database, creating the file if missing, and runs the pending
+/// migrations.
+///
+/// # Errors
+///
+/// * `DatabaseError::Sqlx` - Invalid URL or the database can not be opened
+/// * `DatabaseError::Migrate` - A migration failed
+///
+/// # Example
+///
+/// ```ignore
+/// let pool = connect("sqlite://app.db").await?;
+///
+/// // A new, empty database, e.g. for tests
+/// let pool = connect("sqlite::memory:").await?;
+/// ```
+pub async fn connect(url: &str) -> Result<SqlitePool, DatabaseError> {
+    let options = SqliteConnectOptions::from_str(url)?
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal);
+
+    // Never close the last connection: an in-memory database lives as long as one is open
+    let pool = SqlitePoolOptions::new()
+        .min_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect_with(options)
+        .await?;
+
+    MIGRATOR.run(&pool).await?;
+
+    Ok(pool)
+}
+
+/// Returns the pool provided to the current server function.
+///
+/// # Errors
+///
+/// * `DatabaseError::MissingPool` - The server did not provide it
+pub fn use_pool() -> Result<SqlitePool, DatabaseError> {
+    use_context::<SqlitePool>().ok_or(DatabaseError::MissingPool)
+}
@@ -0,0 +1,156 @@
+use std::fmt;
+
+use http::StatusCode;
+use leptos::prelude::*;
+use leptos::server_fn::codec::JsonEncoding;
+use leptos::server_fn::{Bytes, Encodes};
+use serde::{Deserialize, Serialize};
+
+use super::field_errors::FieldErrors;
+
+/// Error of the app's server functions, serialized as is to the client.
+///
+/// Use it as the error type of a server function, instead of
+/// `ServerFnError`:
+///
+/// ```ignore
+/// #[server]
+/// pub async fn rename(id: i64, name: String) -> Result<(), AppError> {
+///     if name.is_empty() {
+///         return Err(FieldErrors::new().with("name", "Name can not be empty").into());
+///     }
+///     // ...
+/// }
+/// ```
+///
+/// Errors of the server function itself become `AppError::BadRequest` when
+/// its arguments can not be decoded, `AppError::Internal` otherwise (e.g.
+/// the server is unreachable), whose details are only logged. A server function responds with the status of
+/// its error, see `AppError::status_code`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AppError {
+    /// What was not found, e.g. `Todo 3`.
+    NotFound(String),
+
+    /// The request needs a signed-in user, or the credentials are wrong.
+    Unauthorized(String),
+
+    /// Invalid input, per form field.
+    Validation(FieldErrors),
+
+    /// The request itself is malformed, e.g. missing or undecodable server
+    /// function arguments.
+    BadRequest(String),
+
+    /// The request conflicts with the current state, e.g. a taken username.
+    Conflict(String),
+
+    /// Anything else: the details are only logged by the server.
+    Internal(String),
+}
+
+impl AppError {
+    /// Wraps an unexpected error: logs it (SSR), and only keeps a generic
+    /// message for the client.
+    pub fn internal(error: impl fmt::Display) -> Self {
+        #[cfg(feature = "ssr")]
+        tracing::error!("Internal error: {error}");
+        #[cfg(not(feature = "ssr"))]
+        let _ = error;
+
+        Self::Internal("Internal server error".to_string())
+    }
+
+    /// HTTP status of the error, used by `ErrorPage` and server function
+    /// responses.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Validation message of a form field, if any.
+    pub fn field(&self, field: &str) -> Option<&str> {
+        match self {
+            Self::Validation(errors) => errors.get(field),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(what) => write!(f, "{what} not found"),
+            Self::Validation(errors) => write!(f, "Invalid input: {errors}"),
+            Self::Unauthorized(message)
+            | Self::BadRequest(message)
+            | Self::Conflict(message)
+            | Self::Internal(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Makes `AppError` usable as the error type of server functions.
+impl FromServerFnError for AppError {
+    type Encoder = JsonEncoding;
+
+    fn from_server_fn_error(value: ServerFnErrorErr) -> Self {
+        match value {
+            ServerFnErrorErr::Args(_)
+            | ServerFnErrorErr::MissingArg(_)
+            | ServerFnErrorErr::Deserialization(_) => Self::BadRequest(value.to_string()),
+            _ => Self::internal(value),
+        }
+    }
+
+    /// Also sets the status of the server function's response (SSR), so
+    /// HTTP clients see e.g. a 400 rather than the default 500.
+    ///
+    /// Plain form posts keep the redirect back to their page.
+    fn ser(&self) -> Bytes {
+        #[cfg(feature = "ssr")]
+        if !is_form_post() {
+            super::status::set_status(self.status_code());
+        }
+
+        Self::Encoder::encode(self).unwrap_or_else(|e| {
+            Self::Encoder::encode(&Self::internal(e)).expect("an internal error always serializes")
+        })
+    }
+}
+
+/// Whether the server function being run was posted by an HTML form,
+/// without JavaScript: `leptos_axum` redirects these back to the page.
+#[cfg(feature = "ssr")]
+fn is_form_post() -> bool {
+    use http::header::ACCEPT;
+
+    use_context::<http::request::Parts>().is_some_and(|parts| {
+        parts
+            .headers
+            .get(ACCEPT)
+            .and_then(|accept| accept.to_str().ok())
+            .is_some_and(|accept| accept.contains("text/html"))
+    })
+}
+
+impl From<FieldErrors> for AppError {
+    fn from(errors: FieldErrors) -> Self {
+        Self::Validation(errors)
+    }
+}
+
+/// e.g. the failure of `leptos_axum::extract()`.
+impl From<ServerFnErrorErr> for AppError {
+    fn from(value: ServerFnErrorErr) -> Self {
+        Self::from_server_fn_error(value)
+    }
+}
@@ -0,0 +1,52 @@
+use http::StatusCode;
+use leptos::prelude::*;
+use leptos_router::components::A;
+
+use super::status::{error_status, set_status};
+
+/// Fallback of an `ErrorBoundary`: responds with the highest status among
+/// the errors (see `error_status`).
+///
+/// Messages of client errors (4xx) are shown; server errors (5xx) only show
+/// their reason, as their message may leak internals.
+///
+/// # Example
+///
+/// ```ignore
+/// <ErrorBoundary fallback=|errors| view! { <ErrorPage errors /> }>
+///     <Routes fallback=|| view! { <NotFound /> }>
+///         // ...
+///     </Routes>
+/// </ErrorBoundary>
+/// ```
+#[component]
+pub fn ErrorPage(errors: ArcRwSignal<Errors>) -> impl IntoView {
+    let status = errors.with_untracked(|errors| {
+        errors
+            .iter()
+            .map(|(_, error)| error_status(error))
+            .max_by_key(StatusCode::as_u16)
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    });
+    set_status(status);
+
+    let messages = move || {
+        errors.with(|errors| {
+            errors
+                .iter()
+                .map(|(_, error)| error)
+                .filter(|error| error_status(error).is_client_error())
+                .map(|error| view! { <li>{error.to_string()}</li> })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <section class="error-page">
+            <h1 class="error-status">{status.as_u16()}</h1>
+            <p class="error-reason">{status.canonical_reason().unwrap_or("Error")}</p>
+            <ul class="error-detail">{messages}</ul>
+            <A href="/">"Back to home"</A>
+        </section>
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::app_error::AppError;
+
+/// Validation errors of a form, one message per field name.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut errors = FieldErrors::new();
+/// if title.trim().is_empty() {
+///     errors.add("title", "Title can not be empty");
+/// }
+///
+/// // `Err(AppError::Validation(errors))` unless empty
+/// errors.into_result()?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldErrors(BTreeMap<String, String>);
+
+impl FieldErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the message of a field, replacing any previous one.
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.0.insert(field.into(), message.into());
+    }
+
+    /// Builder version of `add`.
+    pub fn with(mut self, field: impl Into<String>, message: impl Into<String>) -> Self {
+        self.add(field, message);
+        self
+    }
+
+    /// Returns the message of a field, if any.
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.0.get(field).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Fields and their message, sorted by field.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(field, message)| (field.as_str(), message.as_str()))
+    }
+
+    /// Returns `Ok(())` without errors, `AppError::Validation` otherwise.
+    pub fn into_result(self) -> Result<(), AppError> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(AppError::Validation(self)),
+        }
+    }
+}
+
+/// Messages joined with `; `, e.g. `title: Title can not be empty`.
+impl fmt::Display for FieldErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (field, message)) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{field}: {message}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use leptos::prelude::*;
+use leptos::server_fn::ServerFn;
+
+use super::app_error::AppError;
+
+/// Error of the latest dispatch of an action, for `FieldError` and `FormError`.
+pub fn action_error<S>(action: ServerAction<S>) -> Signal<Option<AppError>>
+where
+    S: ServerFn<Error = AppError> + Clone + Send + Sync + 'static,
+    S::Output: Send + Sync + 'static,
+{
+    Signal::derive(move || {
+        action.value().with(|value| {
+            value
+                .as_ref()
+                .and_then(|result| result.as_ref().err().cloned())
+        })
+    })
+}
+
+/// Validation message of a form field, shown next to its input.
+///
+/// # Example
+///
+/// ```ignore
+/// let add = ServerAction::<AddTodo>::new();
+/// let error = action_error(add);
+///
+/// view! {
+///     <ActionForm action=add>
+///         <input name="title" />
+///         <FieldError error field="title" />
+///         <FormError error />
+///     </ActionForm>
+/// }
+/// ```
+#[component]
+pub fn FieldError(
+    /// Error of the form's action, e.g. from `action_error`.
+    #[prop(into)]
+    error: Signal<Option<AppError>>,
+    /// Name of the field's input.
+    field: &'static str,
+) -> impl IntoView {
+    move || {
+        error.with(|error| {
+            error
+                .as_ref()
+                .and_then(|error| error.field(field))
+                .map(|message| {
+                    view! {
+                        <p class="field-error" data-field=field>
+                            {message.to_string()}
+                        </p>
+                    }
+                })
+        })
+    }
+}
+
+/// Error of a form that is not about one of its fields, e.g. wrong
+/// credentials: validation errors are left to `FieldError`.
+#[component]
+pub fn FormError(
+    /// Error of the form's action, e.g. from `action_error`.
+    #[prop(into)]
+    error: Signal<Option<AppError>>,
+) -> impl IntoView {
+    move || {
+        error
+            .get()
+            .filter(|error| !matches!(error, AppError::Validation(_)))
+            .map(|error| view! { <p class="error">{error.to_string()}</p> })
+    }
+}
@@ -0,0 +1,53 @@
+//! Errors of the app: typed server function errors, and the pages showing
+//! errors with the HTTP status of the response.
+//!
+//! # Architecture
+//!
+//! - [`AppError`] - Error type of server functions, serialized to the client
+//!   with per-field validation messages ([`FieldErrors`])
+//! - [`FieldError`] / [`FormError`] - Show the error of an action next to
+//!   the form fields
+//! - [`NotFound`] - Page of unknown paths (`404 Not Found`)
+//! - [`ErrorPage`] - Fallback of the app-wide `ErrorBoundary`, showing the
+//!   errors thrown by the current page
+//! - [`error_status`] - Maps an error to its HTTP status
+//! - [`StatusError`] - Error with an explicit status, for pages to throw
+//!
+//! Both pages set the status of the response during SSR, so crawlers and
+//! HTTP clients see a `404` or `500` rather than a `200`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[server]
+//! pub async fn find_invoice(id: i64) -> Result<Invoice, AppError> {
+//!     // ...
+//!     invoice.ok_or_else(|| AppError::NotFound(format!("Invoice {id}")))
+//! }
+//!
+//! #[component]
+//! fn Invoice(id: i64) -> impl IntoView {
+//!     let invoice = Resource::new(move || id, find_invoice);
+//!
+//!     // Thrown to `ErrorPage`: responds with a 404 on routes using `SsrMode::Async`
+//!     view! {
+//!         <Suspense>
+//!             {move || invoice.get().map(|invoice| invoice.map(|invoice| invoice.number))}
+//!         </Suspense>
+//!     }
+//! }
+//! ```
+
+mod app_error;
+mod error_page;
+mod field_errors;
+mod form;
+mod not_found;
+mod status;
+
+pub use app_error::AppError;
+pub use error_page::ErrorPage;
+pub use field_errors::FieldErrors;
+pub use form::{FieldError, FormError, action_error};
+pub use not_found::NotFound;
+pub use status::{StatusError, error_status, set_status};
@@ -0,0 +1,33 @@
+use http::StatusCode;
+use leptos::prelude::*;
+use leptos_meta::{Meta, Title};
+use leptos_router::components::A;
+use leptos_router::hooks::use_location;
+
+use super::status::set_status;
+
+/// Page of unknown paths: responds with `404 Not Found`, and is not indexed
+/// by search engines.
+///
+/// # Example
+///
+/// ```ignore
+/// <Routes fallback=|| view! { <NotFound /> }>
+/// ```
+#[component]
+pub fn NotFound() -> impl IntoView {
+    set_status(StatusCode::NOT_FOUND);
+
+    let path = use_location().pathname;
+
+    view! {
+        <Title text="Page not found" />
+        <Meta name="robots" content="noindex" />
+        <section class="error-page">
+            <h1 class="error-status">"404"</h1>
+            <p class="error-reason">"Page not found"</p>
+            <p class="error-detail">"Nothing lives at "<code>{path}</code></p>
+            <A href="/">"Back to home"</A>
+        </section>
+    }
+}
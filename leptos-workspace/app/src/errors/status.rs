@@ -0,0 +1,69 @@
+use std::fmt;
+
+use http::StatusCode;
+use leptos_router::params::ParamsError;
+
+use super::app_error::AppError;
+
+/// Error with an explicit HTTP status.
+#[derive(Debug, Clone)]
+pub struct StatusError {
+    status: StatusCode,
+    message: String,
+}
+
+impl StatusError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for StatusError {}
+
+/// Returns the HTTP status of an error thrown to an `ErrorBoundary`.
+///
+/// * `StatusError` - Its own status
+/// * `AppError` - `AppError::status_code`
+/// * `ParamsError` - `400 Bad Request`: the URL does not match the page
+/// * Anything else - `500 Internal Server Error`
+pub fn error_status(error: &leptos::error::Error) -> StatusCode {
+    if let Some(error) = error.downcast_ref::<StatusError>() {
+        return error.status();
+    }
+    if let Some(error) = error.downcast_ref::<AppError>() {
+        return error.status_code();
+    }
+    if error.downcast_ref::<ParamsError>().is_some() {
+        return StatusCode::BAD_REQUEST;
+    }
+
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+/// Sets the status of the response being rendered (SSR only, no-op in the browser).
+///
+/// Only takes effect while the first chunk of the response is rendered,
+/// i.e. outside of `Suspense`, or on routes using `SsrMode::Async`.
+pub fn set_status(status: StatusCode) {
+    #[cfg(feature = "ssr")]
+    {
+        if let Some(response) = leptos::prelude::use_context::<leptos_axum::ResponseOptions>() {
+            response.set_status(status);
+        }
+    }
+    #[cfg(not(feature = "ssr"))]
+    let _ = status;
+}
@@ -0,0 +1,24 @@
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use super::locale::LOCALE_COOKIE;
+use super::locale::Locale;
+use crate::errors::AppError;
+
+/// Lifetime of the locale cookie: one year.
+#[cfg(feature = "ssr")]
+const LOCALE_COOKIE_MAX_AGE: std::time::Duration =
+    std::time::Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Remembers the locale of this browser in a cookie, overriding its
+/// `Accept-Language` header on the next requests.
+#[server]
+pub async fn set_locale(locale: Locale) -> Result<(), AppError> {
+    use crate::cookies::SetCookie;
+
+    SetCookie::new(LOCALE_COOKIE, &locale.to_string(), LOCALE_COOKIE_MAX_AGE)
+        .set()
+        .map_err(AppError::internal)?;
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use leptos::prelude::*;
+
+use super::api::SetLocale;
+#[cfg(feature = "ssr")]
+use super::locale::LOCALE_COOKIE;
+use super::locale::Locale;
+use super::translations::{translate, translate_with};
+
+/// Locale state shared through context.
+#[derive(Clone, Copy)]
+pub struct I18n {
+    /// Locale of the texts, updated as soon as a new one is chosen.
+    pub locale: Signal<Locale>,
+
+    /// Dispatched by the switcher (`<ActionForm action=i18n.set_locale>`).
+    pub set_locale: ServerAction<SetLocale>,
+}
+
+impl I18n {
+    /// Returns the text of `key` in the current locale.
+    ///
+    /// Tracks the locale: call it from a closure (or use `t!`) in views.
+    pub fn t(&self, key: &str) -> String {
+        translate(self.locale.get(), key)
+    }
+
+    /// Returns the text of `key` in the current locale, replacing each
+    /// `{name}` with its argument.
+    pub fn t_with(&self, key: &str, args: &[(&str, String)]) -> String {
+        translate_with(self.locale.get(), key, args)
+    }
+
+    /// Text of `key` as a closure following the locale, used by `t!`.
+    pub fn text(self, key: &'static str) -> impl Fn() -> String + Send + Sync + 'static {
+        move || self.t(key)
+    }
+
+    /// Text of `key` with arguments as a closure following both the locale
+    /// and the signals read by `args`, used by `t!`.
+    pub fn text_with<F>(
+        self,
+        key: &'static str,
+        args: F,
+    ) -> impl Fn() -> String + Send + Sync + 'static
+    where
+        F: Fn() -> Vec<(&'static str, String)> + Send + Sync + 'static,
+    {
+        move || self.t_with(key, &args())
+    }
+}
+
+/// Creates the locale state and provides it to every component below the
+/// caller, typically `App`.
+///
+/// During SSR the locale is negotiated from the request (see
+/// [`request_locale`]). When hydrating, it is read back from `<html lang>`,
+/// so both render the same texts.
+pub fn provide_i18n() -> I18n {
+    let set_locale = ServerAction::<SetLocale>::new();
+    let locale = RwSignal::new(initial_locale());
+
+    // Switch right away rather than after the cookie is set
+    Effect::new(move |_| {
+        if let Some(input) = set_locale.input().get() {
+            locale.set(input.locale);
+        }
+    });
+    Effect::new(move |_| set_html_lang(locale.get()));
+
+    let i18n = I18n {
+        locale: locale.into(),
+        set_locale,
+    };
+    provide_context(i18n);
+
+    i18n
+}
+
+/// Returns the locale state of the closest `provide_i18n()` or
+/// `LocaleLayout`.
+///
+/// Provides it if there is none, e.g. in an island, which does not see the
+/// context of the page around it when hydrating.
+pub fn use_i18n() -> I18n {
+    use_context::<I18n>().unwrap_or_else(provide_i18n)
+}
+
+/// Negotiates the locale of the request being rendered.
+///
+/// In order: the route prefix (`/de/...`), the `locale` cookie, the
+/// `Accept-Language` header, and finally the default locale.
+#[cfg(feature = "ssr")]
+pub fn request_locale() -> Locale {
+    use http::header::ACCEPT_LANGUAGE;
+    use http::request::Parts;
+
+    use crate::cookies::get_cookie;
+
+    let Some(parts) = use_context::<Parts>() else {
+        return Locale::default();
+    };
+
+    let from_cookie =
+        || get_cookie(&parts.headers, LOCALE_COOKIE).and_then(|value| value.parse().ok());
+    let from_header = || {
+        parts
+            .headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|header| header.to_str().ok())
+            .and_then(Locale::negotiate)
+    };
+
+    Locale::from_path(parts.uri.path())
+        .or_else(from_cookie)
+        .or_else(from_header)
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "ssr")]
+fn initial_locale() -> Locale {
+    request_locale()
+}
+
+#[cfg(not(feature = "ssr"))]
+fn initial_locale() -> Locale {
+    document()
+        .document_element()
+        .and_then(|html| html.get_attribute("lang"))
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Sets `<html lang>` after the locale changed in the browser (no-op during
+/// SSR, where `shell()` renders it).
+pub(super) fn set_html_lang(locale: Locale) {
+    #[cfg(not(feature = "ssr"))]
+    if let Some(html) = document().document_element() {
+        let _ = html.set_attribute("lang", locale.as_str());
+    }
+    #[cfg(feature = "ssr")]
+    let _ = locale;
+}
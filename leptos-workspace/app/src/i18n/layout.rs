@@ -0,0 +1,32 @@
+use leptos::prelude::*;
+use leptos_router::components::Outlet;
+
+use super::context::{I18n, set_html_lang, use_i18n};
+use super::locale::Locale;
+
+/// Parent route of the pages under a locale prefix, e.g. `/de/...`.
+///
+/// The prefix wins over the cookie and `Accept-Language`: the pages below
+/// get their own [`I18n`] with this locale.
+///
+/// # Example
+///
+/// ```ignore
+/// <ParentRoute path=path!("de") view=|| view! { <LocaleLayout locale=Locale::De /> }>
+///     <AppRoutes />
+/// </ParentRoute>
+/// ```
+#[component]
+pub fn LocaleLayout(locale: Locale) -> impl IntoView {
+    let root = use_i18n();
+    provide_context(I18n {
+        locale: Signal::stored(locale),
+        set_locale: root.set_locale,
+    });
+
+    // Back to the negotiated locale when leaving the prefixed pages
+    Effect::new(move |_| set_html_lang(locale));
+    on_cleanup(move || set_html_lang(root.locale.get_untracked()));
+
+    view! { <Outlet /> }
+}
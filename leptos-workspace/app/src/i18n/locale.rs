@@ -0,0 +1,137 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the cookie holding the chosen locale.
+pub const LOCALE_COOKIE: &str = "locale";
+
+/// A language of the app, with its translations in `app/locales/<code>.json`.
+///
+/// Adding a locale takes a variant here, its translation file in
+/// `translations.rs`, and its prefixed routes in `app.rs`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    /// Every locale, in the order of the switcher.
+    pub const ALL: [Self; 2] = [Self::En, Self::De];
+
+    /// Language code, used by the route prefix, the cookie and `<html lang>`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::De => "de",
+        }
+    }
+
+    /// Name of the language in itself, for the switcher.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::En => "English",
+            Self::De => "Deutsch",
+        }
+    }
+
+    /// Picks the preferred supported locale of an `Accept-Language` header,
+    /// e.g. `de-CH, de;q=0.9, en;q=0.8`.
+    ///
+    /// Regional variants match their language (`de-CH` is `de`).
+    pub fn negotiate(accept_language: &str) -> Option<Self> {
+        let mut ranges: Vec<(&str, f32)> = accept_language
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let tag = parts.next()?.trim();
+                let quality = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |quality| quality.trim().parse().ok())?;
+
+                Some((tag, quality))
+            })
+            .filter(|(_, quality)| *quality > 0.0)
+            .collect();
+
+        // Stable: equal qualities keep the order of the header
+        ranges.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        ranges.into_iter().find_map(|(tag, _)| {
+            let language = tag.split('-').next().unwrap_or(tag);
+            language.parse().ok()
+        })
+    }
+
+    /// Locale of a path prefix, e.g. `de` for `/de/todos`.
+    pub fn from_path(path: &str) -> Option<Self> {
+        path.trim_start_matches('/')
+            .split('/')
+            .next()
+            .and_then(|segment| segment.parse().ok())
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// Parses a language code, ignoring case.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.as_str().eq_ignore_ascii_case(value))
+            .ok_or_else(|| format!("Unsupported locale: {value}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regional_variants_match_their_language() {
+        assert_eq!(
+            Locale::negotiate("de-CH, de;q=0.9, en;q=0.8"),
+            Some(Locale::De)
+        );
+        assert_eq!(Locale::negotiate("EN-us"), Some(Locale::En));
+    }
+
+    #[test]
+    fn highest_quality_wins_over_header_order() {
+        assert_eq!(Locale::negotiate("en;q=0.5, de;q=0.9"), Some(Locale::De));
+        assert_eq!(Locale::negotiate("de;q=0.2, en"), Some(Locale::En));
+    }
+
+    #[test]
+    fn equal_qualities_keep_the_header_order() {
+        assert_eq!(Locale::negotiate("en, de"), Some(Locale::En));
+        assert_eq!(Locale::negotiate("de;q=0.7, en;q=0.7"), Some(Locale::De));
+    }
+
+    #[test]
+    fn unsupported_languages_are_skipped() {
+        assert_eq!(
+            Locale::negotiate("fr, it;q=0.9, en;q=0.1"),
+            Some(Locale::En)
+        );
+        assert_eq!(Locale::negotiate("fr"), None);
+        assert_eq!(Locale::negotiate(""), None);
+    }
+
+    #[test]
+    fn zero_and_invalid_qualities_are_ignored() {
+        assert_eq!(Locale::negotiate("de;q=0, en;q=0.1"), Some(Locale::En));
+        assert_eq!(Locale::negotiate("de;q=abc, en;q=0.3"), Some(Locale::En));
+        assert_eq!(Locale::negotiate("en;q=0"), None);
+    }
+}
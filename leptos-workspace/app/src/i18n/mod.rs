@@ -0,0 +1,69 @@
+//! Translations and locale negotiation.
+//!
+//! # Architecture
+//!
+//! - [`Locale`] - The supported languages, with their translations in
+//!   `app/locales/<code>.json`, compiled into the app
+//! - [`request_locale`] (SSR only) - Negotiates the locale of a request: route
+//!   prefix, then [`LOCALE_COOKIE`], then `Accept-Language`
+//! - [`provide_i18n`] / [`use_i18n`] - The current locale as a signal, read
+//!   back from `<html lang>` when hydrating
+//! - [`t!`](t) - Translated text, reactive to the locale, for `view!`
+//! - [`LocaleLayout`] - Parent route of the prefixed pages (`/en/...`,
+//!   `/de/...`), fixing their locale
+//! - [`LocaleSwitcher`] / [`set_locale`] - Buttons saving the locale in the
+//!   cookie
+//!
+//! # Example
+//!
+//! ```ignore
+//! // app/locales/en.json
+//! // { "cart.items": "{count} items in your cart" }
+//!
+//! let count = RwSignal::new(0);
+//!
+//! view! {
+//!     <h1>{t!("home.title")}</h1>
+//!     <p>{t!("cart.items", count = count.get())}</p>
+//! }
+//! ```
+
+mod api;
+mod context;
+mod layout;
+mod locale;
+mod switcher;
+mod translations;
+
+pub use api::{SetLocale, set_locale};
+#[cfg(feature = "ssr")]
+pub use context::request_locale;
+pub use context::{I18n, provide_i18n, use_i18n};
+pub use layout::LocaleLayout;
+pub use locale::{LOCALE_COOKIE, Locale};
+pub use switcher::LocaleSwitcher;
+pub use translations::{translate, translate_with};
+
+/// Translated text of a key in the current locale, as a closure for `view!`.
+///
+/// Arguments replace their `{name}` placeholder, and are evaluated inside
+/// the closure: signals read there are tracked too.
+///
+/// # Example
+///
+/// ```ignore
+/// view! {
+///     <h1>{t!("home.title")}</h1>
+///     <button>{t!("home.click_me", count = count.get())}</button>
+/// }
+/// ```
+macro_rules! t {
+    ($key:literal) => {
+        $crate::i18n::use_i18n().text($key)
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::use_i18n()
+            .text_with($key, move || vec![$((stringify!($name), ($value).to_string())),+])
+    };
+}
+pub(crate) use t;
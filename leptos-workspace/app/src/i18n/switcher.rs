@@ -0,0 +1,53 @@
+use leptos::prelude::*;
+use leptos_router::NavigateOptions;
+use leptos_router::hooks::{use_location, use_navigate};
+
+use super::context::use_i18n;
+use super::locale::Locale;
+
+/// Buttons choosing the locale, saved in the `locale` cookie.
+///
+/// On a prefixed page (`/de/...`), also moves to the same page under the
+/// new prefix, which would otherwise keep its locale.
+#[component]
+pub fn LocaleSwitcher() -> impl IntoView {
+    let i18n = use_i18n();
+    let location = use_location();
+    let navigate = use_navigate();
+
+    Effect::new(move |_| {
+        let Some(input) = i18n.set_locale.input().get() else {
+            return;
+        };
+
+        let path = location.pathname.get_untracked();
+        if let Some(current) = Locale::from_path(&path) {
+            let rest = &path[1 + current.as_str().len()..];
+            navigate(
+                &format!("/{}{rest}", input.locale),
+                NavigateOptions::default(),
+            );
+        }
+    });
+
+    view! {
+        <ActionForm action=i18n.set_locale attr:class="locale-switcher">
+            {Locale::ALL
+                .into_iter()
+                .map(|option| {
+                    view! {
+                        <button
+                            type="submit"
+                            name="locale"
+                            value=option.as_str()
+                            lang=option.as_str()
+                            aria-pressed=move || (i18n.locale.get() == option).to_string()
+                        >
+                            {option.name()}
+                        </button>
+                    }
+                })
+                .collect_view()}
+        </ActionForm>
+    }
+}
@@ -0,0 +1,57 @@
+//! Translation catalogs, compiled into both the server and the WASM bundle.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use super::locale::Locale;
+
+/// Translations of one locale, by key.
+type Catalog = HashMap<String, String>;
+
+static EN: LazyLock<Catalog> = LazyLock::new(|| parse(include_str!("../../locales/en.json")));
+static DE: LazyLock<Catalog> = LazyLock::new(|| parse(include_str!("../../locales/de.json")));
+
+/// Parses a translation file: a flat JSON object of keys to texts.
+///
+/// # Panics
+///
+/// Panics if the file is not such an object. The files are compiled in, so
+/// a broken one fails on the first translation rather than in production.
+fn parse(json: &str) -> Catalog {
+    serde_json::from_str(json).expect("a translation file to be a JSON object of strings")
+}
+
+fn catalog(locale: Locale) -> &'static Catalog {
+    match locale {
+        Locale::En => &EN,
+        Locale::De => &DE,
+    }
+}
+
+/// Returns the text of `key` in `locale`.
+///
+/// Falls back to the default locale, then to the key itself, so a missing
+/// translation shows up on the page rather than failing.
+pub fn translate(locale: Locale, key: &str) -> String {
+    catalog(locale)
+        .get(key)
+        .or_else(|| catalog(Locale::default()).get(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Returns the text of `key` in `locale`, replacing each `{name}` with its
+/// argument.
+///
+/// # Example
+///
+/// ```ignore
+/// // "Click Me: {count}"
+/// let text = translate_with(Locale::En, "home.click_me", &[("count", count.to_string())]);
+/// ```
+pub fn translate_with(locale: Locale, key: &str, args: &[(&str, String)]) -> String {
+    args.iter()
+        .fold(translate(locale, key), |text, (name, value)| {
+            text.replace(&["{", name, "}"].concat(), value)
+        })
+}
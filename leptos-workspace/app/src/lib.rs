@@ -1,5 +1,26 @@
-mod pages;
-
+{% if auth == true -%}
+pub mod auth;
+{% endif -%}
+#[cfg(feature = "ssr")]
+pub mod cookies;
+{% if database == true -%}
+#[cfg(feature = "ssr")]
+pub mod database;
+{% endif -%}
+pub mod errors;
+{% if i18n == true -%}
+pub mod i18n;
+{% endif -%}
+pub mod pages;
+{% if pwa == true -%}
+pub mod pwa;
+{% endif -%}
+pub mod seo;
+pub mod ssg;
+pub mod theme;
+{% if uploads == true -%}
+pub mod uploads;
+{% endif %}
 mod app;
 pub use app::App;
 
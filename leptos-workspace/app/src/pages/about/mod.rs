@@ -0,0 +1,3 @@
+mod page;
+pub use page::AboutPage;
+pub(crate) use page::META;
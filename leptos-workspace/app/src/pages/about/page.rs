@@ -0,0 +1,35 @@
+use leptos::prelude::*;
+use leptos_router::{LazyRoute, lazy_route};
+
+{% if i18n == true -%}
+use crate::i18n::t;
+{% endif -%}
+use crate::seo::{PageMeta, PageSeo};
+
+pub(crate) const META: PageMeta = PageMeta::new("About", "A starter for full-stack Rust web apps.");
+
+/// Marketing page, pre-rendered when the server starts (see `crate::ssg`).
+pub struct AboutPage;
+
+#[lazy_route]
+impl LazyRoute for AboutPage {
+    fn data() -> Self {
+        Self
+    }
+
+    fn view(_this: Self) -> AnyView {
+        view! {
+            <PageSeo meta=META />
+            {%- if i18n == true %}
+            <h1>{t!("about.title")}</h1>
+            <p>{t!("about.intro")}</p>
+            <p>{t!("about.static")}</p>
+            {%- else %}
+            <h1>"About"</h1>
+            <p>"A starter for full-stack Rust web apps, with Leptos and Axum."</p>
+            <p>"This page is pre-rendered when the server starts, and served from disk."</p>
+            {%- endif %}
+        }
+        .into_any()
+    }
+}
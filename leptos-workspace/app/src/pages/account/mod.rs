@@ -0,0 +1,3 @@
+mod page;
+pub use page::AccountPage;
+pub(crate) use page::META;
@@ -0,0 +1,31 @@
+use leptos::prelude::*;
+
+use crate::auth::use_auth;
+use crate::seo::{PageMeta, PageSeo};
+
+pub(crate) const META: PageMeta = PageMeta::new("Account", "Your account.").with_noindex();
+
+/// Page of the signed-in user, behind `ProtectedRoute`.
+#[component]
+pub fn AccountPage() -> impl IntoView {
+    let auth = use_auth();
+    let username = move || {
+        auth.user
+            .get()
+            .and_then(Result::ok)
+            .flatten()
+            .map(|user| user.username)
+    };
+
+    // Signing out refetches the user: `ProtectedRoute` then redirects to the login page
+    view! {
+        <PageSeo meta=META />
+        <h1>"Account"</h1>
+        <Suspense>
+            <p class="user">"Signed in as "{username}</p>
+        </Suspense>
+        <ActionForm action=auth.logout attr:class="logout">
+            <button type="submit">"Sign out"</button>
+        </ActionForm>
+    }
+}
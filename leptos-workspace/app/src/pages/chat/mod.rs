@@ -0,0 +1,5 @@
+mod page;
+pub mod ws;
+
+pub use page::ChatPage;
+pub(crate) use page::META;
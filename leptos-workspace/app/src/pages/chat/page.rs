@@ -0,0 +1,268 @@
+use futures::StreamExt;
+use leptos::either::Either;
+use leptos::ev::SubmitEvent;
+use leptos::prelude::*;
+use leptos_router::{LazyRoute, lazy_route};
+use uuid::Uuid;
+use websocket_trait::client::{WebSocketClient, provide_websocket_manager, use_websocket_manager};
+
+use super::ws::message::{
+    ChatMessage, HISTORY_LIMIT, MAX_MESSAGE_LEN, MAX_NICKNAME_LEN, Request, Response,
+};
+use super::ws::{ChatClient, ChatManager};
+use crate::seo::{PageMeta, PageSeo};
+
+pub(crate) const META: PageMeta =
+    PageMeta::new("Chat", "Chat room with presence and typing indicators.");
+
+pub struct ChatPage;
+
+#[lazy_route]
+impl LazyRoute for ChatPage {
+    fn data() -> Self {
+        Self
+    }
+
+    fn view(_this: Self) -> AnyView {
+        view! {
+            <PageSeo meta=META />
+            <h1>"Chat"</h1>
+            <ChatDemo />
+        }
+        .into_any()
+    }
+}
+{% if islands == true %}
+/// Chat room demo, the only part of the page shipping WASM.
+#[island]
+{%- else %}
+/// Chat room demo: asks for a nickname, then joins the room.
+#[component]
+{%- endif %}
+fn ChatDemo() -> impl IntoView {
+    let uuid = Uuid::new_v4();
+    let chat_manager = ChatClient::new(uuid).create_manager();
+    let room = RoomState::new(&chat_manager);
+
+    // Share the manager with every component below this one
+    provide_websocket_manager(chat_manager);
+
+    move || match room.nickname.get() {
+        None => Either::Left(view! { <NicknamePrompt room /> }),
+        Some(nickname) => Either::Right(view! { <ChatRoom room nickname /> }),
+    }
+}
+
+/// Client-side view of the chat room, updated from the server's responses.
+#[derive(Clone, Copy)]
+struct RoomState {
+    /// Nickname requested by this tab, joined again after a reconnect.
+    requested: RwSignal<Option<String>>,
+
+    /// Nickname accepted by the server, once joined.
+    nickname: RwSignal<Option<String>>,
+
+    /// Reason of the last refused `Join`.
+    error: RwSignal<Option<String>>,
+
+    /// Latest messages, oldest first.
+    messages: RwSignal<Vec<ChatMessage>>,
+
+    members: RwSignal<Vec<String>>,
+
+    /// Nicknames of the members currently typing.
+    typing: RwSignal<Vec<String>>,
+}
+
+impl RoomState {
+    /// Creates the state and starts following the responses of `manager`.
+    fn new(manager: &ChatManager) -> Self {
+        let room = Self {
+            requested: RwSignal::new(None),
+            nickname: RwSignal::new(None),
+            error: RwSignal::new(None),
+            messages: RwSignal::new(Vec::new()),
+            members: RwSignal::new(Vec::new()),
+            typing: RwSignal::new(Vec::new()),
+        };
+
+        // Every response, in order: signals alone could coalesce consecutive messages
+        let mut responses = Some(manager.stream(|_| true));
+        Effect::new(move |_| {
+            // Effects only run in the browser, where responses arrive
+            if let Some(mut responses) = responses.take() {
+                leptos::task::spawn_local(async move {
+                    while let Some(response) = responses.next().await {
+                        room.apply(response);
+                    }
+                });
+            }
+        });
+
+        // Join once connected, and again after a reconnect
+        let is_connected = manager.is_connected;
+        let manager = manager.clone();
+        Effect::new(move |_| {
+            if !is_connected.get() {
+                return;
+            }
+            if let Some(nickname) = room.requested.get_untracked() {
+                let _ = manager.send(Request::Join { nickname });
+            }
+        });
+
+        room
+    }
+
+    /// Asks to join under `nickname`, connecting first if needed.
+    fn join(&self, manager: &ChatManager, nickname: String) {
+        self.requested.set(Some(nickname.clone()));
+
+        match manager.is_connected.get_untracked() {
+            true => {
+                let _ = manager.send(Request::Join { nickname });
+            }
+            false => manager.connect(),
+        }
+    }
+
+    /// Updates the state from a response of the server.
+    fn apply(&self, response: Response) {
+        match response {
+            Response::Joined {
+                nickname,
+                history,
+                members,
+            } => {
+                self.messages.set(history);
+                self.members.set(members);
+                self.error.set(None);
+                self.nickname.set(Some(nickname));
+            }
+            Response::Rejected { reason } => self.error.set(Some(reason)),
+            Response::Message { message } => self.messages.update(|messages| {
+                messages.push(message);
+                if messages.len() > HISTORY_LIMIT {
+                    messages.remove(0);
+                }
+            }),
+            Response::Members { members } => self.members.set(members),
+            Response::Typing { nicknames } => self.typing.set(nicknames),
+            Response::UpgradeRequired | Response::HandshakeResponse => {}
+        }
+    }
+}
+
+#[component]
+fn NicknamePrompt(room: RoomState) -> impl IntoView {
+    let manager = use_websocket_manager::<ChatClient>();
+    let nickname = RwSignal::new(String::new());
+
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+
+        let nickname = nickname.get_untracked().trim().to_string();
+        if !nickname.is_empty() {
+            room.join(&manager, nickname);
+        }
+    };
+
+    view! {
+        <form class="nickname" on:submit=on_submit>
+            <input
+                name="nickname"
+                placeholder="Nickname"
+                maxlength=MAX_NICKNAME_LEN.to_string()
+                bind:value=nickname
+            />
+            <button type="submit">"Join"</button>
+        </form>
+        {move || room.error.get().map(|error| view! { <p class="error">{error}</p> })}
+    }
+}
+
+#[component]
+fn ChatRoom(room: RoomState, nickname: String) -> impl IntoView {
+    let manager = use_websocket_manager::<ChatClient>();
+    let draft = RwSignal::new(String::new());
+
+    // Whether the server shows this member as typing
+    let typing = RwSignal::new(false);
+    let set_typing = {
+        let manager = manager.clone();
+        move |now: bool| {
+            if typing.get_untracked() != now {
+                typing.set(now);
+                let _ = manager.send(Request::SetTyping { typing: now });
+            }
+        }
+    };
+
+    let on_input = move |ev| {
+        let text = event_target_value(&ev);
+        set_typing(!text.trim().is_empty());
+        draft.set(text);
+    };
+
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+
+        let text = draft.get_untracked();
+        if text.trim().is_empty() {
+            return;
+        }
+
+        // Posting also clears the typing indicator on the server
+        if manager.send(Request::Post { text }).is_ok() {
+            typing.set(false);
+            draft.set(String::new());
+        }
+    };
+
+    // Other members only
+    let typing_indicator = move || {
+        room.typing.with(|nicknames| {
+            let others: Vec<_> = nicknames
+                .iter()
+                .filter(|other| **other != nickname)
+                .map(String::as_str)
+                .collect();
+
+            match others.as_slice() {
+                [] => None,
+                [other] => Some(format!("{other} is typing...")),
+                others => Some(format!("{} are typing...", others.join(", "))),
+            }
+        })
+    };
+
+    view! {
+        <p class="members">"Online: "{move || room.members.get().join(", ")}</p>
+        <ul class="messages">
+            <For
+                each=move || room.messages.get()
+                key=|message| message.id
+                children=|message| {
+                    view! {
+                        <li class="message">
+                            <strong>{message.nickname}</strong>
+                            ": "
+                            {message.text}
+                        </li>
+                    }
+                }
+            />
+        </ul>
+        <p class="typing">{typing_indicator}</p>
+        <form class="composer" on:submit=on_submit>
+            <input
+                name="message"
+                placeholder="Message"
+                maxlength=MAX_MESSAGE_LEN.to_string()
+                prop:value=draft
+                on:input=on_input
+            />
+            <button type="submit">"Send"</button>
+        </form>
+    }
+}
@@ -0,0 +1,71 @@
+use futures::channel::mpsc::UnboundedSender;
+use leptos::prelude::*;
+use uuid::Uuid;
+use websocket_trait::server::{ResponseSender, WebSocketMessage};
+
+use super::message::{Request, Response};
+use super::room::ROOM;
+
+/// Serves one chat connection.
+///
+/// The connection enters the server's chat room on `Join`, and leaves it when
+/// the handler is dropped, i.e. whenever the connection ends.
+pub struct ChatHandler {
+    /// Member id in the room, unlike the handshake uuid not chosen by the client.
+    member: Uuid,
+}
+
+impl Default for ChatHandler {
+    fn default() -> Self {
+        Self {
+            member: Uuid::new_v4(),
+        }
+    }
+}
+
+impl WebSocketMessage for ChatHandler {
+    type Request = Request;
+    type Response = Response;
+
+    async fn handle_request(
+        &mut self,
+        request: Self::Request,
+        tx: &UnboundedSender<Result<Self::Response, ServerFnError>>,
+    ) -> bool {
+        match request {
+            Request::Handshake { uuid, .. } => {
+                tracing::info!("Chat user connected: {uuid}");
+                tx.send_response(Response::HandshakeResponse);
+
+                true
+            }
+            Request::Disconnect { uuid } => {
+                tracing::info!("Chat user disconnect: {uuid}");
+                false
+            }
+            Request::Join { nickname } => {
+                if ROOM.join(self.member, &nickname, tx) {
+                    tracing::info!("{nickname} joined the chat");
+                }
+
+                true
+            }
+            Request::Post { text } => {
+                ROOM.post(self.member, &text);
+
+                true
+            }
+            Request::SetTyping { typing } => {
+                ROOM.set_typing(self.member, typing);
+
+                true
+            }
+        }
+    }
+}
+
+impl Drop for ChatHandler {
+    fn drop(&mut self) {
+        ROOM.leave(self.member);
+    }
+}
@@ -0,0 +1,81 @@
+use rkyv::{Archive, Deserialize, Serialize};
+use uuid::Uuid;
+use websocket_trait::{WebSocketSchema, websocket_protocol};
+
+/// Number of messages kept by the room, and shown to members joining it.
+pub const HISTORY_LIMIT: usize = 50;
+
+/// Longest nickname accepted, in characters.
+pub const MAX_NICKNAME_LEN: usize = 24;
+
+/// Longest message accepted, in characters.
+pub const MAX_MESSAGE_LEN: usize = 500;
+
+#[websocket_protocol(
+    response = Response,
+    server_fn = chat_websocket,
+    handler = super::handler::ChatHandler,
+    client = ChatClient,
+    manager = ChatManager,
+    handshake_response = HandshakeResponse,
+    upgrade_required = UpgradeRequired,
+    record,
+)]
+#[derive(Debug, Clone, Archive, Deserialize, Serialize, WebSocketSchema)]
+pub enum Request {
+    #[websocket(handshake)]
+    Handshake {
+        uuid: Uuid,
+        #[websocket(version)]
+        version: u64,
+    },
+    #[websocket(disconnect)]
+    Disconnect { uuid: Uuid },
+    /// Enters the room under a nickname.
+    Join { nickname: String },
+    /// Posts a message to the room.
+    Post { text: String },
+    /// Shows or hides this member in the typing indicator.
+    SetTyping { typing: bool },
+}
+
+#[derive(Debug, Clone, Archive, Deserialize, Serialize, WebSocketSchema)]
+pub enum Response {
+    // Keep first: outdated clients must still decode it
+    UpgradeRequired,
+    HandshakeResponse,
+    /// `Join` accepted: the nickname as registered, the history and the members.
+    Joined {
+        nickname: String,
+        history: Vec<ChatMessage>,
+        members: Vec<String>,
+    },
+    /// `Join` refused, e.g. the nickname is taken.
+    Rejected {
+        reason: String,
+    },
+    /// New message, broadcast to every member including its author.
+    Message {
+        message: ChatMessage,
+    },
+    /// Nicknames of the members, after someone joined or left.
+    Members {
+        members: Vec<String>,
+    },
+    /// Nicknames of the members currently typing.
+    Typing {
+        nicknames: Vec<String>,
+    },
+}
+
+/// Message posted to the room.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Deserialize, Serialize)]
+pub struct ChatMessage {
+    /// Sequence number, unique in the room.
+    pub id: u64,
+
+    /// Nickname of the author.
+    pub nickname: String,
+
+    pub text: String,
+}
@@ -0,0 +1,13 @@
+pub mod message;
+
+#[cfg(feature = "ssr")]
+pub mod handler;
+#[cfg(feature = "ssr")]
+mod room;
+
+use leptos::server_fn::ServerFn;
+
+pub use message::{ChatClient, ChatManager};
+
+/// URL path of the `chat_websocket` server function, for native clients.
+pub const CHAT_WEBSOCKET_PATH: &str = <message::ChatWebsocket as ServerFn>::PATH;
@@ -0,0 +1,212 @@
+//! Chat room shared by every connection of the server.
+//!
+//! Keeps the members (with the channel to their client) and a bounded
+//! history in memory: the room is empty again after a restart.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Mutex, PoisonError};
+
+use futures::channel::mpsc::UnboundedSender;
+use leptos::prelude::ServerFnError;
+use uuid::Uuid;
+use websocket_trait::server::ResponseSender;
+
+use super::message::{ChatMessage, HISTORY_LIMIT, MAX_MESSAGE_LEN, MAX_NICKNAME_LEN, Response};
+
+/// The chat room of this server.
+pub(super) static ROOM: ChatRoom = ChatRoom::new();
+
+/// Channel of responses to one client.
+type ResponseTx = UnboundedSender<Result<Response, ServerFnError>>;
+
+/// A connection that joined the room.
+struct Member {
+    nickname: String,
+
+    /// Whether the member is shown in the typing indicator.
+    typing: bool,
+
+    tx: ResponseTx,
+}
+
+struct RoomState {
+    /// Members by connection id.
+    members: BTreeMap<Uuid, Member>,
+
+    /// Latest messages, oldest first, at most `HISTORY_LIMIT`.
+    history: VecDeque<ChatMessage>,
+
+    /// Id of the next message.
+    next_id: u64,
+}
+
+/// Members and history of a chat room.
+///
+/// Every change is broadcast to all members while the lock is held, so all
+/// clients see the changes in the same order.
+pub(super) struct ChatRoom {
+    state: Mutex<RoomState>,
+}
+
+impl ChatRoom {
+    const fn new() -> Self {
+        Self {
+            state: Mutex::new(RoomState {
+                members: BTreeMap::new(),
+                history: VecDeque::new(),
+                next_id: 0,
+            }),
+        }
+    }
+
+    /// Adds a member, answering `Joined` or `Rejected` on `tx`.
+    ///
+    /// # Returns
+    ///
+    /// Whether the member joined.
+    pub(super) fn join(&self, id: Uuid, nickname: &str, tx: &ResponseTx) -> bool {
+        let mut state = self.lock();
+        let nickname = nickname.trim();
+
+        let rejection =
+            if nickname.is_empty() {
+                Some("Nickname can not be empty".to_string())
+            } else if nickname.chars().count() > MAX_NICKNAME_LEN {
+                Some(format!(
+                    "Nickname can not exceed {MAX_NICKNAME_LEN} characters"
+                ))
+            } else if state.members.iter().any(|(&member, other)| {
+                member != id && other.nickname.eq_ignore_ascii_case(nickname)
+            }) {
+                Some(format!("Nickname {nickname} is taken"))
+            } else {
+                None
+            };
+
+        if let Some(reason) = rejection {
+            tx.send_response(Response::Rejected { reason });
+            return false;
+        }
+
+        state.members.insert(
+            id,
+            Member {
+                nickname: nickname.to_string(),
+                typing: false,
+                tx: tx.clone(),
+            },
+        );
+
+        tx.send_response(Response::Joined {
+            nickname: nickname.to_string(),
+            history: state.history.iter().cloned().collect(),
+            members: state.nicknames(),
+        });
+        state.broadcast(Response::Members {
+            members: state.nicknames(),
+        });
+
+        true
+    }
+
+    /// Posts a message of a member, truncated to `MAX_MESSAGE_LEN` characters.
+    ///
+    /// Posting also hides the author from the typing indicator.
+    pub(super) fn post(&self, id: Uuid, text: &str) {
+        let mut guard = self.lock();
+        let state = &mut *guard;
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+
+        let Some(member) = state.members.get_mut(&id) else {
+            return;
+        };
+        let was_typing = std::mem::replace(&mut member.typing, false);
+        let message = ChatMessage {
+            id: state.next_id,
+            nickname: member.nickname.clone(),
+            text: text.chars().take(MAX_MESSAGE_LEN).collect(),
+        };
+
+        state.next_id += 1;
+        state.history.push_back(message.clone());
+        if state.history.len() > HISTORY_LIMIT {
+            state.history.pop_front();
+        }
+
+        state.broadcast(Response::Message { message });
+        if was_typing {
+            state.broadcast_typing();
+        }
+    }
+
+    /// Shows or hides a member in the typing indicator.
+    pub(super) fn set_typing(&self, id: Uuid, typing: bool) {
+        let mut state = self.lock();
+
+        let Some(member) = state.members.get_mut(&id) else {
+            return;
+        };
+        if std::mem::replace(&mut member.typing, typing) != typing {
+            state.broadcast_typing();
+        }
+    }
+
+    /// Removes a member, if it joined.
+    pub(super) fn leave(&self, id: Uuid) {
+        let mut state = self.lock();
+
+        let Some(member) = state.members.remove(&id) else {
+            return;
+        };
+        state.broadcast(Response::Members {
+            members: state.nicknames(),
+        });
+        if member.typing {
+            state.broadcast_typing();
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RoomState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl RoomState {
+    /// Nicknames of the members, sorted.
+    fn nicknames(&self) -> Vec<String> {
+        let mut nicknames: Vec<_> = self
+            .members
+            .values()
+            .map(|member| member.nickname.clone())
+            .collect();
+        nicknames.sort_unstable();
+
+        nicknames
+    }
+
+    /// Sends the nicknames of the members currently typing to every member.
+    fn broadcast_typing(&self) {
+        let mut nicknames: Vec<_> = self
+            .members
+            .values()
+            .filter(|member| member.typing)
+            .map(|member| member.nickname.clone())
+            .collect();
+        nicknames.sort_unstable();
+
+        self.broadcast(Response::Typing { nicknames });
+    }
+
+    /// Sends a response to every member.
+    ///
+    /// Sending to a closed connection is only logged: its handler leaves the
+    /// room when dropped.
+    fn broadcast(&self, response: Response) {
+        for member in self.members.values() {
+            member.tx.send_response(response.clone());
+        }
+    }
+}
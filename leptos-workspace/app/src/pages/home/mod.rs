@@ -1,9 +1,11 @@
 {% if websocket == true -%}
 mod page;
-mod ws;
+pub mod ws;
 
 pub use page::HomePage;
+pub(crate) use page::META;
 {% else -%}
 mod page;
 pub use page::HomePage;
+pub(crate) use page::META;
 {% endif -%}
@@ -1,72 +1,163 @@
 {%- if websocket == true %}use leptos::either::Either;
 use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
 use leptos_router::{LazyRoute, lazy_route};
 use uuid::Uuid;
-use websocket_trait::client::WebSocketClient;
+use websocket_trait::client::{
+    ConnectionState, Transport, WebSocketClient, WebSocketError, provide_websocket_manager,
+    set_transport, use_websocket_manager,
+};
 
-use super::ws::{RkyvWebSocketClient, WebSocketManager};
+use super::ws::RkyvWebSocketClient;
+{%- if compression == true %}
+use super::ws::message::{ECHO_SIZE, Request};
+{%- endif %}
+{%- if i18n == true %}
+use crate::i18n::{t, use_i18n};
+{%- endif %}
+use crate::seo::{PageMeta, PageSeo};
+
+pub(crate) const META: PageMeta =
+    PageMeta::new("Home", "Leptos and Axum starter, with a WebSocket demo.");
 
 pub struct HomePage {
-    websocket_manager: WebSocketManager,
+    /// `?transport=websocket|sse` forces a transport (default: websocket, falling back to SSE).
+    transport: Option<String>,
+
+    /// `?shared=true` shares one connection across the tabs of this browser
+    /// (default: one connection per tab).
+    shared: bool,
 }
 
 #[lazy_route]
 impl LazyRoute for HomePage {
     fn data() -> Self {
-        let uuid = Uuid::new_v4();
-        let websocket_manager = RkyvWebSocketClient::new(uuid).create_manager();
+        let query = use_query_map().get_untracked();
+        let transport = query.get("transport");
+        let shared = query.get("shared").as_deref() == Some("true");
 
-        Self { websocket_manager }
+        Self { transport, shared }
     }
 
     fn view(this: Self) -> AnyView {
         view! {
-            {move || match this.websocket_manager.is_connected.get() {
-                false => {
-                    Either::Left(
-                        view! {
-                            <DisconnectedComponent websocket_manager=this
-                                .websocket_manager
-                                .clone() />
-                        }
-                            .into_any(),
-                    )
-                }
-                true => {
-                    Either::Right(
-                        view! {
-                            <ConnectedComponent websocket_manager=this.websocket_manager.clone() />
-                        }
-                            .into_any(),
-                    )
-                }
-            }}
+            <PageSeo meta=META />
+            <WebSocketDemo transport=this.transport shared=this.shared />
         }
         .into_any()
     }
 }
+{% if islands == true %}
+/// WebSocket demo, the only part of the page shipping WASM.
+#[island]
+{%- else %}
+/// WebSocket demo: connects to the server and shows the round-trip time.
+#[component]
+{%- endif %}
+fn WebSocketDemo(transport: Option<String>, shared: bool) -> impl IntoView {
+    if let Some(transport) = transport.and_then(|transport| transport.parse::<Transport>().ok()) {
+        set_transport(transport);
+    }
+
+    let uuid = Uuid::new_v4();
+    let websocket_manager = RkyvWebSocketClient::new(uuid).create_manager();
+    // Opt-in: tabs of the same browser share a single connection
+    let websocket_manager = if shared {
+        websocket_manager.with_shared_connection("rkyv_websocket")
+    } else {
+        websocket_manager
+    };
+    let is_connected = websocket_manager.is_connected;
+
+    // Share the manager with every component below this one
+    provide_websocket_manager(websocket_manager);
+
+    move || match is_connected.get() {
+        false => Either::Left(view! { <DisconnectedComponent /> }.into_any()),
+        true => Either::Right(view! { <ConnectedComponent /> }.into_any()),
+    }
+}
 
 #[component]
-fn DisconnectedComponent(websocket_manager: WebSocketManager) -> impl IntoView {
+fn DisconnectedComponent() -> impl IntoView {
+    let websocket_manager = use_websocket_manager::<RkyvWebSocketClient>();
+    let state = websocket_manager.state;
+
+    // Reason of the last failed attempt (e.g. handshake timeout)
+    let failure = move || match state.get() {
+        // This tab runs an outdated bundle: only a reload can fix it
+        ConnectionState::Failed(WebSocketError::UpgradeRequired) => Some(
+            view! {
+                <p>{WebSocketError::UpgradeRequired.to_string()}</p>
+                <button on:click=move |_| {
+                    let _ = window().location().reload();
+                }>{% if i18n == true %}{t!("home.reload")}{% else %}"Reload"{% endif %}</button>
+            }
+            .into_any(),
+        ),
+        ConnectionState::Failed(e) => Some(view! { <p>{e.to_string()}</p> }.into_any()),
+        _ => None,
+    };
+
     view! {
         <button on:click=move |_| {
             websocket_manager.connect();
-        }>"Connect"</button>
+        }>{% if i18n == true %}{t!("home.connect")}{% else %}"Connect"{% endif %}</button>
+        {failure}
     }
 }
 
 #[component]
-fn ConnectedComponent(websocket_manager: WebSocketManager) -> impl IntoView {
+fn ConnectedComponent() -> impl IntoView {
+    let websocket_manager = use_websocket_manager::<RkyvWebSocketClient>();
+    let rtt = websocket_manager.rtt;
+    let quality = websocket_manager.quality;
+    {%- if i18n == true %}
+    let i18n = use_i18n();
+    let latency = move || match (rtt.get(), quality.get()) {
+        (Some(rtt), Some(quality)) => i18n.t_with(
+            "home.rtt",
+            &[
+                ("rtt", rtt.as_millis().to_string()),
+                ("quality", quality.to_string()),
+            ],
+        ),
+        _ => i18n.t("home.rtt_measuring"),
+    };
+    {%- else %}
+    let latency = move || match (rtt.get(), quality.get()) {
+        (Some(rtt), Some(quality)) => format!("RTT: {}ms ({quality})", rtt.as_millis()),
+        _ => "RTT: measuring...".to_string(),
+    };
+    {%- endif %}
+    {%- if compression == true %}
+    let echo_manager = websocket_manager.clone();
+    {%- endif %}
+
     view! {
         <button on:click=move |_| {
             websocket_manager.disconnect();
-        }>"Disconnect"</button>
+        }>{% if i18n == true %}{t!("home.disconnect")}{% else %}"Disconnect"{% endif %}</button>
+        {%- if compression == true %}
+        // Above the threshold: sent and echoed back compressed
+        <button on:click=move |_| {
+            let _ = echo_manager.send(Request::echo(ECHO_SIZE));
+        }>{% if i18n == true %}{t!("home.echo")}{% else %}"Send 4 KiB"{% endif %}</button>
+        {%- endif %}
+        <p>{latency}</p>
     }
 }
 {%- else -%}
 use leptos::prelude::*;
 use leptos_router::{LazyRoute, lazy_route};
 
+{% if i18n == true -%}
+use crate::i18n::t;
+{% endif -%}
+use crate::seo::{PageMeta, PageSeo};
+
+pub(crate) const META: PageMeta = PageMeta::new("Home", "Leptos and Axum starter.");
+
 pub struct HomePage;
 
 #[lazy_route]
@@ -76,19 +167,40 @@ impl LazyRoute for HomePage {
     }
 
     fn view(_this: Self) -> AnyView {
-        let (count, set_count) = signal(0);
-        let on_click = move |_| {
-            set_count.update(|count| {
-                *count += 1;
-                leptos::logging::log!("Update num: {count}");
-            })
-        };
-
         view! {
+            <PageSeo meta=META />
+            {%- if i18n == true %}
+            <h1>{t!("home.title")}</h1>
+            {%- else %}
             <h1>"Welcome to Leptos!"</h1>
-            <button on:click=on_click>"Click Me: "{count}</button>
+            {%- endif %}
+            <Counter />
         }
         .into_any()
     }
 }
+{% if islands == true %}
+/// Counter demo, the only part of the page shipping WASM.
+#[island]
+{%- else %}
+/// Counter demo.
+#[component]
+{%- endif %}
+fn Counter() -> impl IntoView {
+    let (count, set_count) = signal(0);
+    let on_click = move |_| {
+        set_count.update(|count| {
+            *count += 1;
+            leptos::logging::log!("Update num: {count}");
+        })
+    };
+
+    view! {
+        {%- if i18n == true %}
+        <button on:click=on_click>{t!("home.click_me", count = count.get())}</button>
+        {%- else %}
+        <button on:click=on_click>"Click Me: "{count}</button>
+        {%- endif %}
+    }
+}
 {%- endif %}
@@ -4,6 +4,7 @@ use websocket_trait::server::{ResponseSender, WebSocketMessage};
 
 use super::message::{Request, Response};
 
+#[derive(Default)]
 pub struct RkyvWebSocketMessage;
 
 impl WebSocketMessage for RkyvWebSocketMessage {
@@ -16,7 +17,7 @@ impl WebSocketMessage for RkyvWebSocketMessage {
         tx: &UnboundedSender<Result<Self::Response, ServerFnError>>,
     ) -> bool {
         match request {
-            Request::Handshake { uuid } => {
+            Request::Handshake { uuid, .. } => {
                 tracing::info!("User connected: {uuid}");
                 tx.send_response(Response::HandshakeResponse);
 
@@ -26,6 +27,18 @@ impl WebSocketMessage for RkyvWebSocketMessage {
                 tracing::info!("User disconnect: {uuid}");
                 false
             }
+            Request::Ping { id } => {
+                tx.send_response(Response::Pong { id });
+
+                true
+            }
+            {%- if compression == true %}
+            Request::Echo { text } => {
+                tx.send_response(Response::Echo { text });
+
+                true
+            }
+            {%- endif %}
         }
     }
 }
@@ -1,13 +1,97 @@
+use leptos::server_fn::codec::RkyvEncoding;
 use rkyv::{Archive, Deserialize, Serialize};
 use uuid::Uuid;
+{%- if compression == true %}
+use websocket_trait::compression::Compressed;
+{%- endif %}
+use websocket_trait::{WebSocketSchema, websocket_protocol};
+{% if compression == true %}
+/// Codec of the endpoint: rkyv, deflated from 1 KiB.
+pub type Encoding = Compressed<RkyvEncoding>;
 
-#[derive(Debug, Clone, Archive, Deserialize, Serialize)]
+/// Size of the demo echo, above the compression threshold.
+pub const ECHO_SIZE: usize = 4 * 1024;
+{%- else %}
+/// Codec of the endpoint.
+pub type Encoding = RkyvEncoding;
+{%- endif %}
+
+#[websocket_protocol(
+    response = Response,
+    encoding = Encoding,
+    server_fn = rkyv_websocket,
+    handler = super::handler::RkyvWebSocketMessage,
+    client = RkyvWebSocketClient,
+    manager = WebSocketManager,
+    handshake_response = HandshakeResponse,
+    pong = Pong,
+    upgrade_required = UpgradeRequired,
+    on_response = log_response,
+    sse = rkyv_sse,
+    record,
+)]
+#[derive(Debug, Clone, Archive, Deserialize, Serialize, WebSocketSchema)]
 pub enum Request {
-    Handshake { uuid: Uuid },
+    #[websocket(handshake)]
+    Handshake {
+        uuid: Uuid,
+        #[websocket(version)]
+        version: u64,
+    },
+    #[websocket(disconnect)]
     Disconnect { uuid: Uuid },
+    #[websocket(ping)]
+    Ping { id: u32 },
+    {%- if compression == true %}
+    /// Asks the server to send `text` back.
+    Echo { text: String },
+    {%- endif %}
+}
+{%- if compression == true %}
+
+impl Request {
+    /// Echo of `size` bytes of repeated text, which deflates well like most
+    /// real payloads (markup, JSON, logs).
+    pub fn echo(size: usize) -> Self {
+        let text = "The quick brown fox jumps over the lazy dog. "
+            .chars()
+            .cycle()
+            .take(size)
+            .collect();
+
+        Self::Echo { text }
+    }
 }
+{%- endif %}
 
-#[derive(Debug, Clone, Archive, Deserialize, Serialize)]
+#[derive(Debug, Clone, Archive, Deserialize, Serialize, WebSocketSchema)]
 pub enum Response {
+    // Keep first: outdated clients must still decode it
+    UpgradeRequired,
     HandshakeResponse,
+    Pong { id: u32 },
+    {%- if compression == true %}
+    Echo { text: String },
+    {%- endif %}
+}
+{% if compression == true %}
+/// Logs the handshake acknowledgement and the echoes on the client.
+fn log_response(response: Response) {
+    match response {
+        Response::HandshakeResponse => {
+            leptos::logging::log!("Received: FrontendResponse::HandshakeResponse");
+        }
+        Response::Echo { text } => {
+            leptos::logging::log!("Received: FrontendResponse::Echo ({} bytes)", text.len());
+        }
+        _ => {}
+    }
+}
+{%- else %}
+/// Logs the handshake acknowledgement on the client.
+fn log_response(response: Response) {
+    if let Response::HandshakeResponse = response {
+        leptos::logging::log!("Received: FrontendResponse::HandshakeResponse");
+    }
 }
+{%- endif %}
@@ -1,8 +1,11 @@
-mod client;
-mod connection;
-mod message;
+pub mod message;
 
 #[cfg(feature = "ssr")]
-mod handler;
+pub mod handler;
 
-pub use client::{RkyvWebSocketClient, WebSocketManager};
+use leptos::server_fn::ServerFn;
+
+pub use message::{RkyvWebSocketClient, WebSocketManager};
+
+/// URL path of the `rkyv_websocket` server function, for native clients.
+pub const RKYV_WEBSOCKET_PATH: &str = <message::RkyvWebsocket as ServerFn>::PATH;
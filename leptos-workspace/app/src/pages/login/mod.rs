@@ -0,0 +1,3 @@
+mod page;
+pub use page::LoginPage;
+pub(crate) use page::META;
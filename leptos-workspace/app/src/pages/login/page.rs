@@ -0,0 +1,90 @@
+use leptos::prelude::*;
+{%- if islands == true %}
+use leptos_router::hooks::use_query_map;
+{%- else %}
+use leptos_router::hooks::{use_navigate, use_query_map};
+{%- endif %}
+use leptos_router::{LazyRoute, lazy_route};
+
+{% if islands == true -%}
+use crate::auth::{Login, is_local_path};
+{% else -%}
+use crate::auth::{is_local_path, use_auth};
+{% endif -%}
+use crate::errors::{FormError, action_error};
+use crate::seo::{PageMeta, PageSeo};
+
+pub(crate) const META: PageMeta =
+    PageMeta::new("Sign in", "Sign in to your account.").with_noindex();
+
+pub struct LoginPage {
+    /// Where to go once signed in.
+    next: String,
+}
+
+#[lazy_route]
+impl LazyRoute for LoginPage {
+    fn data() -> Self {
+        // `?next=/account` is set by `ProtectedRoute`: only follow paths of this site
+        let next = use_query_map()
+            .get_untracked()
+            .get("next")
+            .filter(|next| is_local_path(next))
+            .unwrap_or_else(|| "/".to_string());
+
+        Self { next }
+    }
+
+    fn view(this: Self) -> AnyView {
+        view! {
+            <PageSeo meta=META />
+            <h1>"Sign in"</h1>
+            <LoginForm next=this.next />
+        }
+        .into_any()
+    }
+}
+{% if islands == true %}
+/// Login form and its error, the only part of the page shipping WASM.
+///
+/// Loads `next` once signed in, so the whole page shows the new user.
+#[island]
+fn LoginForm(next: String) -> impl IntoView {
+    let login = ServerAction::<Login>::new();
+
+    Effect::new(move |_| {
+        if let Some(Ok(_)) = login.value().get() {
+            let _ = window().location().set_href(&next);
+        }
+    });
+{%- else %}
+/// Login form and its error: navigates to `next` once signed in.
+#[component]
+fn LoginForm(next: String) -> impl IntoView {
+    let login = use_auth().login;
+
+    let navigate = use_navigate();
+    Effect::new(move |_| {
+        if let Some(Ok(_)) = login.value().get() {
+            navigate(&next, Default::default());
+        }
+    });
+{%- endif %}
+
+    let error = action_error(login);
+
+    view! {
+        <ActionForm action=login attr:class="login">
+            <input name="username" placeholder="Username" autocomplete="username" required />
+            <input
+                name="password"
+                type="password"
+                placeholder="Password"
+                autocomplete="current-password"
+                required
+            />
+            <button type="submit">"Sign in"</button>
+        </ActionForm>
+        <FormError error />
+    }
+}
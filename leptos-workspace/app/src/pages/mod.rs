@@ -1,2 +1,170 @@
-mod home;
+//! Pages of the app, routed in `AppRoutes` (`app.rs`).
+
+{% if i18n == true -%}
+use crate::i18n::Locale;
+{% endif -%}
+use crate::seo::PageMeta;
+
+pub mod about;
+{% if auth == true -%}
+pub mod account;
+{% endif -%}
+{% if websocket == true -%}
+pub mod chat;
+{% endif -%}
+pub mod home;
+{% if auth == true -%}
+pub mod login;
+{% endif -%}
+{% if pwa == true -%}
+pub mod offline;
+{% endif -%}
+pub mod status;
+pub mod streaming;
+{% if database == true -%}
+pub mod todos;
+{% endif -%}
+{% if uploads == true -%}
+pub mod upload;
+{% endif -%}
+pub use about::AboutPage;
+{% if auth == true -%}
+pub use account::AccountPage;
+{% endif -%}
+{% if websocket == true -%}
+pub use chat::ChatPage;
+{% endif -%}
 pub use home::HomePage;
+{% if auth == true -%}
+pub use login::LoginPage;
+{% endif -%}
+{% if pwa == true -%}
+pub use offline::OfflinePage;
+{% endif -%}
+pub use status::StatusPage;
+pub use streaming::StreamingPage;
+{% if database == true -%}
+pub use todos::TodosPage;
+{% endif -%}
+{% if uploads == true -%}
+pub use upload::UploadPage;
+{% endif -%}
+
+/// Paths of the pages of `AppRoutes` (`app.rs`) with their metadata, for
+/// `/sitemap.xml`: pages asking not to be indexed (`PageMeta::with_noindex`)
+/// are left out of it.
+///
+/// Routes with parameters have no single URL to list. Every other route must
+/// have its page here, which the tests check.
+pub const PAGES: &[(&str, PageMeta)] = &[
+    ("/", home::META),
+    ("/about", about::META),
+    {%- if websocket == true %}
+    ("/chat", chat::META),
+    {%- endif %}
+    {%- if auth == true %}
+    ("/login", login::META),
+    ("/account", account::META),
+    {%- endif %}
+    {%- if pwa == true %}
+    ("/offline", offline::META),
+    {%- endif %}
+    ("/streaming", streaming::META),
+    ("/streaming/in-order", streaming::META),
+    ("/streaming/partially-blocked", streaming::META),
+    ("/streaming/async", streaming::META),
+    {%- if database == true %}
+    ("/todos", todos::META),
+    {%- endif %}
+    {%- if uploads == true %}
+    ("/upload", upload::META),
+    {%- endif %}
+];
+
+/// Whether the page of a route asks not to be indexed, e.g. `/login`.
+pub fn is_noindex(path: &str) -> bool {
+    let path = page_path(path);
+
+    PAGES
+        .iter()
+        .any(|(page, meta)| meta.noindex && *page == path)
+}
+
+{% if i18n == true -%}
+/// Path of the page of a route, as in [`PAGES`]: without its trailing slash
+/// and locale prefix, e.g. `/login` for `/de/login/`.
+pub fn page_path(path: &str) -> &str {
+    let path = path.trim_end_matches('/');
+    let path = Locale::ALL
+        .iter()
+        .find_map(|locale| {
+            path.strip_prefix('/')?
+                .strip_prefix(locale.as_str())
+                .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+        .unwrap_or(path);
+
+    if path.is_empty() { "/" } else { path }
+}
+{% else -%}
+/// Path of the page of a route, as in [`PAGES`]: without its trailing
+/// slash, e.g. `/login` for `/login/`.
+pub fn page_path(path: &str) -> &str {
+    let path = path.trim_end_matches('/');
+
+    if path.is_empty() { "/" } else { path }
+}
+{% endif -%}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use leptos_axum::AxumRouteListing;
+
+    use super::*;
+
+    #[test]
+    fn every_static_route_has_a_page() {
+        // Rendering `App` spawns its resources, never polled here
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let _runtime = runtime.enter();
+        let routes = leptos_axum::generate_route_list(crate::App);
+        let routes: BTreeSet<&str> = routes
+            .iter()
+            .map(AxumRouteListing::path)
+            .filter(|path| !path.contains(['{', '*']))
+            .map(page_path)
+            .collect();
+        let pages: BTreeSet<&str> = PAGES.iter().map(|(page, _)| *page).collect();
+
+        assert_eq!(routes, pages);
+    }
+
+    #[test]
+    fn page_paths_drop_the_trailing_slash() {
+        assert_eq!(page_path("/"), "/");
+        assert_eq!(page_path(""), "/");
+        assert_eq!(page_path("/about/"), "/about");
+        {%- if i18n == true %}
+        assert_eq!(page_path("/de"), "/");
+        assert_eq!(page_path("/en/streaming/async"), "/streaming/async");
+        assert_eq!(page_path("/delta"), "/delta");
+        {%- endif %}
+    }
+    {%- if auth == true %}
+
+    #[test]
+    fn noindex_pages_are_found_by_path() {
+        assert!(is_noindex("/login"));
+        assert!(is_noindex("/account/"));
+        {%- if i18n == true %}
+        assert!(is_noindex("/de/login"));
+        {%- endif %}
+        assert!(!is_noindex("/about"));
+        assert!(!is_noindex("/"));
+    }
+    {%- endif %}
+}
@@ -0,0 +1,37 @@
+use leptos::prelude::*;
+use leptos_router::{LazyRoute, lazy_route};
+
+{% if i18n == true -%}
+use crate::i18n::t;
+{% endif -%}
+use crate::seo::{PageMeta, PageSeo};
+
+pub(crate) const META: PageMeta =
+    PageMeta::new("Offline", "This page is not available offline.").with_noindex();
+
+/// Shown by the service worker when a page cannot be loaded, e.g. without
+/// network (see `crate::pwa`).
+pub struct OfflinePage;
+
+#[lazy_route]
+impl LazyRoute for OfflinePage {
+    fn data() -> Self {
+        Self
+    }
+
+    fn view(_this: Self) -> AnyView {
+        view! {
+            <PageSeo meta=META />
+            {%- if i18n == true %}
+            <h1>{t!("offline.title")}</h1>
+            <p>{t!("offline.message")}</p>
+            <a href="" rel="external">{t!("offline.retry")}</a>
+            {%- else %}
+            <h1>"You are offline"</h1>
+            <p>"This page could not be loaded. Check your connection and try again."</p>
+            <a href="" rel="external">"Try again"</a>
+            {%- endif %}
+        }
+        .into_any()
+    }
+}
@@ -0,0 +1,32 @@
+use http::StatusCode;
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+use crate::errors::StatusError;
+
+/// Throws the error of a status code, e.g. `/status/503`, to preview the
+/// error page of the app's `ErrorBoundary`.
+#[component]
+pub fn StatusPage() -> impl IntoView {
+    let params = use_params_map();
+
+    move || -> Result<(), StatusError> {
+        let code = params.read().get("code").unwrap_or_default();
+        let status = code
+            .parse()
+            .ok()
+            .and_then(|code| StatusCode::from_u16(code).ok())
+            .filter(|status| status.is_client_error() || status.is_server_error())
+            .ok_or_else(|| {
+                StatusError::new(
+                    StatusCode::BAD_REQUEST,
+                    format!("{code} is not an error status"),
+                )
+            })?;
+
+        Err(StatusError::new(
+            status,
+            format!("Example of a {} error", status.as_u16()),
+        ))
+    }
+}
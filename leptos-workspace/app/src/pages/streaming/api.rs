@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppError;
+
+/// Time taken by [`get_summary`].
+///
+/// Seconds apart from the page and the report, so the parts of a streamed
+/// response can be told apart by timing (see the e2e tests).
+pub const SUMMARY_DELAY: Duration = Duration::from_millis(1000);
+
+/// Time taken by [`get_report`], well after the summary.
+pub const REPORT_DELAY: Duration = Duration::from_millis(2500);
+
+/// Number of reports, from 1.
+pub const REPORT_COUNT: u32 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Summary {
+    pub reports: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub number: u32,
+    pub values: Vec<u32>,
+}
+
+/// Returns the summary of the reports, after [`SUMMARY_DELAY`].
+#[server]
+pub async fn get_summary() -> Result<Summary, AppError> {
+    tokio::time::sleep(SUMMARY_DELAY).await;
+
+    Ok(Summary {
+        reports: REPORT_COUNT,
+    })
+}
+
+/// Returns a report, after [`REPORT_DELAY`].
+///
+/// # Errors
+///
+/// * `AppError::NotFound` - No report with this number
+#[server]
+pub async fn get_report(number: u32) -> Result<Report, AppError> {
+    tokio::time::sleep(REPORT_DELAY).await;
+
+    if !(1..=REPORT_COUNT).contains(&number) {
+        return Err(AppError::NotFound(format!("Report {number}")));
+    }
+
+    // Made-up figures, the same for every request
+    let values = (1..=7).map(|day| (number * 37 + day * 11) % 100).collect();
+
+    Ok(Report { number, values })
+}
@@ -0,0 +1,9 @@
+mod api;
+mod page;
+
+pub use api::{
+    GetReport, GetSummary, REPORT_COUNT, REPORT_DELAY, Report, SUMMARY_DELAY, Summary, get_report,
+    get_summary,
+};
+pub(crate) use page::META;
+pub use page::StreamingPage;
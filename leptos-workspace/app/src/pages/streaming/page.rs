@@ -0,0 +1,138 @@
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::{use_location, use_query_map};
+use leptos_router::{LazyRoute, lazy_route};
+
+use super::api::{REPORT_COUNT, Report, Summary, get_report, get_summary};
+use crate::errors::AppError;
+{% if i18n == true -%}
+use crate::i18n::t;
+{% endif -%}
+use crate::seo::{PageMeta, PageSeo};
+
+pub(crate) const META: PageMeta =
+    PageMeta::new("Streaming", "Slow server data under each SSR mode.");
+
+/// Paths of the page and their SSR mode, set in `AppRoutes`.
+const MODES: [(&str, &str); 4] = [
+    ("/streaming", "OutOfOrder"),
+    ("/streaming/in-order", "InOrder"),
+    ("/streaming/partially-blocked", "PartiallyBlocked"),
+    ("/streaming/async", "Async"),
+];
+
+/// Resources loading slow server data, served with another SSR mode on
+/// each of its paths ([`MODES`]).
+///
+/// - The summary is a blocking resource: `SsrMode::PartiallyBlocked` waits
+///   for it before sending the page
+/// - The report follows `?report=<n>`: `<Transition>` keeps showing the
+///   previous one while the next one loads
+pub struct StreamingPage {
+    summary: Resource<Result<Summary, AppError>>,
+    report: Resource<Result<Report, AppError>>,
+}
+
+#[lazy_route]
+impl LazyRoute for StreamingPage {
+    fn data() -> Self {
+        let query = use_query_map();
+
+        // Both start loading at once, while the page renders
+        let summary = Resource::new_blocking(|| (), |_| get_summary());
+        let report = Resource::new(
+            move || {
+                query
+                    .read()
+                    .get("report")
+                    .and_then(|number| number.parse().ok())
+                    .unwrap_or(1)
+            },
+            get_report,
+        );
+
+        Self { summary, report }
+    }
+
+    fn view(this: Self) -> AnyView {
+        let StreamingPage { summary, report } = this;
+
+        view! {
+            <PageSeo meta=META />
+            {%- if i18n == true %}
+            <h1>{t!("streaming.title")}</h1>
+            <p>{t!("streaming.intro")}</p>
+            {%- else %}
+            <h1>"Streaming"</h1>
+            <p>"The same page with each SSR mode: slow server data streams in once loaded."</p>
+            {%- endif %}
+            <nav class="ssr-modes">
+                {MODES
+                    .iter()
+                    .map(|(href, mode)| view! { <A href=*href>{*mode}</A> })
+                    .collect_view()}
+            </nav>
+            {%- if i18n == true %}
+            <Suspense fallback=|| view! { <p>{t!("streaming.loading_summary")}</p> }>
+                {move || {
+                    summary
+                        .get()
+                        .map(|summary| {
+                            summary
+                                .map(|summary| {
+                                    let count = summary.reports;
+                                    view! { <p>{t!("streaming.summary", count = count)}</p> }
+                                })
+                        })
+                }}
+            </Suspense>
+            <Transition fallback=|| view! { <p>{t!("streaming.loading_report")}</p> }>
+            {%- else %}
+            <Suspense fallback=|| view! { <p>"Loading summary..."</p> }>
+                {move || {
+                    summary
+                        .get()
+                        .map(|summary| {
+                            summary
+                                .map(|summary| {
+                                    view! { <p>{format!("{} reports available", summary.reports)}</p> }
+                                })
+                        })
+                }}
+            </Suspense>
+            <Transition fallback=|| view! { <p>"Loading report..."</p> }>
+            {%- endif %}
+                {move || {
+                    report.get().map(|report| report.map(|report| view! { <ReportView report /> }))
+                }}
+            </Transition>
+        }
+        .into_any()
+    }
+}
+
+/// A report, with a link to the next one.
+#[component]
+fn ReportView(report: Report) -> impl IntoView {
+    let pathname = use_location().pathname;
+    let number = report.number;
+    let next = number % REPORT_COUNT + 1;
+
+    view! {
+        {%- if i18n == true %}
+        <h2>{t!("streaming.report", number = number)}</h2>
+        {%- else %}
+        <h2>{format!("Report {number}")}</h2>
+        {%- endif %}
+        <ol class="report">
+            {report.values.into_iter().map(|value| view! { <li>{value}</li> }).collect_view()}
+        </ol>
+        <A href=move || format!("{}?report={next}", pathname.get())>
+            {%- if i18n == true %}
+            {t!("streaming.next_report")}
+            {%- else %}
+            "Next report"
+            {%- endif %}
+        </A>
+    }
+}
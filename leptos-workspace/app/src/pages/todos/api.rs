@@ -0,0 +1,91 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use crate::database::use_pool;
+use crate::errors::AppError;
+#[cfg(feature = "ssr")]
+use crate::errors::FieldErrors;
+
+/// Longest accepted title, in characters.
+pub const MAX_TITLE_LEN: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(sqlx::FromRow))]
+pub struct Todo {
+    pub id: i64,
+    pub title: String,
+    pub completed: bool,
+}
+
+/// Returns every todo, oldest first.
+#[server]
+pub async fn list_todos() -> Result<Vec<Todo>, AppError> {
+    let pool = use_pool()?;
+    let todos = sqlx::query_as::<_, Todo>("SELECT id, title, completed FROM todos ORDER BY id")
+        .fetch_all(&pool)
+        .await?;
+
+    Ok(todos)
+}
+
+/// Adds a todo, not completed.
+///
+/// # Errors
+///
+/// * `AppError::Validation` - The title is empty or longer than `MAX_TITLE_LEN` characters
+#[server]
+pub async fn add_todo(title: String) -> Result<Todo, AppError> {
+    let title = title.trim();
+    let mut errors = FieldErrors::new();
+    if title.is_empty() {
+        errors.add("title", "Title can not be empty");
+    } else if title.chars().count() > MAX_TITLE_LEN {
+        errors.add(
+            "title",
+            format!("Title can not exceed {MAX_TITLE_LEN} characters"),
+        );
+    }
+    errors.into_result()?;
+
+    let pool = use_pool()?;
+    let todo = sqlx::query_as::<_, Todo>(
+        "INSERT INTO todos (title) VALUES (?) RETURNING id, title, completed",
+    )
+    .bind(title)
+    .fetch_one(&pool)
+    .await?;
+
+    Ok(todo)
+}
+
+/// Marks a todo as completed, or not completed again.
+///
+/// # Errors
+///
+/// * `AppError::NotFound` - No todo with this id
+#[server]
+pub async fn toggle_todo(id: i64) -> Result<(), AppError> {
+    let pool = use_pool()?;
+    let result = sqlx::query("UPDATE todos SET completed = NOT completed WHERE id = ?")
+        .bind(id)
+        .execute(&pool)
+        .await?;
+
+    match result.rows_affected() {
+        0 => Err(AppError::NotFound(format!("Todo {id}"))),
+        _ => Ok(()),
+    }
+}
+
+/// Deletes a todo; deleting it twice is not an error.
+#[server]
+pub async fn delete_todo(id: i64) -> Result<(), AppError> {
+    let pool = use_pool()?;
+    sqlx::query("DELETE FROM todos WHERE id = ?")
+        .bind(id)
+        .execute(&pool)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+mod api;
+mod page;
+
+pub use api::{
+    AddTodo, DeleteTodo, ListTodos, MAX_TITLE_LEN, Todo, ToggleTodo, add_todo, delete_todo,
+    list_todos, toggle_todo,
+};
+pub(crate) use page::META;
+pub use page::TodosPage;
@@ -0,0 +1,123 @@
+use leptos::either::Either;
+use leptos::prelude::*;
+use leptos_router::{LazyRoute, lazy_route};
+
+use super::api::{AddTodo, DeleteTodo, MAX_TITLE_LEN, Todo, ToggleTodo, list_todos};
+use crate::errors::{FieldError, FormError, action_error};
+use crate::seo::{PageMeta, PageSeo};
+
+pub(crate) const META: PageMeta = PageMeta::new("Todos", "Things to do, stored in SQLite.");
+
+pub struct TodosPage {
+    add: ServerAction<AddTodo>,
+    toggle: ServerAction<ToggleTodo>,
+    delete: ServerAction<DeleteTodo>,
+}
+
+#[lazy_route]
+impl LazyRoute for TodosPage {
+    fn data() -> Self {
+        Self {
+            add: ServerAction::new(),
+            toggle: ServerAction::new(),
+            delete: ServerAction::new(),
+        }
+    }
+
+    fn view(this: Self) -> AnyView {
+        let TodosPage {
+            add,
+            toggle,
+            delete,
+        } = this;
+
+        // Refetched after every change
+        let todos = Resource::new(
+            move || {
+                (
+                    add.version().get(),
+                    toggle.version().get(),
+                    delete.version().get(),
+                )
+            },
+            |_| list_todos(),
+        );
+
+        // Clear the input once the todo is added
+        let title = NodeRef::<leptos::html::Input>::new();
+        Effect::new(move |_| {
+            if let (Some(Ok(_)), Some(input)) = (add.value().get(), title.get()) {
+                input.set_value("");
+            }
+        });
+
+        let add_error = action_error(add);
+
+        // Every change is a form: the page also works before hydration
+        view! {
+            <PageSeo meta=META />
+            <h1>"Todos"</h1>
+            <ActionForm action=add attr:class="new-todo">
+                <input
+                    name="title"
+                    placeholder="What needs to be done?"
+                    maxlength=MAX_TITLE_LEN.to_string()
+                    required
+                    node_ref=title
+                />
+                <button type="submit">"Add"</button>
+                <FieldError error=add_error field="title" />
+                <FormError error=add_error />
+            </ActionForm>
+            <Transition fallback=|| view! { <p>"Loading..."</p> }>
+                {move || {
+                    todos
+                        .get()
+                        .map(|todos| match todos {
+                            Err(error) => {
+                                Either::Left(view! { <p class="error">{error.to_string()}</p> })
+                            }
+                            Ok(todos) => Either::Right(view! { <TodoList todos toggle delete /> }),
+                        })
+                }}
+            </Transition>
+        }
+        .into_any()
+    }
+}
+
+#[component]
+fn TodoList(
+    todos: Vec<Todo>,
+    toggle: ServerAction<ToggleTodo>,
+    delete: ServerAction<DeleteTodo>,
+) -> impl IntoView {
+    if todos.is_empty() {
+        return Either::Left(view! { <p class="empty">"Nothing to do"</p> });
+    }
+
+    let items = todos
+        .into_iter()
+        .map(|todo| {
+            let id = todo.id;
+
+            view! {
+                <li class="todo" class:completed=todo.completed>
+                    <span class="title">{todo.title}</span>
+                    <ActionForm action=toggle attr:class="toggle">
+                        <input type="hidden" name="id" value=id.to_string() />
+                        <button type="submit">
+                            {if todo.completed { "Undo" } else { "Done" }}
+                        </button>
+                    </ActionForm>
+                    <ActionForm action=delete attr:class="delete">
+                        <input type="hidden" name="id" value=id.to_string() />
+                        <button type="submit">"Delete"</button>
+                    </ActionForm>
+                </li>
+            }
+        })
+        .collect_view();
+
+    Either::Right(view! { <ul class="todos">{items}</ul> })
+}
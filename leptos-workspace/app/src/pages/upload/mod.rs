@@ -0,0 +1,3 @@
+mod page;
+pub(crate) use page::META;
+pub use page::UploadPage;
@@ -0,0 +1,34 @@
+use leptos::prelude::*;
+use leptos_router::{LazyRoute, lazy_route};
+
+{% if i18n == true -%}
+use crate::i18n::t;
+{% endif -%}
+use crate::seo::{PageMeta, PageSeo};
+use crate::uploads::Uploader;
+
+pub(crate) const META: PageMeta = PageMeta::new("Upload", "Upload files, and download them back.");
+
+pub struct UploadPage;
+
+#[lazy_route]
+impl LazyRoute for UploadPage {
+    fn data() -> Self {
+        Self
+    }
+
+    fn view(_this: Self) -> AnyView {
+        view! {
+            <PageSeo meta=META />
+            {%- if i18n == true %}
+            <h1>{t!("upload.title")}</h1>
+            <p>{t!("upload.intro")}</p>
+            {%- else %}
+            <h1>"Upload"</h1>
+            <p>"Images, PDFs and text files, up to 10 MB."</p>
+            {%- endif %}
+            <Uploader />
+        }
+        .into_any()
+    }
+}
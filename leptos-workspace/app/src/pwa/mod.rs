@@ -0,0 +1,33 @@
+//! Progressive Web App: installable, and usable offline.
+//!
+//! # Architecture
+//!
+//! - `/manifest.webmanifest` - Name, colors and icon of the installed app,
+//!   generated by the server from [`SITE_NAME`](crate::seo::SITE_NAME)
+//! - `/sw.js` - Service worker generated by the server
+//!   (`server/src/server/pwa.rs`): precaches the `/pkg` assets of
+//!   `target/site` and [`OFFLINE_PATH`], shown when a page cannot be loaded
+//! - `/pwa.js` - Registers the worker, and shows [`UpdatePrompt`] once a new
+//!   version is installed
+//!
+//! Reloading from the prompt activates the new worker, which deletes the
+//! caches of the previous version.
+
+mod update_prompt;
+
+pub use update_prompt::UpdatePrompt;
+
+/// Path of the web app manifest.
+pub const MANIFEST_PATH: &str = "/manifest.webmanifest";
+
+/// Path of the service worker, at the root so its scope is the whole site.
+pub const SERVICE_WORKER_PATH: &str = "/sw.js";
+
+/// Path of the page shown by the service worker when offline.
+pub const OFFLINE_PATH: &str = "/offline";
+
+/// Color of the browser UI around the installed app (the accent color).
+pub const THEME_COLOR: &str = "#0d74ce";
+
+/// Background of the splash screen of the installed app.
+pub const BACKGROUND_COLOR: &str = "#ffffff";
@@ -0,0 +1,24 @@
+use leptos::prelude::*;
+
+{% if i18n == true -%}
+use crate::i18n::t;
+
+{% endif -%}
+/// Banner offering to reload once a new version of the app is installed.
+///
+/// Rendered hidden: `/pwa.js` shows it when a new service worker is waiting,
+/// and its button activates that worker, which reloads the page.
+#[component]
+pub fn UpdatePrompt() -> impl IntoView {
+    view! {
+        <div id="update-prompt" class="update-prompt" role="status" hidden>
+            {%- if i18n == true %}
+            <span>{t!("pwa.update_available")}</span>
+            <button type="button">{t!("pwa.reload")}</button>
+            {%- else %}
+            <span>"A new version is available."</span>
+            <button type="button">"Reload"</button>
+            {%- endif %}
+        </div>
+    }
+}
@@ -0,0 +1,34 @@
+//! Page metadata for search engines and link previews.
+//!
+//! # Architecture
+//!
+//! - [`PageMeta`] - Title, description, image and indexing of a page,
+//!   declared next to its route
+//! - [`PageSeo`] - Renders a [`PageMeta`] into `<head>`, with the canonical
+//!   URL and OpenGraph tags
+//! - [`SiteUrl`] - Public URL of the site, provided by the server for
+//!   absolute URLs
+//!
+//! The server also generates `/sitemap.xml` and `/robots.txt` from the routes
+//! of `App` and the metadata of their pages in `crate::pages::PAGES`
+//! (`server/src/server/seo.rs`).
+//!
+//! # Example
+//!
+//! ```ignore
+//! const META: PageMeta = PageMeta::new("Account", "Your account.").with_noindex();
+//!
+//! #[component]
+//! pub fn AccountPage() -> impl IntoView {
+//!     view! {
+//!         <PageSeo meta=META />
+//!         <h1>"Account"</h1>
+//!     }
+//! }
+//! ```
+
+mod page_meta;
+mod site_url;
+
+pub use page_meta::{PageMeta, PageSeo, SITE_NAME};
+pub use site_url::{SiteUrl, use_site_url};
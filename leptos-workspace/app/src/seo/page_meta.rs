@@ -0,0 +1,82 @@
+use leptos::prelude::*;
+use leptos_meta::{Link, Meta, Title};
+use leptos_router::hooks::use_location;
+
+use super::site_url::use_site_url;
+
+/// Name of the site, after the title of every page and in OpenGraph tags.
+pub const SITE_NAME: &str = "{{project-name}}";
+
+/// Metadata of a page, declared next to its route.
+///
+/// # Example
+///
+/// ```ignore
+/// const META: PageMeta = PageMeta::new("Todos", "Things to do, stored in SQLite.");
+///
+/// view! {
+///     <PageSeo meta=META />
+///     <h1>"Todos"</h1>
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageMeta {
+    /// Title of the page, followed by `SITE_NAME` in `<title>`.
+    pub title: &'static str,
+
+    /// Summary shown by search engines and link previews.
+    pub description: &'static str,
+
+    /// Path of the preview image of link previews, e.g. `/og/todos.png`.
+    pub image: Option<&'static str>,
+
+    /// Asks search engines not to index the page, e.g. for account pages,
+    /// and leaves it out of the sitemap (see `crate::pages::PAGES`).
+    pub noindex: bool,
+}
+
+impl PageMeta {
+    pub const fn new(title: &'static str, description: &'static str) -> Self {
+        Self {
+            title,
+            description,
+            image: None,
+            noindex: false,
+        }
+    }
+
+    pub const fn with_image(mut self, image: &'static str) -> Self {
+        self.image = Some(image);
+        self
+    }
+
+    pub const fn with_noindex(mut self) -> Self {
+        self.noindex = true;
+        self
+    }
+}
+
+/// Renders the metadata of the current page into `<head>`: title,
+/// description, canonical URL and OpenGraph tags.
+///
+/// The canonical URL is the current path, so pages of parameterized or
+/// locale-prefixed routes point to themselves.
+#[component]
+pub fn PageSeo(meta: PageMeta) -> impl IntoView {
+    let site_url = use_site_url();
+    let url = site_url.join(&use_location().pathname.get_untracked());
+    let image = meta.image.map(|image| site_url.join(image));
+
+    view! {
+        <Title text=meta.title />
+        <Meta name="description" content=meta.description />
+        <Link rel="canonical" href=url.clone() />
+        <Meta property="og:type" content="website" />
+        <Meta property="og:site_name" content=SITE_NAME />
+        <Meta property="og:title" content=meta.title />
+        <Meta property="og:description" content=meta.description />
+        <Meta property="og:url" content=url />
+        {image.map(|image| view! { <Meta property="og:image" content=image /> })}
+        {meta.noindex.then(|| view! { <Meta name="robots" content="noindex" /> })}
+    }
+}
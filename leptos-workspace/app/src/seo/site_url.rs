@@ -0,0 +1,43 @@
+use leptos::prelude::*;
+
+/// Public URL of the site, e.g. `https://example.com`, without a trailing
+/// slash.
+///
+/// Provided by the server (`SITE_URL`) for canonical URLs and OpenGraph
+/// tags, which need absolute URLs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteUrl(String);
+
+impl SiteUrl {
+    pub fn new(url: impl Into<String>) -> Self {
+        let url = url.into();
+
+        Self(url.trim_end_matches('/').to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Absolute URL of a path of the site, e.g. `https://example.com/todos`.
+    pub fn join(&self, path: &str) -> String {
+        format!("{}/{}", self.0, path.trim_start_matches('/'))
+    }
+}
+
+/// Returns the URL provided by the server (by default, the `site-addr` of
+/// `Cargo.toml`), or the origin of the page in the browser.
+pub fn use_site_url() -> SiteUrl {
+    if let Some(site_url) = use_context::<SiteUrl>() {
+        return site_url;
+    }
+
+    #[cfg(feature = "ssr")]
+    {
+        SiteUrl::new("http://127.0.0.1:3000")
+    }
+    #[cfg(not(feature = "ssr"))]
+    {
+        SiteUrl::new(window().location().origin().unwrap_or_default())
+    }
+}
@@ -1,19 +1,32 @@
 use leptos::prelude::*;
-use leptos_meta::{Link, Meta, MetaTags, Stylesheet};
+use leptos_meta::{Link, MetaTags, Stylesheet};
 
 use crate::app::App;
-
+{% if i18n == true -%}
+use crate::i18n::request_locale;
+{% endif -%}
+{% if pwa == true -%}
+use crate::pwa::{MANIFEST_PATH, THEME_COLOR};
+{% endif %}
 pub fn shell(options: LeptosOptions) -> impl IntoView {
+{%- if i18n == true %}
+    // Negotiated from the route prefix, the `locale` cookie or `Accept-Language`
+    let lang = request_locale().as_str();
+{% endif %}
     view! {
         <!DOCTYPE html>
-        <html lang="en">
+        {% if i18n == true %}<html lang=lang>{% else %}<html lang="en">{% endif %}
             <head>
                 <meta charset="utf-8" />
                 <meta name="viewport" content="width=device-width, initial-scale=1" />
                 <AutoReload options=options.clone() />
-                <HydrationScripts options />
+                <HydrationScripts options{% if islands == true %} islands=true{% endif %} />
                 <Link rel="shortcut icon" type_="image/ico" href="/favicon.ico" />
-                <Meta name="color-scheme" content="dark" />
+                {%- if pwa == true %}
+                <link rel="manifest" href=MANIFEST_PATH />
+                <meta name="theme-color" content=THEME_COLOR />
+                <script src="/pwa.js" defer></script>
+                {%- endif %}
                 {% if style == "unocss" %}<Stylesheet id="uno" href="/uno.css" />{%else%}<Stylesheet id="leptos" href="/pkg/{{project-name}}.css" />{% endif %}
                 <MetaTags />
             </head>
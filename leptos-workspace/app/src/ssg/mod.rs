@@ -0,0 +1,29 @@
+//! Static site generation of pages that need no per-request rendering.
+//!
+//! # Architecture
+//!
+//! - [`static_page`] - Rendering mode of a static route: rendered to
+//!   `target/site/<path>.html` when the server starts, then served from disk
+//! - [`invalidate`] / [`invalidate_all`] - Regenerate static pages in the
+//!   background, e.g. after their content changed
+//!
+//! The server renders the static pages at startup, adds their cache headers,
+//! and exposes the invalidation API (`server/src/server/ssg.rs`).
+//!
+{% if i18n == true -%}
+//! Static pages are rendered without a request: they show the default theme
+//! and the locale of their prefix, whatever the cookies of the visitor.
+{% else -%}
+//! Static pages are rendered without a request: they show the default theme,
+//! whatever the cookies of the visitor.
+{% endif -%}
+//!
+//! # Example
+//!
+//! ```ignore
+//! <Route path=path!("about") view={Lazy::<AboutPage>::new()} ssr=static_page() />
+//! ```
+
+mod regeneration;
+
+pub use regeneration::{invalidate, invalidate_all, static_page};
@@ -0,0 +1,87 @@
+use std::sync::{LazyLock, Mutex, PoisonError};
+
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use leptos::prelude::*;
+use leptos_router::SsrMode;
+use leptos_router::location::RequestUrl;
+use leptos_router::static_routes::StaticRoute;
+
+/// A rendered static page, regenerated when its path is invalidated.
+struct StaticPage {
+    path: String,
+    regenerate: UnboundedSender<()>,
+}
+
+/// Static pages rendered by this server.
+static PAGES: LazyLock<Mutex<Vec<StaticPage>>> = LazyLock::new(Default::default);
+
+/// Rendering mode of a static page: rendered once to `target/site`, then
+/// served from disk until [`invalidate`]d.
+///
+/// # Example
+///
+/// ```ignore
+/// <Route path=path!("about") view=AboutPage ssr=static_page() />
+/// ```
+pub fn static_page() -> SsrMode {
+    SsrMode::Static(StaticRoute::new().regenerate(|_params| invalidations()))
+}
+
+/// Regenerates the static page of `path`, e.g. `/about`, in the background.
+///
+/// Returns whether `path` is a rendered static page.
+pub fn invalidate(path: &str) -> bool {
+    let path = normalize(path);
+
+    notify(|page| page.path == path) > 0
+}
+
+/// Regenerates every static page in the background.
+///
+/// Returns the number of pages being regenerated.
+pub fn invalidate_all() -> usize {
+    notify(|_| true)
+}
+
+/// Stream of the invalidations of the page being rendered, listened to by
+/// Leptos to regenerate it.
+fn invalidations() -> UnboundedReceiver<()> {
+    let path = use_context::<RequestUrl>()
+        .and_then(|url| url.parse().ok())
+        .map(|url| normalize(url.path()))
+        .unwrap_or_else(|| "/".to_string());
+    let (regenerate, invalidations) = mpsc::unbounded();
+
+    PAGES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(StaticPage { path, regenerate });
+
+    invalidations
+}
+
+/// Notifies the matching pages, forgetting those no longer listening.
+fn notify(matches: impl Fn(&StaticPage) -> bool) -> usize {
+    let mut pages = PAGES.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut notified = 0;
+
+    pages.retain(|page| {
+        if !matches(page) {
+            return !page.regenerate.is_closed();
+        }
+        let listening = page.regenerate.unbounded_send(()).is_ok();
+        notified += usize::from(listening);
+
+        listening
+    });
+
+    notified
+}
+
+/// `/about/` and `/about` are the same page.
+fn normalize(path: &str) -> String {
+    match path.trim_end_matches('/') {
+        "" => "/".to_string(),
+        path => path.to_string(),
+    }
+}
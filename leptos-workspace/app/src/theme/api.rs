@@ -0,0 +1,25 @@
+use leptos::prelude::*;
+
+#[cfg(feature = "ssr")]
+use super::preference::THEME_COOKIE;
+use super::preference::Theme;
+use crate::errors::AppError;
+
+/// Lifetime of the theme cookie: one year.
+#[cfg(feature = "ssr")]
+const THEME_COOKIE_MAX_AGE: std::time::Duration =
+    std::time::Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Remembers the theme of this browser in a cookie, read back during SSR.
+///
+/// The cookie is not `HttpOnly`: it holds no secret.
+#[server]
+pub async fn set_theme(theme: Theme) -> Result<(), AppError> {
+    use crate::cookies::SetCookie;
+
+    SetCookie::new(THEME_COOKIE, &theme.to_string(), THEME_COOKIE_MAX_AGE)
+        .set()
+        .map_err(AppError::internal)?;
+
+    Ok(())
+}
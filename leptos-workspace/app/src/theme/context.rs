@@ -0,0 +1,67 @@
+use leptos::prelude::*;
+
+use super::api::SetTheme;
+#[cfg(feature = "ssr")]
+use super::preference::THEME_COOKIE;
+use super::preference::Theme;
+
+/// Theme state shared through context.
+#[derive(Clone, Copy)]
+pub struct ThemeContext {
+    /// The current theme, updated as soon as a new one is chosen.
+    pub theme: Signal<Theme>,
+
+    /// Dispatched by the toggle (`<ActionForm action=theme.set_theme>`).
+    pub set_theme: ServerAction<SetTheme>,
+}
+
+/// Creates the theme state and provides it to every component below the
+/// caller, typically `App`.
+///
+/// The initial theme comes from the cookie of the request during SSR, and
+/// from the `data-theme` attribute rendered by the server when hydrating,
+/// so both render the same theme.
+pub fn provide_theme() -> ThemeContext {
+    let set_theme = ServerAction::<SetTheme>::new();
+    let theme = RwSignal::new(initial_theme());
+
+    // Switch right away rather than after the cookie is set
+    Effect::new(move |_| {
+        if let Some(input) = set_theme.input().get() {
+            theme.set(input.theme);
+        }
+    });
+
+    let context = ThemeContext {
+        theme: theme.into(),
+        set_theme,
+    };
+    provide_context(context);
+
+    context
+}
+
+/// Returns the theme state provided by `provide_theme()`.
+///
+/// # Panics
+///
+/// Panics if no ancestor called `provide_theme()`.
+pub fn use_theme() -> ThemeContext {
+    expect_context::<ThemeContext>()
+}
+
+#[cfg(feature = "ssr")]
+fn initial_theme() -> Theme {
+    crate::cookies::request_cookie(THEME_COOKIE)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(not(feature = "ssr"))]
+fn initial_theme() -> Theme {
+    document()
+        .document_element()
+        .and_then(|html| html.get_attribute("data-theme"))
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default()
+}
@@ -0,0 +1,43 @@
+//! Light, dark and system color themes.
+//!
+//! # Architecture
+//!
+//! - [`Theme`] - The theme, rendered as `<html data-theme="...">`
+//! - [`set_theme`] - Server function saving the theme in the
+//!   [`THEME_COOKIE`] cookie
+//! - [`provide_theme`] / [`use_theme`] - The current theme as a signal, read
+//!   from the cookie during SSR so the page renders without a flash
+//! - [`ThemeHead`] - Sets the `data-theme` attribute and the `color-scheme`
+//!   meta tag
+//! - [`ThemeToggle`] - Buttons choosing the theme
+//!
+//! The styles pick their colors from the attribute: `style/main.scss`, or
+//! the preflight of `uno.config.ts` with the UnoCSS style.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // In `App`
+//! provide_theme();
+//!
+//! view! {
+//!     <ThemeHead />
+//!     <footer>
+//!         <ThemeToggle />
+//!     </footer>
+//! }
+//!
+//! // In any component
+//! let theme = use_theme().theme;
+//! let dark = move || theme.get() == Theme::Dark;
+//! ```
+
+mod api;
+mod context;
+mod preference;
+mod toggle;
+
+pub use api::{SetTheme, set_theme};
+pub use context::{ThemeContext, provide_theme, use_theme};
+pub use preference::{THEME_COOKIE, Theme};
+pub use toggle::{ThemeHead, ThemeToggle};
@@ -0,0 +1,49 @@
+use leptos::prelude::*;
+use leptos_meta::{Html, Meta};
+
+use super::context::use_theme;
+use super::preference::Theme;
+
+/// Renders the current theme on `<html data-theme>` and in the
+/// `color-scheme` meta tag.
+///
+/// Rendered once by `App`: the styles select their colors from the
+/// attribute.
+#[component]
+pub fn ThemeHead() -> impl IntoView {
+    let theme = use_theme().theme;
+
+    view! {
+        <Html {..} data-theme=move || theme.get().as_str() />
+        <Meta name="color-scheme" content=move || theme.get().color_scheme() />
+    }
+}
+
+/// Buttons choosing the light, dark or system theme.
+///
+/// A plain form: without JavaScript, the choice is saved and the page
+/// reloaded by the server.
+#[component]
+pub fn ThemeToggle() -> impl IntoView {
+    let theme = use_theme();
+
+    view! {
+        <ActionForm action=theme.set_theme attr:class="theme-toggle">
+            {Theme::ALL
+                .into_iter()
+                .map(|option| {
+                    view! {
+                        <button
+                            type="submit"
+                            name="theme"
+                            value=option.as_str()
+                            aria-pressed=move || (theme.theme.get() == option).to_string()
+                        >
+                            {option.label()}
+                        </button>
+                    }
+                })
+                .collect_view()}
+        </ActionForm>
+    }
+}
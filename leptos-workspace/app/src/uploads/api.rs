@@ -0,0 +1,86 @@
+#[cfg(feature = "ssr")]
+use futures::StreamExt;
+use leptos::prelude::*;
+use leptos::server_fn::codec::{MultipartData, MultipartFormData, StreamingText, TextStream};
+
+use super::StoredFile;
+#[cfg(feature = "ssr")]
+use super::server::{UploadError, UploadState, UploadTracker, received_bytes};
+#[cfg(feature = "ssr")]
+use super::{FILE_FIELD, UPLOAD_ID_FIELD};
+use crate::errors::AppError;
+
+/// Stores the file of a multipart form, and returns it.
+///
+/// The form may start with an `upload_id` field: the bytes received are then
+/// published to [`upload_progress`] under this id. The `file` field is
+/// streamed to the storage, never held in memory.
+///
+/// # Errors
+///
+/// * `AppError::Validation` - No file, or a file too large, of a type not
+///   accepted, or not matching its type
+#[server(input = MultipartFormData)]
+pub async fn upload_file(data: MultipartData) -> Result<StoredFile, AppError> {
+    let uploads = UploadState::from_context()?;
+    let mut multipart = data.into_inner().ok_or(UploadError::MissingFile)?;
+
+    let mut tracker = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(UploadError::multipart)?
+    {
+        let field_name = field.name().map(str::to_string);
+        match field_name.as_deref() {
+            Some(UPLOAD_ID_FIELD) => {
+                let upload_id = field.text().await.map_err(UploadError::multipart)?;
+                tracker = Some(UploadTracker::start(&upload_id));
+            }
+            Some(FILE_FIELD) => {
+                // Browsers send an empty file when none is selected
+                let name = field.file_name().unwrap_or_default().to_string();
+                if name.is_empty() {
+                    return Err(UploadError::MissingFile.into());
+                }
+                let content_type = field
+                    .content_type()
+                    .map_or("application/octet-stream", |mime| mime.essence_str())
+                    .to_string();
+                let content = field
+                    .map(|chunk| chunk.map_err(UploadError::multipart))
+                    .boxed();
+
+                let file = uploads
+                    .store(&name, &content_type, content, |received| {
+                        if let Some(tracker) = &tracker {
+                            tracker.set(received);
+                        }
+                    })
+                    .await?;
+
+                return Ok(file);
+            }
+            _ => {}
+        }
+    }
+
+    Err(UploadError::MissingFile.into())
+}
+
+/// Streams the bytes received by an upload, one number per line, until it
+/// is stored or rejected.
+#[server(output = StreamingText)]
+pub async fn upload_progress(upload_id: String) -> Result<TextStream<AppError>, AppError> {
+    let lines = received_bytes(&upload_id).map(|received| Ok(format!("{received}\n")));
+
+    Ok(TextStream::new(lines))
+}
+
+/// Returns the stored files, newest first.
+#[server]
+pub async fn list_files() -> Result<Vec<StoredFile>, AppError> {
+    let uploads = UploadState::from_context()?;
+
+    Ok(uploads.list().await?)
+}
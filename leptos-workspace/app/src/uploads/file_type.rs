@@ -0,0 +1,72 @@
+/// A type of file accepted by uploads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileType {
+    /// MIME type, as sent by the browser and served back on download.
+    pub mime: &'static str,
+
+    /// Extension of the stored files, which gives back their type.
+    pub extension: &'static str,
+
+    /// First bytes of every file of this type, empty if there are none.
+    pub signature: &'static [u8],
+}
+
+impl FileType {
+    /// Checks that the first bytes of a file start like this type.
+    ///
+    /// `head` holds the whole signature unless the file is shorter, which
+    /// then never matches.
+    pub fn matches(&self, head: &[u8]) -> bool {
+        head.starts_with(self.signature)
+    }
+}
+
+/// Types accepted unless set with `UploadState::with_file_types`.
+///
+/// SVG and HTML are left out on purpose: served back from the site, they
+/// could run scripts.
+pub const DEFAULT_FILE_TYPES: &[FileType] = &[
+    FileType {
+        mime: "image/png",
+        extension: "png",
+        signature: b"\x89PNG\r\n\x1a\n",
+    },
+    FileType {
+        mime: "image/jpeg",
+        extension: "jpg",
+        signature: b"\xff\xd8\xff",
+    },
+    FileType {
+        mime: "image/gif",
+        extension: "gif",
+        signature: b"GIF8",
+    },
+    FileType {
+        mime: "application/pdf",
+        extension: "pdf",
+        signature: b"%PDF-",
+    },
+    FileType {
+        mime: "text/plain",
+        extension: "txt",
+        signature: b"",
+    },
+];
+
+/// Returns the type of a MIME type, e.g. `image/png`.
+pub fn file_type_of_mime(file_types: &[FileType], mime: &str) -> Option<FileType> {
+    file_types
+        .iter()
+        .copied()
+        .find(|file_type| file_type.mime == mime)
+}
+
+/// Returns the type of a file name, from its extension.
+pub fn file_type_of_name(file_types: &[FileType], name: &str) -> Option<FileType> {
+    let (_, extension) = name.rsplit_once('.')?;
+
+    file_types
+        .iter()
+        .copied()
+        .find(|file_type| file_type.extension.eq_ignore_ascii_case(extension))
+}
@@ -0,0 +1,49 @@
+//! File uploads: multipart server functions, progress and pluggable storage.
+//!
+//! # Architecture
+//!
+//! - [`upload_file`] - Multipart server function streaming a file to the
+//!   storage, within the size and type limits
+//! - [`upload_progress`] - Streams the bytes received by an upload, for a
+//!   progress bar
+//! - [`list_files`] - The stored files
+//! - [`Uploader`] - Upload form with progress, and the stored files
+//! - [`server`] (SSR only) - [`UploadState`](server::UploadState) with the
+//!   limits, and the [`FileStorage`](server::FileStorage) trait, implemented
+//!   by [`LocalStorage`](server::LocalStorage)
+//!
+//! The server serves the stored files at [`DOWNLOAD_PATH`]`/<key>`, with
+//! the content type of their extension.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Another storage, e.g. an S3-compatible bucket
+//! let uploads = UploadState::new(S3Storage::new(bucket))
+//!     .with_max_size(50 * 1024 * 1024)
+//!     .with_file_types(&[FileType { mime: "image/png", extension: "png", signature: b"\x89PNG" }]);
+//! ```
+
+mod api;
+mod file_type;
+#[cfg(feature = "ssr")]
+pub mod server;
+mod stored_file;
+mod uploader;
+
+pub use api::{ListFiles, UploadFile, UploadProgress, list_files, upload_file, upload_progress};
+pub use file_type::{DEFAULT_FILE_TYPES, FileType, file_type_of_mime, file_type_of_name};
+pub use stored_file::StoredFile;
+pub use uploader::Uploader;
+
+/// Largest accepted file unless set with `UploadState::with_max_size`: 10 MB.
+pub const DEFAULT_MAX_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Path of the route serving the stored files.
+pub const DOWNLOAD_PATH: &str = "/files";
+
+/// Name of the form field of the file.
+pub const FILE_FIELD: &str = "file";
+
+/// Name of the form field of the upload id, sent before the file.
+pub const UPLOAD_ID_FIELD: &str = "upload_id";
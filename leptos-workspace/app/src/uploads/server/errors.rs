@@ -0,0 +1,65 @@
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::errors::{AppError, FieldErrors};
+
+use super::super::FILE_FIELD;
+
+#[derive(Debug, Error)]
+pub enum UploadError {
+    // ====== Validation =====
+    #[error("File exceeds the limit of {} MB", max_size / 1024 / 1024)]
+    TooLarge { max_size: u64 },
+
+    #[error("Files of type {0} are not accepted")]
+    UnsupportedType(String),
+
+    #[error("The content of the file is not {0}")]
+    ContentMismatch(&'static str),
+
+    #[error("No file to upload")]
+    MissingFile,
+
+    #[error("Invalid file key {0:?}")]
+    InvalidKey(String),
+
+    // ====== Request =====
+    #[error("Multipart: {0}")]
+    Multipart(String),
+
+    // ====== Storage =====
+    #[error("Storage: {0}")]
+    Storage(String),
+
+    #[error("Io: {0}")]
+    Io(#[from] std::io::Error),
+
+    // ====== Context =====
+    #[error("UploadState is not provided: add it with `leptos_routes_with_context`")]
+    MissingState,
+}
+
+impl UploadError {
+    /// Wraps an error of the multipart request, e.g. a closed connection.
+    pub fn multipart(error: impl fmt::Display) -> Self {
+        Self::Multipart(error.to_string())
+    }
+}
+
+/// Rejected files are validation errors of the file field; the rest is only
+/// logged, the client gets `AppError::Internal`.
+impl From<UploadError> for AppError {
+    fn from(error: UploadError) -> Self {
+        match error {
+            UploadError::TooLarge { .. }
+            | UploadError::UnsupportedType(_)
+            | UploadError::ContentMismatch(_)
+            | UploadError::MissingFile => FieldErrors::new()
+                .with(FILE_FIELD, error.to_string())
+                .into(),
+            UploadError::InvalidKey(key) => AppError::NotFound(format!("File {key}")),
+            error => AppError::internal(error),
+        }
+    }
+}
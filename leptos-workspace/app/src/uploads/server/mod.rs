@@ -0,0 +1,383 @@
+//! Server side of uploads (SSR only).
+//!
+//! [`UploadState`] bundles the storage and the limits, and is provided to
+//! server functions through the Leptos context by the server:
+//!
+//! ```ignore
+//! let uploads = UploadState::new(LocalStorage::new("uploads")).with_max_size(50 * 1024 * 1024);
+//!
+//! Router::new().leptos_routes_with_context(
+//!     &leptos_options,
+//!     routes,
+//!     move || provide_context(uploads.clone()),
+//!     move || shell(leptos_options.clone()),
+//! )
+//! ```
+
+mod errors;
+mod progress;
+mod storage;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use futures::StreamExt;
+use futures::stream::{self, BoxStream, Fuse};
+use leptos::prelude::use_context;
+
+pub use errors::UploadError;
+pub use progress::{UploadTracker, received_bytes};
+pub use storage::{FileStorage, LocalStorage, StoredContent, StoredObject};
+
+use super::{DEFAULT_FILE_TYPES, DEFAULT_MAX_SIZE, FileType, StoredFile};
+use super::{file_type_of_mime, file_type_of_name};
+
+/// Future returned by [`FileStorage`].
+pub type StorageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, UploadError>> + Send + 'a>>;
+
+/// Content of a file, chunk by chunk.
+pub type ByteStream<'a> = BoxStream<'a, Result<Bytes, UploadError>>;
+
+/// Storage and limits of uploads, cheap to clone.
+#[derive(Clone)]
+pub struct UploadState {
+    storage: Arc<dyn FileStorage>,
+    max_size: u64,
+    file_types: &'static [FileType],
+}
+
+impl UploadState {
+    /// Creates the state from a storage, with [`DEFAULT_MAX_SIZE`] and
+    /// [`DEFAULT_FILE_TYPES`].
+    pub fn new(storage: impl FileStorage) -> Self {
+        Self {
+            storage: Arc::new(storage),
+            max_size: DEFAULT_MAX_SIZE,
+            file_types: DEFAULT_FILE_TYPES,
+        }
+    }
+
+    /// Sets the largest accepted file, in bytes.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Sets the accepted types of files.
+    pub fn with_file_types(mut self, file_types: &'static [FileType]) -> Self {
+        self.file_types = file_types;
+        self
+    }
+
+    /// Returns the state provided to the current server function.
+    ///
+    /// # Errors
+    ///
+    /// * `UploadError::MissingState` - The server did not provide it
+    pub fn from_context() -> Result<Self, UploadError> {
+        use_context::<Self>().ok_or(UploadError::MissingState)
+    }
+
+    /// Checks and stores an uploaded file, calling `on_progress` with the
+    /// bytes received so far.
+    ///
+    /// The type sent by the browser must be accepted, and match the first
+    /// bytes of the file. The content is streamed to the storage, and the
+    /// upload stops as soon as it exceeds the size limit.
+    ///
+    /// # Errors
+    ///
+    /// * `UploadError::UnsupportedType` - The type is not accepted
+    /// * `UploadError::ContentMismatch` - The content is not of this type
+    /// * `UploadError::TooLarge` - The file exceeds the size limit
+    /// * `UploadError::MissingFile` - The file is empty
+    /// * `UploadError::Multipart` / `UploadError::Storage` / `UploadError::Io` -
+    ///   The request or the storage failed
+    pub async fn store(
+        &self,
+        name: &str,
+        content_type: &str,
+        content: ByteStream<'_>,
+        on_progress: impl Fn(u64) + Send,
+    ) -> Result<StoredFile, UploadError> {
+        let file_type = file_type_of_mime(self.file_types, content_type)
+            .ok_or_else(|| UploadError::UnsupportedType(content_type.to_string()))?;
+        let key = new_key(name, file_type);
+        let max_size = self.max_size;
+
+        let mut received = 0;
+        let content = check_signature(content, file_type).map(move |chunk| {
+            let chunk = chunk?;
+            received += chunk.len() as u64;
+            if received > max_size {
+                return Err(UploadError::TooLarge { max_size });
+            }
+            on_progress(received);
+
+            Ok(chunk)
+        });
+
+        let size = self.storage.put(&key, content.boxed()).await?;
+        if size == 0 {
+            self.storage.delete(&key).await?;
+            return Err(UploadError::MissingFile);
+        }
+
+        Ok(stored_file(key, file_type, size))
+    }
+
+    /// Returns a stored file and its content, `None` if missing.
+    ///
+    /// # Errors
+    ///
+    /// * `UploadError::InvalidKey` - The key can not be one of a stored file
+    /// * `UploadError::Storage` / `UploadError::Io` - The storage failed
+    pub async fn open(
+        &self,
+        key: &str,
+    ) -> Result<Option<(StoredFile, ByteStream<'static>)>, UploadError> {
+        if !is_valid_key(key) {
+            return Err(UploadError::InvalidKey(key.to_string()));
+        }
+        let Some(file_type) = file_type_of_name(self.file_types, key) else {
+            return Ok(None);
+        };
+
+        let stored = self.storage.get(key).await?;
+
+        Ok(stored.map(|stored| {
+            let file = stored_file(key.to_string(), file_type, stored.size);
+            (file, stored.content)
+        }))
+    }
+
+    /// Returns the stored files of accepted types, newest first.
+    ///
+    /// # Errors
+    ///
+    /// * `UploadError::Storage` / `UploadError::Io` - The storage failed
+    pub async fn list(&self) -> Result<Vec<StoredFile>, UploadError> {
+        let mut files: Vec<_> = self
+            .storage
+            .list()
+            .await?
+            .into_iter()
+            .filter(|object| is_valid_key(&object.key))
+            .filter_map(|object| {
+                let file_type = file_type_of_name(self.file_types, &object.key)?;
+                Some(stored_file(object.key, file_type, object.size))
+            })
+            .collect();
+
+        // Keys start with their upload time
+        files.sort_unstable_by(|a, b| b.key.cmp(&a.key));
+
+        Ok(files)
+    }
+}
+
+/// Holds back the first chunks of `content` until they cover the signature
+/// of `file_type`, then checks it: a client may send a file byte by byte.
+fn check_signature(content: ByteStream<'_>, file_type: FileType) -> ByteStream<'_> {
+    // The bytes received so far, until checked
+    let head = Some(Vec::new());
+
+    stream::unfold((content.fuse(), head), move |(content, head)| {
+        next_checked(content, head, file_type)
+    })
+    .boxed()
+}
+
+/// Next chunk of [`check_signature`], and what is left of the content.
+type Checked<'a> = (
+    Result<Bytes, UploadError>,
+    (Fuse<ByteStream<'a>>, Option<Vec<u8>>),
+);
+
+async fn next_checked(
+    mut content: Fuse<ByteStream<'_>>,
+    mut head: Option<Vec<u8>>,
+    file_type: FileType,
+) -> Option<Checked<'_>> {
+    let Some(buffer) = head.as_mut() else {
+        let chunk = content.next().await?;
+        return Some((chunk, (content, None)));
+    };
+
+    loop {
+        match content.next().await {
+            Some(Ok(chunk)) => {
+                buffer.extend_from_slice(&chunk);
+                if buffer.len() >= file_type.signature.len() {
+                    break;
+                }
+            }
+            Some(Err(e)) => return Some((Err(e), (content, None))),
+            // Shorter than the signature, unless empty
+            None if buffer.is_empty() => return None,
+            None => break,
+        }
+    }
+
+    let head = head.take().unwrap_or_default();
+    let checked = if file_type.matches(&head) {
+        Ok(Bytes::from(head))
+    } else {
+        Err(UploadError::ContentMismatch(file_type.mime))
+    };
+
+    Some((checked, (content, None)))
+}
+
+/// Returns a new key: the upload time, a counter, and the file name with
+/// the extension of its type, e.g. `18c5f6a1b2c3d4e50001-report.pdf`.
+fn new_key(name: &str, file_type: FileType) -> String {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed) % 0x10000;
+
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    let stem: String = stem
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+            _ => '_',
+        })
+        .take(64)
+        .collect();
+    let stem = if stem.is_empty() { "file" } else { &stem };
+
+    format!("{nanos:016x}{count:04x}-{stem}.{}", file_type.extension)
+}
+
+/// Keys are made by [`new_key`]: anything else, e.g. `../secret`, is
+/// rejected before reaching the storage.
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with('.')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn stored_file(key: String, file_type: FileType, size: u64) -> StoredFile {
+    // The uploaded name follows the time and counter
+    let name = key
+        .split_once('-')
+        .map_or(key.as_str(), |(_, name)| name)
+        .to_string();
+
+    StoredFile {
+        key,
+        name,
+        content_type: file_type.mime.to_string(),
+        size,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    const PNG: FileType = DEFAULT_FILE_TYPES[0];
+    const TXT: FileType = DEFAULT_FILE_TYPES[4];
+
+    /// Runs `content`, sent in chunks, through `check_signature`.
+    fn check(chunks: &[&'static [u8]], file_type: FileType) -> Result<Vec<u8>, UploadError> {
+        let chunks = chunks.iter().map(|chunk| Ok(Bytes::from_static(chunk)));
+        let mut content = check_signature(stream::iter(chunks).boxed(), file_type);
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = block_on(content.next()) {
+            bytes.extend_from_slice(&chunk?);
+        }
+
+        Ok(bytes)
+    }
+
+    #[test]
+    fn signatures_match_whole_only() {
+        assert!(PNG.matches(b"\x89PNG\r\n\x1a\n and the rest"));
+        assert!(!PNG.matches(b"\x89"));
+        assert!(!PNG.matches(b""));
+        assert!(!PNG.matches(b"GIF89a"));
+        assert!(TXT.matches(b""));
+    }
+
+    #[test]
+    fn signature_is_checked_across_short_chunks() {
+        let chunks: &[&[u8]] = &[b"\x89", b"PN", b"G\r\n\x1a", b"\n", b"data"];
+
+        assert_eq!(check(chunks, PNG).unwrap(), b"\x89PNG\r\n\x1a\ndata");
+    }
+
+    #[test]
+    fn short_chunks_of_another_type_are_rejected() {
+        let chunks: &[&[u8]] = &[b"\x89", b"GIF89a", b"data"];
+
+        assert!(matches!(
+            check(chunks, PNG),
+            Err(UploadError::ContentMismatch("image/png"))
+        ));
+    }
+
+    #[test]
+    fn files_shorter_than_their_signature_are_rejected() {
+        assert!(matches!(
+            check(&[b"\x89", b"PNG"], PNG),
+            Err(UploadError::ContentMismatch("image/png"))
+        ));
+        // Left to `store`, as a missing file
+        assert_eq!(check(&[], PNG).unwrap(), b"");
+    }
+
+    #[test]
+    fn keys_outside_of_the_storage_are_invalid() {
+        assert!(is_valid_key("18c5f6a1b2c3d4e50001-report.pdf"));
+
+        assert!(!is_valid_key(""));
+        assert!(!is_valid_key("../x"));
+        assert!(!is_valid_key("a/../../x"));
+        assert!(!is_valid_key(".x.part"));
+        assert!(!is_valid_key("..\\x"));
+        assert!(!is_valid_key("x%2f..%2fy"));
+    }
+
+    #[test]
+    fn new_keys_are_valid_and_sanitized() {
+        let key = new_key("../Résumé été.final.PDF", DEFAULT_FILE_TYPES[3]);
+        let (prefix, name) = key.split_once('-').unwrap();
+
+        assert_eq!(prefix.len(), 20);
+        assert_eq!(name, "___R_sum___t__final.pdf");
+        assert!(is_valid_key(&key));
+
+        let key = new_key(".part", TXT);
+        assert!(key.ends_with("-file.txt"));
+        assert!(is_valid_key(&key));
+    }
+
+    #[test]
+    fn new_keys_are_unique() {
+        assert_ne!(new_key("a.txt", TXT), new_key("a.txt", TXT));
+    }
+
+    #[test]
+    fn stored_files_keep_the_uploaded_name() {
+        let file = stored_file("18c5f6a1b2c3d4e50001-my-report.pdf".to_string(), TXT, 3);
+
+        assert_eq!(file.name, "my-report.pdf");
+        assert_eq!(file.content_type, "text/plain");
+        assert_eq!(file.size, 3);
+    }
+}
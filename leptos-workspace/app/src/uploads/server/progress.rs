@@ -0,0 +1,84 @@
+//! Progress of the uploads in flight, by upload id.
+//!
+//! The upload and its progress stream are two requests, in any order: the
+//! first one creates the channel, and the upload removes it once done.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::Duration;
+
+use futures::Stream;
+use tokio::sync::watch;
+
+/// Time after which a progress stream without news ends, e.g. for an upload
+/// that never started.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+static UPLOADS: LazyLock<Mutex<HashMap<String, watch::Sender<u64>>>> =
+    LazyLock::new(Default::default);
+
+/// Bytes received by an upload, published until dropped.
+pub struct UploadTracker {
+    upload_id: String,
+    sender: watch::Sender<u64>,
+}
+
+impl UploadTracker {
+    pub fn start(upload_id: &str) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+            sender: sender(upload_id),
+        }
+    }
+
+    pub fn set(&self, received: u64) {
+        self.sender.send_replace(received);
+    }
+}
+
+impl Drop for UploadTracker {
+    /// Ends the progress streams, once they got the last value.
+    fn drop(&mut self) {
+        remove(&self.upload_id);
+    }
+}
+
+/// Returns the bytes received by an upload, as they arrive.
+pub fn received_bytes(upload_id: &str) -> impl Stream<Item = u64> + Send + 'static {
+    let upload_id = upload_id.to_string();
+    let receiver = sender(&upload_id).subscribe();
+
+    futures::stream::unfold(receiver, move |mut receiver| {
+        let upload_id = upload_id.clone();
+        async move {
+            match tokio::time::timeout(IDLE_TIMEOUT, receiver.changed()).await {
+                Ok(Ok(())) => {
+                    let received = *receiver.borrow_and_update();
+                    Some((received, receiver))
+                }
+                // Done
+                Ok(Err(_)) => None,
+                Err(_) => {
+                    remove(&upload_id);
+                    None
+                }
+            }
+        }
+    })
+}
+
+fn sender(upload_id: &str) -> watch::Sender<u64> {
+    UPLOADS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(upload_id.to_string())
+        .or_insert_with(|| watch::channel(0).0)
+        .clone()
+}
+
+fn remove(upload_id: &str) {
+    UPLOADS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .remove(upload_id);
+}
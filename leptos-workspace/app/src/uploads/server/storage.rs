@@ -0,0 +1,176 @@
+//! File storages.
+
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use super::{ByteStream, StorageFuture, UploadError};
+
+/// Size of the chunks read from disk by [`LocalStorage`].
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Content of a stored file.
+pub struct StoredContent {
+    /// Size, in bytes.
+    pub size: u64,
+
+    pub content: ByteStream<'static>,
+}
+
+/// A stored file, as listed by [`FileStorage::list`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredObject {
+    pub key: String,
+
+    /// Size, in bytes.
+    pub size: u64,
+}
+
+/// Storage of the uploaded files, by key.
+///
+/// Keys are checked by `UploadState`: ASCII letters, digits, `-`, `_` and
+/// `.`, not starting with `.`.
+///
+/// Implement it to keep files elsewhere than on the local disk, e.g. in an
+/// S3-compatible bucket, and pass it to `UploadState::new`.
+///
+/// # Example
+///
+/// ```ignore
+/// impl FileStorage for S3Storage {
+///     fn put<'a>(&'a self, key: &'a str, content: ByteStream<'a>) -> StorageFuture<'a, u64> {
+///         Box::pin(async move {
+///             // Multipart upload of `content` to s3://bucket/{key}
+///             Ok(size)
+///         })
+///     }
+///     // ...
+/// }
+/// ```
+pub trait FileStorage: Send + Sync + 'static {
+    /// Stores a new file, and returns its size.
+    ///
+    /// Nothing is kept if `content` fails, e.g. past the size limit.
+    fn put<'a>(&'a self, key: &'a str, content: ByteStream<'a>) -> StorageFuture<'a, u64>;
+
+    /// Returns the content of a file, `None` if missing.
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Option<StoredContent>>;
+
+    /// Returns every stored file, in any order.
+    fn list(&self) -> StorageFuture<'_, Vec<StoredObject>>;
+
+    /// Removes a file; missing files are ignored.
+    fn delete<'a>(&'a self, key: &'a str) -> StorageFuture<'a, ()>;
+}
+
+/// Storage in a local directory, created on the first upload.
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    dir: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Writes to a hidden `.part` file, renamed once complete: a failed
+    /// upload is never listed nor served.
+    async fn write(&self, key: &str, mut content: ByteStream<'_>) -> Result<u64, UploadError> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let partial = self.dir.join(format!(".{key}.part"));
+        let mut file = tokio::fs::File::create(&partial).await?;
+
+        let mut size = 0;
+        let written: Result<(), UploadError> = async {
+            while let Some(chunk) = content.try_next().await? {
+                file.write_all(&chunk).await?;
+                size += chunk.len() as u64;
+            }
+            file.flush().await?;
+
+            Ok(())
+        }
+        .await;
+
+        drop(file);
+        if let Err(error) = written {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(error);
+        }
+        tokio::fs::rename(&partial, self.dir.join(key)).await?;
+
+        Ok(size)
+    }
+}
+
+impl FileStorage for LocalStorage {
+    fn put<'a>(&'a self, key: &'a str, content: ByteStream<'a>) -> StorageFuture<'a, u64> {
+        Box::pin(self.write(key, content))
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Option<StoredContent>> {
+        Box::pin(async move {
+            let file = match tokio::fs::File::open(self.dir.join(key)).await {
+                Ok(file) => file,
+                Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+                Err(error) => return Err(error.into()),
+            };
+            let size = file.metadata().await?.len();
+
+            let content = futures::stream::try_unfold(file, |mut file| async move {
+                let mut chunk = vec![0; READ_CHUNK_SIZE];
+                let read = file.read(&mut chunk).await?;
+                if read == 0 {
+                    return Ok(None);
+                }
+                chunk.truncate(read);
+
+                Ok::<_, UploadError>(Some((Bytes::from(chunk), file)))
+            });
+
+            Ok(Some(StoredContent {
+                size,
+                content: content.boxed(),
+            }))
+        })
+    }
+
+    fn list(&self) -> StorageFuture<'_, Vec<StoredObject>> {
+        Box::pin(async move {
+            let mut entries = match tokio::fs::read_dir(&self.dir).await {
+                Ok(entries) => entries,
+                // Nothing uploaded yet
+                Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(error) => return Err(error.into()),
+            };
+
+            let mut objects = Vec::new();
+            while let Some(entry) = entries.next_entry().await? {
+                let key = entry.file_name().to_string_lossy().into_owned();
+                let metadata = entry.metadata().await?;
+                if key.starts_with('.') || !metadata.is_file() {
+                    continue;
+                }
+                objects.push(StoredObject {
+                    key,
+                    size: metadata.len(),
+                });
+            }
+
+            Ok(objects)
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            match tokio::fs::remove_file(self.dir.join(key)).await {
+                Err(error) if error.kind() != ErrorKind::NotFound => Err(error.into()),
+                _ => Ok(()),
+            }
+        })
+    }
+}
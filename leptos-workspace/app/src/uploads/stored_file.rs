@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use super::DOWNLOAD_PATH;
+
+/// A file kept by the upload storage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredFile {
+    /// Unique name in the storage, e.g. `18c5f6a1b2c3d4e50001-report.pdf`.
+    pub key: String,
+
+    /// Name shown to users, from the uploaded file name.
+    pub name: String,
+
+    pub content_type: String,
+
+    /// Size, in bytes.
+    pub size: u64,
+}
+
+impl StoredFile {
+    /// Path of the download route serving this file.
+    pub fn url(&self) -> String {
+        format!("{DOWNLOAD_PATH}/{}", self.key)
+    }
+}
@@ -0,0 +1,188 @@
+use futures::StreamExt;
+use leptos::either::Either;
+use leptos::ev::SubmitEvent;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use web_sys::FormData;
+use web_sys::js_sys::{Date, Math};
+
+use super::api::{list_files, upload_file, upload_progress};
+use super::{DEFAULT_FILE_TYPES, FILE_FIELD, StoredFile, UPLOAD_ID_FIELD};
+use crate::errors::{FieldError, FormError};
+{% if i18n == true -%}
+use crate::i18n::t;
+{% endif -%}
+
+{% if islands == true -%}
+/// Upload form with its progress, and the stored files: an island, the
+/// only part of the page shipping WASM.
+///
+/// The upload and its progress are two requests, sharing a random upload
+/// id. `accept` lists [`DEFAULT_FILE_TYPES`]: the server checks the types
+/// it is configured with.
+#[island]
+{% else -%}
+/// Upload form with its progress, and the stored files.
+///
+/// The upload and its progress are two requests, sharing a random upload
+/// id. `accept` lists [`DEFAULT_FILE_TYPES`]: the server checks the types
+/// it is configured with.
+#[component]
+{% endif -%}
+pub fn Uploader() -> impl IntoView {
+    let upload = Action::new_local(|data: &FormData| upload_file(data.clone().into()));
+    let files = Resource::new(move || upload.version().get(), |_| list_files());
+    let input = NodeRef::<leptos::html::Input>::new();
+
+    // Bytes received by the server, and size of the file
+    let (progress, set_progress) = signal(None::<(u64, u64)>);
+
+    let error = Signal::derive(move || {
+        upload.value().with(|value| {
+            value
+                .as_ref()
+                .and_then(|result| result.as_ref().err().cloned())
+        })
+    });
+
+    // Complete once stored, gone once rejected
+    Effect::new(move |_| match upload.value().get() {
+        Some(Ok(_)) => set_progress.update(|progress| {
+            if let Some((received, size)) = progress {
+                *received = *size;
+            }
+        }),
+        Some(Err(_)) => set_progress.set(None),
+        None => {}
+    });
+
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let Some(file) = input
+            .get()
+            .and_then(|input| input.files())
+            .and_then(|files| files.get(0))
+        else {
+            return;
+        };
+
+        // The id comes first, so the server knows it when the file arrives
+        let upload_id = format!(
+            "{:x}-{:x}",
+            Date::now() as u64,
+            (Math::random() * 2f64.powi(52)) as u64
+        );
+        let Ok(data) = FormData::new() else {
+            return;
+        };
+        if data.append_with_str(UPLOAD_ID_FIELD, &upload_id).is_err()
+            || data
+                .append_with_blob_and_filename(FILE_FIELD, &file, &file.name())
+                .is_err()
+        {
+            return;
+        }
+
+        set_progress.set(Some((0, file.size() as u64)));
+        spawn_local(async move {
+            let Ok(lines) = upload_progress(upload_id).await else {
+                return;
+            };
+            let mut lines = lines.into_inner();
+            while let Some(Ok(chunk)) = lines.next().await {
+                // A chunk may hold several lines
+                if let Some(received) = chunk.lines().rev().find_map(|line| line.parse().ok()) {
+                    set_progress.update(|progress| {
+                        if let Some((current, _)) = progress {
+                            *current = received;
+                        }
+                    });
+                }
+            }
+        });
+        upload.dispatch_local(data);
+    };
+
+    let accept = DEFAULT_FILE_TYPES
+        .iter()
+        .map(|file_type| file_type.mime)
+        .collect::<Vec<_>>()
+        .join(",");
+
+    view! {
+        <form class="uploader" on:submit=on_submit>
+            <input type="file" name=FILE_FIELD accept=accept required node_ref=input />
+            <button type="submit" disabled=move || upload.pending().get()>
+                {%- if i18n == true %}
+                {t!("upload.submit")}
+                {%- else %}
+                "Upload"
+                {%- endif %}
+            </button>
+            <FieldError error field=FILE_FIELD />
+            <FormError error />
+        </form>
+        {move || {
+            progress
+                .get()
+                .map(|(received, size)| {
+                    let percent = (received * 100).checked_div(size).unwrap_or(100);
+                    view! {
+                        <div class="upload-progress">
+                            <progress max=size.to_string() value=received.to_string()></progress>
+                            <span>{format!("{percent}%")}</span>
+                        </div>
+                    }
+                })
+        }}
+        {%- if i18n == true %}
+        <h2>{t!("upload.files")}</h2>
+        {%- else %}
+        <h2>"Files"</h2>
+        {%- endif %}
+        <Transition>
+            {move || files.get().map(|files| files.map(|files| view! { <FileList files /> }))}
+        </Transition>
+    }
+}
+
+/// Links to the stored files, with their size.
+#[component]
+fn FileList(files: Vec<StoredFile>) -> impl IntoView {
+    if files.is_empty() {
+        {%- if i18n == true %}
+        return Either::Left(view! { <p>{t!("upload.no_files")}</p> });
+        {%- else %}
+        return Either::Left(view! { <p>"No files yet."</p> });
+        {%- endif %}
+    }
+
+    // `rel="external"`: downloads are not pages of the router
+    Either::Right(view! {
+        <ul class="files">
+            {files
+                .into_iter()
+                .map(|file| {
+                    view! {
+                        <li>
+                            <a href=file.url() rel="external">
+                                {file.name.clone()}
+                            </a>
+                            " "
+                            <span class="file-size">{format_size(file.size)}</span>
+                        </li>
+                    }
+                })
+                .collect_view()}
+        </ul>
+    })
+}
+
+/// e.g. `512 B`, `12.3 KB`, `4.0 MB`.
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{bytes} B"),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
@@ -1,8 +1,17 @@
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
 pub fn hydrate() {
+    {%- if islands == true %}
+    // Links the islands of `app`, hydrated where the server rendered them
+    #[allow(unused_imports)]
+    use app::*;
+
+    console_error_panic_hook::set_once();
+    leptos::mount::hydrate_islands();
+    {%- else %}
     use app::*;
 
     console_error_panic_hook::set_once();
     leptos::mount::hydrate_lazy(App);
+    {%- endif %}
 }
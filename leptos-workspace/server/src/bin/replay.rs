@@ -0,0 +1,59 @@
+//! Session replay binary.
+//!
+//! Replays sessions recorded with `WEBSOCKET_RECORD_DIR` against the current
+//! handler of their endpoint, and prints the responses that differ from the
+//! recording.
+//!
+//! # Usage
+//! ```bash
+//! WEBSOCKET_RECORD_DIR=recordings cargo leptos watch
+//! cargo run -p server --features ssr --bin replay -- rkyv_websocket recordings/1760781234567-0.log
+//! ```
+//!
+//! The first argument names the server function of the endpoint, one of
+//! [`PROTOCOLS`]. Exits with an error when any replay differs from its
+//! recording.
+
+use app::pages::chat::ws::message::chat_websocket_replay;
+use app::pages::home::ws::message::rkyv_websocket_replay;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+
+/// Server functions of the recorded endpoints.
+const PROTOCOLS: [&str; 2] = ["rkyv_websocket", "chat_websocket"];
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Install color-eyre for beautiful error messages
+    color_eyre::install()?;
+
+    let mut args = std::env::args().skip(1);
+    let protocol = args.next().unwrap_or_default();
+    let paths: Vec<String> = args.collect();
+    if !PROTOCOLS.contains(&protocol.as_str()) || paths.is_empty() {
+        return Err(eyre!(
+            "Usage: replay <protocol> <session log>..., with a protocol of {PROTOCOLS:?}"
+        ));
+    }
+
+    let mut mismatches = 0;
+    for path in &paths {
+        let report = match protocol.as_str() {
+            "chat_websocket" => chat_websocket_replay(path).await?,
+            _ => rkyv_websocket_replay(path).await?,
+        };
+
+        match report.is_match() {
+            true => println!("{path}: identical"),
+            false => {
+                mismatches += 1;
+                println!("{path}: differs\n{report}");
+            }
+        }
+    }
+
+    match mismatches {
+        0 => Ok(()),
+        _ => Err(eyre!("{mismatches} of {} sessions differ", paths.len())),
+    }
+}
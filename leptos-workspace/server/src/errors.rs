@@ -1,3 +1,4 @@
+use app::errors::AppError;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -9,3 +10,13 @@ pub enum Error {
     #[error("Server: {0}")]
     Server(#[from] crate::server::ServerError),
 }
+
+/// Lets server functions running in this server return its errors with `?`.
+impl From<Error> for AppError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Server(error) => error.into(),
+            error => AppError::internal(error),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//! Authentication state provided to every server function.
+
+use app::auth::server::{AuthState, MemorySessionStore, MemoryUserStore};
+
+use super::errors::ServerError;
+
+/// Creates the authentication state of the server.
+///
+/// Users and sessions are kept in memory: replace the stores to persist
+/// them. Debug builds add a `demo` user (password `demo-password`).
+pub fn auth_state() -> Result<AuthState, ServerError> {
+    let users = MemoryUserStore::default();
+
+    #[cfg(debug_assertions)]
+    users.add("demo", "demo-password")?;
+
+    Ok(AuthState::new(MemorySessionStore::default(), users))
+}
@@ -1,13 +1,30 @@
 use std::net::SocketAddr;
 
+{% if database == true -%}
+use app::database::SqlitePool;
+{% endif -%}
+{% if uploads == true -%}
+use app::uploads::server::UploadState;
+{% endif -%}
 use app::{App, shell};
 use axum::Router;
 use leptos::prelude::*;
-use leptos_axum::{LeptosRoutes, generate_route_list};
+use leptos_axum::{LeptosRoutes, generate_route_list_with_exclusions_and_ssg_and_context};
 use tokio::net::TcpListener;
 use tokio_util::sync::CancellationToken;
 
+{% if auth == true -%}
+use super::auth::auth_state;
+{% endif -%}
 use super::errors::ServerError;
+{% if pwa == true -%}
+use super::pwa::pwa_routes;
+{% endif -%}
+use super::seo::{seo_routes, site_url};
+use super::ssg::{ssg_routes, static_paths, with_cache_headers};
+{% if uploads == true -%}
+use super::uploads::{upload_dir, upload_state, uploads_routes};
+{% endif -%}
 
 pub struct AxumServer {
     listener: TcpListener,
@@ -17,13 +34,20 @@ pub struct AxumServer {
 }
 
 impl AxumServer {
+    {%- if database == true %}
+    pub async fn new(shutdown: CancellationToken, pool: SqlitePool) -> Result<Self, ServerError> {
+    {%- else %}
     pub async fn new(shutdown: CancellationToken) -> Result<Self, ServerError> {
+    {%- endif %}
         let conf = get_configuration(None)?;
         let addr = conf.leptos_options.site_addr;
         let leptos_options = conf.leptos_options;
 
         // build `router`
-        let app = Self::build_router(leptos_options)?;
+        {%- if uploads == true %}
+        let uploads = upload_state(upload_dir());
+        {%- endif %}
+        let app = Self::build_router(leptos_options{% if database == true %}, pool{% endif %}{% if uploads == true %}, uploads{% endif %}).await?;
 
         let listener = tokio::net::TcpListener::bind(&addr)
             .await
@@ -43,6 +67,9 @@ impl AxumServer {
         addr: SocketAddr,
         cargo_toml_path: Option<&str>,
         shutdown: CancellationToken,
+        {%- if database == true %}
+        pool: SqlitePool,
+        {%- endif %}
     ) -> Result<Self, ServerError> {
         let conf = get_configuration(cargo_toml_path)?;
         let leptos_options = conf.leptos_options;
@@ -68,7 +95,11 @@ impl AxumServer {
         }
 
         // build `router`
-        let app = Self::build_router(leptos_options)?;
+        {%- if uploads == true %}
+        // Every scenario stores its uploads apart
+        let uploads = upload_state(std::env::temp_dir().join(format!("uploads-{}", addr.port())));
+        {%- endif %}
+        let app = Self::build_router(leptos_options{% if database == true %}, pool{% endif %}{% if uploads == true %}, uploads{% endif %}).await?;
 
         let listener = tokio::net::TcpListener::bind(&addr)
             .await
@@ -83,18 +114,75 @@ impl AxumServer {
     }
     {%- endif %}
 
-    fn build_router(leptos_options: LeptosOptions) -> Result<Router, ServerError> {
-        let routes = generate_route_list(App);
+    /// Builds the router, after rendering the static pages to `target/site`.
+    {%- if database == true or uploads == true %}
+    async fn build_router(
+        leptos_options: LeptosOptions,
+        {%- if database == true %}
+        pool: SqlitePool,
+        {%- endif %}
+        {%- if uploads == true %}
+        uploads: UploadState,
+        {%- endif %}
+    ) -> Result<Router, ServerError> {
+    {%- else %}
+    async fn build_router(leptos_options: LeptosOptions) -> Result<Router, ServerError> {
+    {%- endif %}
+        let site_url = site_url(&leptos_options);
+        {%- if auth == true %}
+        let auth = auth_state()?;
+        {%- endif %}
+
+        // Server functions and SSR read these from the context
+        let additional_context = {
+            let site_url = site_url.clone();
+            {%- if uploads == true %}
+            let uploads = uploads.clone();
+            {%- endif %}
+            move || {
+                provide_context(site_url.clone());
+                {%- if auth == true %}
+                provide_context(auth.clone());
+                {%- endif %}
+                {%- if database == true %}
+                provide_context(pool.clone());
+                {%- endif %}
+                {%- if uploads == true %}
+                provide_context(uploads.clone());
+                {%- endif %}
+            }
+        };
+
+        let (routes, static_pages) = generate_route_list_with_exclusions_and_ssg_and_context(
+            App,
+            None,
+            additional_context.clone(),
+        );
+        static_pages.generate(&leptos_options).await;
+
+        // Before `routes` moves into the Leptos routes
+        let seo = seo_routes(&site_url, &routes);
+        let static_paths = static_paths(&routes);
 
         let router = Router::new()
-            .leptos_routes(&leptos_options, routes, {
+            .leptos_routes_with_context(&leptos_options, routes, additional_context.clone(), {
                 let leptos_options = leptos_options.clone();
                 move || shell(leptos_options.clone())
             })
-            .fallback(leptos_axum::file_and_error_handler(shell))
-            .with_state(leptos_options);
-
-        Ok(router)
+            .merge(seo)
+            .merge(ssg_routes())
+            {%- if uploads == true %}
+            .merge(uploads_routes(uploads))
+            {%- endif %}
+            {%- if pwa == true %}
+            .merge(pwa_routes(&leptos_options).await)
+            {%- endif %}
+            .fallback(leptos_axum::file_and_error_handler_with_context(
+                additional_context,
+                shell,
+            ));
+
+        Ok(with_cache_headers(router, static_paths).with_state(leptos_options))
     }
 
     pub async fn serve(self) -> Result<(), ServerError> {
@@ -3,6 +3,9 @@ use tokio_util::sync::CancellationToken;
 use crate::{Error, TaskSupervisor};
 
 use super::axum_server::AxumServer;
+{% if database == true -%}
+use super::database::connect_pool;
+{% endif -%}
 use super::errors::ServerError;
 
 pub struct Server {
@@ -20,7 +23,12 @@ pub struct CucumberServer {
 
 impl Server {
     pub async fn new(shutdown: CancellationToken) -> Result<Self, ServerError> {
+        {%- if database == true %}
+        let pool = connect_pool().await?;
+        let axum_server = AxumServer::new(shutdown.clone(), pool).await?;
+        {%- else %}
         let axum_server = AxumServer::new(shutdown.clone()).await?;
+        {%- endif %}
 
         Ok(Self {
             axum_server,
@@ -36,7 +44,14 @@ impl Server {
         sender: tokio::sync::oneshot::Sender<()>,
         shutdown: CancellationToken,
     ) -> Result<CucumberServer, ServerError> {
+        {%- if database == true %}
+        // Every scenario gets its own empty database
+        let pool = app::database::connect("sqlite::memory:").await?;
+        let axum_server =
+            AxumServer::cucumber_new(addr, cargo_toml_path, shutdown.clone(), pool).await?;
+        {%- else %}
         let axum_server = AxumServer::cucumber_new(addr, cargo_toml_path, shutdown.clone()).await?;
+        {%- endif %}
 
         let server = Server {
             axum_server,
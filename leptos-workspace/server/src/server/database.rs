@@ -0,0 +1,16 @@
+//! Database of the server.
+
+use app::database::{self, DEFAULT_DATABASE_URL, SqlitePool};
+
+use super::errors::ServerError;
+
+/// Connects to `DATABASE_URL` (default: `DEFAULT_DATABASE_URL`) and runs
+/// the pending migrations.
+///
+/// The SQLite file is created if missing.
+pub async fn connect_pool() -> Result<SqlitePool, ServerError> {
+    let url = std::env::var("DATABASE_URL").unwrap_or_else(|_| DEFAULT_DATABASE_URL.to_string());
+    tracing::info!("Database: {url}");
+
+    Ok(database::connect(&url).await?)
+}
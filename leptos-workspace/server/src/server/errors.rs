@@ -1,5 +1,12 @@
 use std::net::SocketAddr;
 
+{% if auth == true -%}
+use app::auth::server::AuthError;
+{% endif -%}
+{% if database == true -%}
+use app::database::DatabaseError;
+{% endif -%}
+use app::errors::AppError;
 use leptos::config::errors::LeptosConfigError;
 use thiserror::Error;
 
@@ -19,6 +26,18 @@ pub enum ServerError {
         #[source]
         source: std::io::Error,
     },
+    {%- if auth == true %}
+
+    // ====== Auth =====
+    #[error("Auth: {0}")]
+    Auth(#[from] AuthError),
+    {%- endif %}
+    {%- if database == true %}
+
+    // ====== Database =====
+    #[error("Database: {0}")]
+    Database(#[from] DatabaseError),
+    {%- endif %}
     {%- if cucumber == true %}
 
     // ====== feature `cucumber` =====
@@ -27,3 +46,17 @@ pub enum ServerError {
     Io(#[from] std::io::Error),
     {%- endif %}
 }
+
+/// Lets server functions running in this server return its errors with `?`.
+impl From<ServerError> for AppError {
+    fn from(error: ServerError) -> Self {
+        {%- if auth == true %}
+        match error {
+            ServerError::Auth(error) => error.into(),
+            error => AppError::internal(error),
+        }
+        {%- else %}
+        AppError::internal(error)
+        {%- endif %}
+    }
+}
@@ -1,6 +1,20 @@
+{% if auth == true -%}
+mod auth;
+{% endif -%}
 mod axum_server;
 mod core;
+{% if database == true -%}
+mod database;
+{% endif -%}
 mod errors;
+{% if pwa == true -%}
+mod pwa;
+{% endif -%}
+mod seo;
+mod ssg;
+{% if uploads == true -%}
+mod uploads;
+{% endif -%}
 
 pub use core::Server;
 pub use errors::ServerError;
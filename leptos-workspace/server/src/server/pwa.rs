@@ -0,0 +1,120 @@
+//! Web app manifest and service worker of the server.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+
+use app::pwa::{BACKGROUND_COLOR, MANIFEST_PATH, OFFLINE_PATH, SERVICE_WORKER_PATH, THEME_COLOR};
+use app::seo::SITE_NAME;
+use axum::Router;
+use axum::http::{HeaderName, header};
+use axum::routing::get;
+use leptos::prelude::*;
+use serde_json::json;
+
+/// Template of the service worker, completed by [`service_worker`].
+const SERVICE_WORKER: &str = include_str!("service_worker.js");
+
+/// Lets the worker control the whole site, whatever its path.
+const SERVICE_WORKER_ALLOWED: HeaderName = HeaderName::from_static("service-worker-allowed");
+
+/// Assets precached besides `/pkg`, served from `target/site`.
+{% if style == "unocss" -%}
+const PRECACHED_ASSETS: &[&str] = &["/favicon.ico", "/icon.svg", "/pwa.js", "/uno.css"];
+{% else -%}
+const PRECACHED_ASSETS: &[&str] = &["/favicon.ico", "/icon.svg", "/pwa.js"];
+{% endif %}
+/// Routes of the web app manifest and of the service worker.
+///
+/// The worker precaches the files of `<site-root>/<site-pkg-dir>` as they are
+/// when the server starts: its cache is named after their content, so a new
+/// build installs a new worker, and shows the update prompt.
+pub async fn pwa_routes<S>(leptos_options: &LeptosOptions) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let manifest = manifest().to_string();
+    let service_worker = service_worker(leptos_options).await;
+
+    Router::new()
+        .route(
+            MANIFEST_PATH,
+            get(move || {
+                std::future::ready((
+                    [(header::CONTENT_TYPE, "application/manifest+json")],
+                    manifest.clone(),
+                ))
+            }),
+        )
+        .route(
+            SERVICE_WORKER_PATH,
+            get(move || {
+                std::future::ready((
+                    [
+                        (header::CONTENT_TYPE, "text/javascript; charset=utf-8"),
+                        // Browsers check for a new worker on every navigation
+                        (header::CACHE_CONTROL, "no-cache"),
+                        (SERVICE_WORKER_ALLOWED, "/"),
+                    ],
+                    service_worker.clone(),
+                ))
+            }),
+        )
+}
+
+fn manifest() -> serde_json::Value {
+    json!({
+        "name": SITE_NAME,
+        "short_name": SITE_NAME,
+        "start_url": "/",
+        "scope": "/",
+        "display": "standalone",
+        "theme_color": THEME_COLOR,
+        "background_color": BACKGROUND_COLOR,
+        "icons": [
+            { "src": "/icon.svg", "sizes": "any", "type": "image/svg+xml", "purpose": "any maskable" },
+        ],
+    })
+}
+
+async fn service_worker(leptos_options: &LeptosOptions) -> String {
+    let pkg_dir =
+        Path::new(leptos_options.site_root.as_ref()).join(leptos_options.site_pkg_dir.as_ref());
+    let mut hasher = DefaultHasher::new();
+    let mut precache: Vec<String> = std::iter::once(OFFLINE_PATH)
+        .chain(PRECACHED_ASSETS.iter().copied())
+        .map(str::to_string)
+        .collect();
+
+    match pkg_files(&pkg_dir).await {
+        Ok(files) => {
+            for (name, content) in files {
+                (&name, content).hash(&mut hasher);
+                precache.push(format!("/{}/{name}", leptos_options.site_pkg_dir));
+            }
+        }
+        Err(error) => tracing::warn!("No assets to precache in {}: {error}", pkg_dir.display()),
+    }
+    let cache_name = format!("{SITE_NAME}-{:016x}", hasher.finish());
+    tracing::info!("Service worker cache: {cache_name}");
+
+    SERVICE_WORKER
+        .replace("__CACHE_NAME__", &cache_name)
+        .replace("__PRECACHE__", &json!(precache).to_string())
+        .replace("__OFFLINE_PAGE__", OFFLINE_PATH)
+}
+
+/// Files of the `pkg` directory with their content, sorted by name.
+async fn pkg_files(pkg_dir: &Path) -> std::io::Result<Vec<(String, Vec<u8>)>> {
+    let mut files = Vec::new();
+    let mut entries = tokio::fs::read_dir(pkg_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if !entry.file_type().await?.is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        files.push((name, tokio::fs::read(entry.path()).await?));
+    }
+    files.sort_unstable();
+
+    Ok(files)
+}
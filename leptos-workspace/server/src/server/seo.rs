@@ -0,0 +1,77 @@
+//! `/sitemap.xml` and `/robots.txt` of the server.
+
+use std::future::ready;
+
+use app::pages::is_noindex;
+use app::seo::SiteUrl;
+use axum::Router;
+use axum::http::header;
+use axum::routing::get;
+use leptos::prelude::*;
+use leptos_axum::AxumRouteListing;
+
+/// Returns the public URL of the site: `SITE_URL`, or the `site-addr` of
+/// `Cargo.toml`.
+pub fn site_url(leptos_options: &LeptosOptions) -> SiteUrl {
+    let url = std::env::var("SITE_URL")
+        .unwrap_or_else(|_| format!("http://{}", leptos_options.site_addr));
+    tracing::info!("Site URL: {url}");
+
+    SiteUrl::new(url)
+}
+
+/// Routes of `/sitemap.xml` and `/robots.txt`.
+///
+/// The sitemap lists the static routes of `App`: routes with parameters
+/// (e.g. `/status/{code}`) have no single URL to list, and pages asking not
+/// to be indexed are left out (`app::pages::is_noindex`).
+pub fn seo_routes<S>(site_url: &SiteUrl, routes: &[AxumRouteListing]) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let sitemap = sitemap(site_url, routes);
+    let robots = robots(site_url);
+
+    Router::new()
+        .route(
+            "/sitemap.xml",
+            get(move || ready(([(header::CONTENT_TYPE, "application/xml")], sitemap.clone()))),
+        )
+        .route(
+            "/robots.txt",
+            get(move || ready(([(header::CONTENT_TYPE, "text/plain")], robots.clone()))),
+        )
+}
+
+fn is_listed(path: &str) -> bool {
+    !path.contains(['{', '*']) && !is_noindex(path)
+}
+
+fn sitemap(site_url: &SiteUrl, routes: &[AxumRouteListing]) -> String {
+    let mut paths: Vec<&str> = routes
+        .iter()
+        .map(AxumRouteListing::path)
+        .filter(|path| is_listed(path))
+        .collect();
+    paths.sort_unstable();
+    paths.dedup();
+
+    let urls: String = paths
+        .into_iter()
+        .map(|path| format!("  <url><loc>{}</loc></url>\n", site_url.join(path)))
+        .collect();
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+         {urls}\
+         </urlset>\n"
+    )
+}
+
+fn robots(site_url: &SiteUrl) -> String {
+    format!(
+        "User-agent: *\nAllow: /\nSitemap: {}\n",
+        site_url.join("sitemap.xml")
+    )
+}
@@ -0,0 +1,45 @@
+// Service worker of {{project-name}}, generated by the server (`server/src/server/pwa.rs`).
+//
+// Precaches the assets of `target/site`, serves them from the cache, and
+// shows the offline page when a page cannot be loaded.
+
+const CACHE = "__CACHE_NAME__";
+const PRECACHE = __PRECACHE__;
+const OFFLINE_PAGE = "__OFFLINE_PAGE__";
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(caches.open(CACHE).then((cache) => cache.addAll(PRECACHE)));
+});
+
+// Sent by the update prompt of the page (`/pwa.js`)
+self.addEventListener("message", (event) => {
+  if (event.data === "skip-waiting") {
+    self.skipWaiting();
+  }
+});
+
+// Deletes the caches of the previous versions
+self.addEventListener("activate", (event) => {
+  event.waitUntil(
+    caches
+      .keys()
+      .then((keys) => Promise.all(keys.filter((key) => key !== CACHE).map((key) => caches.delete(key))))
+      .then(() => self.clients.claim()),
+  );
+});
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  if (request.method !== "GET") {
+    return;
+  }
+
+  // Pages: always from the network, the offline page when it fails
+  if (request.mode === "navigate") {
+    event.respondWith(fetch(request).catch(() => caches.match(OFFLINE_PAGE)));
+    return;
+  }
+
+  // Assets: from the cache when precached
+  event.respondWith(caches.match(request).then((cached) => cached || fetch(request)));
+});
@@ -0,0 +1,110 @@
+//! Static pages of the server: cache headers and invalidation API.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use app::ssg::{invalidate, invalidate_all};
+use axum::Router;
+use axum::extract::{Query, Request};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use leptos_axum::AxumRouteListing;
+use leptos_router::SsrMode;
+use subtle::ConstantTimeEq;
+
+/// `Cache-Control` of the static pages: browsers and CDNs may reuse them for
+/// a minute, then revalidate them against their `Last-Modified` date.
+const STATIC_CACHE_CONTROL: &str = "public, max-age=60, stale-while-revalidate=600";
+
+/// Path of the invalidation API.
+const INVALIDATE_PATH: &str = "/api/static/invalidate";
+
+/// Returns the paths of the static routes of `App` (`ssr=static_page()`).
+pub fn static_paths(routes: &[AxumRouteListing]) -> Arc<HashSet<String>> {
+    let paths = routes
+        .iter()
+        .filter(|route| matches!(route.mode(), SsrMode::Static(_)))
+        .map(|route| route.path().to_string())
+        .collect();
+
+    Arc::new(paths)
+}
+
+/// Adds [`STATIC_CACHE_CONTROL`] to the successful responses of the static
+/// pages.
+pub fn with_cache_headers<S>(router: Router<S>, static_paths: Arc<HashSet<String>>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router.layer(middleware::from_fn(move |request: Request, next: Next| {
+        let is_static = static_paths.contains(request.uri().path());
+
+        async move {
+            let mut response = next.run(request).await;
+            if is_static && response.status().is_success() {
+                response
+                    .headers_mut()
+                    .entry(header::CACHE_CONTROL)
+                    .or_insert(HeaderValue::from_static(STATIC_CACHE_CONTROL));
+            }
+
+            response
+        }
+    }))
+}
+
+/// Routes of the invalidation API, enabled by the `SSG_INVALIDATION_TOKEN`
+/// environment variable.
+///
+/// `POST /api/static/invalidate?path=/about` regenerates a page, and without
+/// `path` every page. Requests need an `Authorization: Bearer <token>` header.
+pub fn ssg_routes<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let Ok(token) = std::env::var("SSG_INVALIDATION_TOKEN") else {
+        tracing::info!("Static pages invalidation API disabled: SSG_INVALIDATION_TOKEN is unset");
+        return Router::new();
+    };
+    let token: Arc<str> = token.into();
+
+    Router::new().route(
+        INVALIDATE_PATH,
+        post(
+            move |headers: HeaderMap, Query(query): Query<HashMap<String, String>>| async move {
+                invalidate_handler(&token, &headers, query.get("path").map(String::as_str))
+            },
+        ),
+    )
+}
+
+fn invalidate_handler(token: &str, headers: &HeaderMap, path: Option<&str>) -> Response {
+    let authorized = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        // Constant time: the response time tells nothing of the token
+        .is_some_and(|bearer| bool::from(bearer.as_bytes().ct_eq(token.as_bytes())));
+    if !authorized {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    match path {
+        Some(path) if invalidate(path) => {
+            tracing::info!("Regenerating static page {path}");
+            StatusCode::ACCEPTED.into_response()
+        }
+        Some(path) => (
+            StatusCode::NOT_FOUND,
+            format!("{path} is not a static page"),
+        )
+            .into_response(),
+        None => {
+            let count = invalidate_all();
+            tracing::info!("Regenerating {count} static pages");
+            StatusCode::ACCEPTED.into_response()
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Upload storage of the server, and download route of the stored files.
+
+use std::path::PathBuf;
+
+use app::uploads::server::{LocalStorage, UploadError, UploadState};
+use axum::Router;
+use axum::body::Body;
+use axum::extract::Path;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+
+/// Directory of the uploads when `UPLOAD_DIR` is not set, relative to the
+/// working directory.
+const DEFAULT_UPLOAD_DIR: &str = "uploads";
+
+/// Route of the stored files, at `app::uploads::DOWNLOAD_PATH`.
+const DOWNLOAD_ROUTE: &str = "/files/{key}";
+
+/// Returns `UPLOAD_DIR`, or [`DEFAULT_UPLOAD_DIR`].
+pub fn upload_dir() -> PathBuf {
+    std::env::var("UPLOAD_DIR")
+        .unwrap_or_else(|_| DEFAULT_UPLOAD_DIR.to_string())
+        .into()
+}
+
+/// Creates the upload state of the server, storing files in `dir`.
+///
+/// Replace `LocalStorage` to keep them elsewhere, e.g. in an S3-compatible
+/// bucket, and set the limits with `with_max_size` / `with_file_types`.
+pub fn upload_state(dir: PathBuf) -> UploadState {
+    tracing::info!("Uploads: {}", dir.display());
+
+    UploadState::new(LocalStorage::new(dir))
+}
+
+/// Routes serving the stored files with their content type.
+pub fn uploads_routes<S>(uploads: UploadState) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new().route(
+        DOWNLOAD_ROUTE,
+        get(move |Path(key): Path<String>| async move { download(&uploads, &key).await }),
+    )
+}
+
+async fn download(uploads: &UploadState, key: &str) -> Response {
+    match uploads.open(key).await {
+        Ok(Some((file, content))) => (
+            [
+                (header::CONTENT_TYPE, file.content_type),
+                (header::CONTENT_LENGTH, file.size.to_string()),
+                // Keys only hold ASCII letters, digits, `-`, `_` and `.`
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("inline; filename=\"{}\"", file.name),
+                ),
+                // Browsers must not guess another type, e.g. HTML in a text file
+                (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+            ],
+            Body::from_stream(content),
+        )
+            .into_response(),
+        Ok(None) | Err(UploadError::InvalidKey(_)) => StatusCode::NOT_FOUND.into_response(),
+        Err(error) => {
+            tracing::error!("Failed to read the upload {key}: {error}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Arguments of the `#[websocket_protocol(...)]` attribute.
+
+use proc_macro2::{Span, TokenStream};
+use syn::parse::Parser;
+use syn::{Ident, LitStr, Path};
+
+/// Parsed attribute arguments.
+pub(crate) struct ProtocolArgs {
+    /// Response enum sent by the server.
+    pub(crate) response: Path,
+
+    /// Name of the generated server function.
+    pub(crate) server_fn: Ident,
+
+    /// Server-side `WebSocketMessage` implementation.
+    pub(crate) handler: Path,
+
+    /// Name of the generated client type.
+    pub(crate) client: Ident,
+
+    /// Name of the generated manager alias, if any.
+    pub(crate) manager: Option<Ident>,
+
+    /// Codec used in both directions, if not the default.
+    pub(crate) encoding: Option<Path>,
+
+    /// Response variant acknowledging the handshake.
+    pub(crate) handshake_response: Ident,
+
+    /// Response variant answering a ping.
+    pub(crate) pong: Option<Ident>,
+
+    /// Response variant rejecting an outdated client.
+    pub(crate) upgrade_required: Option<Ident>,
+
+    /// Function called with every response.
+    pub(crate) on_response: Option<Path>,
+
+    /// Name prefix of the generated SSE transport server functions, if any.
+    pub(crate) sse: Option<Ident>,
+
+    /// Whether connections are recorded when `WEBSOCKET_RECORD_DIR` is set.
+    pub(crate) record: bool,
+
+    /// Cargo feature enabling the server-side code (default: `ssr`).
+    pub(crate) ssr_feature: LitStr,
+}
+
+impl ProtocolArgs {
+    /// Parses the attribute arguments, reporting unknown or missing ones.
+    pub(crate) fn parse(attr: TokenStream) -> syn::Result<Self> {
+        let mut response = None;
+        let mut server_fn = None;
+        let mut handler = None;
+        let mut client = None;
+        let mut manager = None;
+        let mut encoding = None;
+        let mut handshake_response = None;
+        let mut pong = None;
+        let mut upgrade_required = None;
+        let mut on_response = None;
+        let mut sse = None;
+        let mut record = false;
+        let mut ssr_feature = None;
+
+        let parser = syn::meta::parser(|meta| {
+            if meta.path.is_ident("response") {
+                response = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("server_fn") {
+                server_fn = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("handler") {
+                handler = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("client") {
+                client = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("manager") {
+                manager = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("encoding") {
+                encoding = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("handshake_response") {
+                handshake_response = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("pong") {
+                pong = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("upgrade_required") {
+                upgrade_required = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("on_response") {
+                on_response = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("sse") {
+                sse = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("record") {
+                record = true;
+            } else if meta.path.is_ident("ssr_feature") {
+                ssr_feature = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported websocket_protocol argument"));
+            }
+
+            Ok(())
+        });
+        parser.parse2(attr)?;
+
+        Ok(Self {
+            response: required(response, "response")?,
+            server_fn: required(server_fn, "server_fn")?,
+            handler: required(handler, "handler")?,
+            client: required(client, "client")?,
+            manager,
+            encoding,
+            handshake_response: required(handshake_response, "handshake_response")?,
+            pong,
+            upgrade_required,
+            on_response,
+            sse,
+            record,
+            ssr_feature: ssr_feature.unwrap_or_else(|| LitStr::new("ssr", Span::call_site())),
+        })
+    }
+}
+
+/// Unwraps a required argument.
+fn required<T>(value: Option<T>, name: &str) -> syn::Result<T> {
+    value.ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            format!("missing `{name} = ...` argument in #[websocket_protocol]"),
+        )
+    })
+}
@@ -0,0 +1,553 @@
+//! Generated server functions, client and manager alias.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ItemEnum;
+
+use crate::args::ProtocolArgs;
+use crate::variants::{LifecycleVariants, MarkedVariant};
+
+/// Expands the annotated request enum into the full endpoint.
+pub(crate) fn expand(args: ProtocolArgs, mut item: ItemEnum) -> syn::Result<TokenStream> {
+    let variants = LifecycleVariants::take(&mut item)?;
+
+    if variants.ping.is_some() && args.pong.is_none() {
+        return Err(syn::Error::new(
+            item.ident.span(),
+            "a `pong = ...` argument is required with a #[websocket(ping)] variant",
+        ));
+    }
+
+    if variants.handshake.version.is_some() != args.upgrade_required.is_some() {
+        return Err(syn::Error::new(
+            item.ident.span(),
+            "a #[websocket(version)] handshake field requires an `upgrade_required = ...` argument, and vice versa",
+        ));
+    }
+
+    let encoding = match &args.encoding {
+        Some(encoding) => quote!(#encoding),
+        None => quote!(::leptos::server_fn::codec::RkyvEncoding),
+    };
+
+    let versioned = versioned(&args, &item, &variants);
+    let server_fn = server_fn(&args, &item, &encoding, versioned.is_some());
+    let sse = sse(&args, &item, &encoding, versioned.is_some());
+    let replay = replay(&args, &item, &encoding, versioned.is_some());
+    let client = client(&args, &item, &variants, &encoding);
+    let manager = args.manager.as_ref().map(|manager| {
+        let vis = &item.vis;
+        let client = &args.client;
+        quote! {
+            #[doc = concat!("WebSocket manager for [`", stringify!(#client), "`].")]
+            #vis type #manager = ::websocket_trait::client::GenericWebSocketManager<#client>;
+        }
+    });
+
+    Ok(quote! {
+        #item
+        #versioned
+        #server_fn
+        #sse
+        #replay
+        #client
+        #manager
+    })
+}
+
+/// `VersionedProtocol` impl for requests carrying a protocol version.
+fn versioned(
+    args: &ProtocolArgs,
+    item: &ItemEnum,
+    variants: &LifecycleVariants,
+) -> Option<TokenStream> {
+    let version = variants.handshake.version.as_ref()?;
+    let upgrade_required = args.upgrade_required.as_ref()?;
+    let request = &item.ident;
+    let response = &args.response;
+    let handshake = &variants.handshake.ident;
+
+    Some(quote! {
+        impl ::websocket_trait::protocol::VersionedProtocol for #request {
+            type Response = #response;
+
+            const VERSION: u64 = ::websocket_trait::protocol::protocol_version::<#request, #response>();
+
+            fn handshake_version(&self) -> Option<u64> {
+                match self {
+                    Self::#handshake { #version, .. } => Some(*#version),
+                    _ => None,
+                }
+            }
+
+            fn upgrade_required() -> Self::Response {
+                #response::#upgrade_required
+            }
+        }
+    })
+}
+
+/// Websocket server function serving one backend per connection.
+fn server_fn(
+    args: &ProtocolArgs,
+    item: &ItemEnum,
+    encoding: &TokenStream,
+    versioned: bool,
+) -> TokenStream {
+    let vis = &item.vis;
+    let request = &item.ident;
+    let ProtocolArgs {
+        response,
+        server_fn,
+        ..
+    } = args;
+
+    let backend = backend(args, encoding, versioned, args.record);
+
+    quote! {
+        #[::leptos::server(protocol = ::leptos::server_fn::Websocket<#encoding, #encoding>)]
+        #[::leptos::lazy]
+        #vis async fn #server_fn(
+            input: ::leptos::server_fn::BoxedStream<#request, ::leptos::prelude::ServerFnError>,
+        ) -> Result<
+            ::leptos::server_fn::BoxedStream<#response, ::leptos::prelude::ServerFnError>,
+            ::leptos::prelude::ServerFnError,
+        > {
+            let (tx, rx) = ::websocket_trait::__private::futures::channel::mpsc::unbounded();
+            let websocket_backend = #backend;
+
+            ::websocket_trait::__private::tokio::spawn(async move {
+                websocket_backend.serve().await;
+            });
+
+            Ok(rx.into())
+        }
+    }
+}
+
+/// SSE transport server functions sharing a session registry.
+///
+/// `{sse}_open` streams the responses of a new session, `{sse}_send` delivers
+/// one request to it.
+fn sse(
+    args: &ProtocolArgs,
+    item: &ItemEnum,
+    encoding: &TokenStream,
+    versioned: bool,
+) -> Option<TokenStream> {
+    let sse = args.sse.as_ref()?;
+    let vis = &item.vis;
+    let request = &item.ident;
+    let handler = &args.handler;
+
+    let (open, send) = sse_fns(sse);
+    let sessions = format_ident!("{}_SESSIONS", sse.to_string().to_uppercase());
+    let ssr_feature = &args.ssr_feature;
+    let backend = backend(args, encoding, versioned, args.record);
+
+    Some(quote! {
+        #[cfg(feature = #ssr_feature)]
+        static #sessions: ::websocket_trait::server::sse::SseSessions<#request> =
+            ::websocket_trait::server::sse::SseSessions::new();
+
+        #[::leptos::server(output = ::websocket_trait::sse::EventStreamEncoding)]
+        #[::leptos::lazy]
+        #vis async fn #open() -> Result<::websocket_trait::sse::EventStream, ::leptos::prelude::ServerFnError> {
+            Ok(::websocket_trait::server::sse::open::<#handler, #encoding>(
+                &#sessions,
+                |input, tx| #backend,
+            ))
+        }
+
+        #[::leptos::server]
+        #[::leptos::lazy]
+        #vis async fn #send(session: String, payload: String) -> Result<(), ::leptos::prelude::ServerFnError> {
+            ::websocket_trait::server::sse::send::<#request, #encoding>(&#sessions, &session, &payload)
+        }
+    })
+}
+
+/// Replays a recorded session through the same backend, without recording it.
+fn replay(
+    args: &ProtocolArgs,
+    item: &ItemEnum,
+    encoding: &TokenStream,
+    versioned: bool,
+) -> Option<TokenStream> {
+    if !args.record {
+        return None;
+    }
+
+    let vis = &item.vis;
+    let ProtocolArgs {
+        server_fn,
+        handler,
+        ssr_feature,
+        ..
+    } = args;
+
+    let replay = format_ident!("{}_replay", server_fn);
+    let backend = backend(args, encoding, versioned, false);
+
+    Some(quote! {
+        #[doc = concat!("Replays a session recorded by [`", stringify!(#server_fn), "`] against the current handler.")]
+        #[cfg(feature = #ssr_feature)]
+        #vis async fn #replay(
+            path: impl AsRef<::std::path::Path>,
+        ) -> Result<::websocket_trait::server::ReplayReport, ::websocket_trait::server::ReplayError> {
+            ::websocket_trait::server::replay::<#handler, #encoding>(path, |input, tx| #backend).await
+        }
+    })
+}
+
+/// Names of the SSE `open` and `send` server functions.
+fn sse_fns(sse: &syn::Ident) -> (syn::Ident, syn::Ident) {
+    (format_ident!("{}_open", sse), format_ident!("{}_send", sse))
+}
+
+/// Backend serving one connection, from `input` and `tx` in scope.
+fn backend(
+    args: &ProtocolArgs,
+    encoding: &TokenStream,
+    versioned: bool,
+    record: bool,
+) -> TokenStream {
+    let handler = &args.handler;
+    let version_check = versioned.then(|| quote!(.with_version_check()));
+    let recorder = record.then(|| quote!(.with_recorder_from_env::<#encoding>()));
+
+    quote! {
+        ::websocket_trait::server::GenericWebsocketBackend::<#handler>::new(
+            input,
+            tx,
+            <#handler as ::core::default::Default>::default(),
+        )
+        #version_check
+        #recorder
+    }
+}
+
+/// Client type with its `WebSocketClient` and `SharedWebSocketClient` impls.
+fn client(
+    args: &ProtocolArgs,
+    item: &ItemEnum,
+    variants: &LifecycleVariants,
+    encoding: &TokenStream,
+) -> TokenStream {
+    let vis = &item.vis;
+    let request = &item.ident;
+    let ProtocolArgs {
+        response,
+        server_fn,
+        client,
+        handshake_response,
+        ..
+    } = args;
+
+    // The protocol version is a constant, not a client field
+    let version = variants.handshake.version.as_ref();
+    let (field_names, field_types): (Vec<_>, Vec<_>) = variants
+        .handshake
+        .fields
+        .iter()
+        .filter(|(name, _)| Some(name) != version)
+        .map(|(name, ty)| (name, ty))
+        .unzip();
+
+    let handshake_request = construct(request, &variants.handshake, version);
+    let disconnect_request = construct(request, &variants.disconnect, version);
+
+    let on_response = args
+        .on_response
+        .as_ref()
+        .map(|on_response| quote!(#on_response(response);));
+
+    let ping = variants
+        .ping
+        .as_ref()
+        .zip(args.pong.as_ref())
+        .map(|(ping, pong)| {
+            let ping_variant = &ping.ident;
+            let (id, _) = &ping.fields[0];
+
+            quote! {
+                fn create_ping_request(&self, id: u32) -> Option<Self::Request> {
+                    Some(#request::#ping_variant { #id: id })
+                }
+
+                fn pong_id(response: &Self::Response) -> Option<u32> {
+                    match response {
+                        #response::#pong { #id, .. } => Some(*#id),
+                        _ => None,
+                    }
+                }
+            }
+        });
+
+    let upgrade_required = args.upgrade_required.as_ref().map(|upgrade_required| {
+        quote! {
+            fn is_upgrade_required(response: &Self::Response) -> bool {
+                matches!(response, #response::#upgrade_required { .. })
+            }
+        }
+    });
+
+    // With SSE support, the transport is picked per connection
+    let stream = match &args.sse {
+        Some(sse) => {
+            let (open, send) = sse_fns(sse);
+            quote! {
+                ::websocket_trait::client::transport::connect(
+                    rx,
+                    |rx| #server_fn(rx.into()),
+                    |rx| ::websocket_trait::client::transport::connect_sse::<_, _, #encoding, _, _, _, _>(
+                        rx,
+                        #open,
+                        #send,
+                    ),
+                )
+                .await
+            }
+        }
+        None => quote!(#server_fn(rx.into()).await),
+    };
+
+    let new_doc = format!(
+        "Creates a client sending `{request}::{}` on connect.",
+        variants.handshake.ident
+    );
+
+    quote! {
+        #[doc = concat!("Client of the [`", stringify!(#server_fn), "`] websocket endpoint.")]
+        #[derive(Clone)]
+        #vis struct #client {
+            #(#field_names: ::leptos::prelude::StoredValue<#field_types>,)*
+        }
+
+        const _: () = {
+            use ::leptos::prelude::*;
+            use ::leptos::server_fn::{Decodes, Encodes};
+
+            impl #client {
+                #[doc = #new_doc]
+                #vis fn new(#(#field_names: #field_types),*) -> Self {
+                    Self {
+                        #(#field_names: StoredValue::new(#field_names),)*
+                    }
+                }
+            }
+
+            impl ::websocket_trait::client::WebSocketClient for #client {
+                type Request = #request;
+                type Response = #response;
+
+                fn create_handshake_request(&self) -> Self::Request {
+                    let Self { #(#field_names),* } = self;
+                    #handshake_request
+                }
+
+                fn create_disconnect_request(&self) -> Self::Request {
+                    #[allow(unused_variables)]
+                    let Self { #(#field_names),* } = self;
+                    #disconnect_request
+                }
+
+                fn handle_response(response: Self::Response, is_connected: RwSignal<bool>) {
+                    if matches!(response, #response::#handshake_response { .. }) {
+                        is_connected.set(true);
+                    }
+                    #on_response
+                }
+
+                #ping
+
+                #upgrade_required
+
+                async fn get_stream(
+                    rx: ::websocket_trait::__private::futures::channel::mpsc::UnboundedReceiver<
+                        Result<Self::Request, ServerFnError>,
+                    >,
+                ) -> Result<::leptos::server_fn::BoxedStream<Self::Response, ServerFnError>, ServerFnError> {
+                    #stream
+                }
+            }
+
+            /// Relays messages between tabs with the endpoint's own encoding.
+            impl ::websocket_trait::client::SharedWebSocketClient for #client {
+                fn encode_request(request: &Self::Request) -> Option<Vec<u8>> {
+                    <#encoding as Encodes<Self::Request>>::encode(request)
+                        .map(|bytes| bytes.to_vec())
+                        .ok()
+                }
+
+                fn decode_request(bytes: &[u8]) -> Option<Self::Request> {
+                    <#encoding as Decodes<Self::Request>>::decode(
+                        ::leptos::server_fn::Bytes::copy_from_slice(bytes),
+                    )
+                    .ok()
+                }
+
+                fn encode_response(response: &Self::Response) -> Option<Vec<u8>> {
+                    <#encoding as Encodes<Self::Response>>::encode(response)
+                        .map(|bytes| bytes.to_vec())
+                        .ok()
+                }
+
+                fn decode_response(bytes: &[u8]) -> Option<Self::Response> {
+                    <#encoding as Decodes<Self::Response>>::decode(
+                        ::leptos::server_fn::Bytes::copy_from_slice(bytes),
+                    )
+                    .ok()
+                }
+            }
+        };
+    }
+}
+
+/// Builds a request variant from the client fields bound by name.
+///
+/// The field named like the handshake's version field gets the protocol version.
+fn construct(
+    request: &syn::Ident,
+    variant: &MarkedVariant,
+    version: Option<&syn::Ident>,
+) -> TokenStream {
+    let ident = &variant.ident;
+    if variant.is_unit {
+        return quote!(#request::#ident);
+    }
+
+    let fields = variant.fields.iter().map(|(name, _)| {
+        if Some(name) == version {
+            quote!(#name: <#request as ::websocket_trait::protocol::VersionedProtocol>::VERSION)
+        } else {
+            quote!(#name: #name.get_value())
+        }
+    });
+    quote! {
+        #request::#ident { #(#fields,)* }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Expands `item` with the given attribute arguments.
+    fn expand_with(attr: TokenStream, item: TokenStream) -> String {
+        let args = ProtocolArgs::parse(attr).expect("valid arguments");
+        let item = syn::parse2(item).expect("valid enum");
+
+        expand(args, item).expect("valid protocol").to_string()
+    }
+
+    /// Request enum with a handshake carrying a uuid, a disconnect and a ping.
+    fn request() -> TokenStream {
+        quote! {
+            pub enum Request {
+                #[websocket(handshake)]
+                Handshake { uuid: Uuid },
+                #[websocket(disconnect)]
+                Disconnect { uuid: Uuid },
+                #[websocket(ping)]
+                Ping { id: u32 },
+            }
+        }
+    }
+
+    fn required_args() -> TokenStream {
+        quote! {
+            response = Response,
+            server_fn = websocket,
+            handler = Handler,
+            client = Client,
+            handshake_response = HandshakeResponse,
+            pong = Pong,
+        }
+    }
+
+    #[test]
+    fn server_items_are_gated_by_the_ssr_feature_by_default() {
+        let args = required_args();
+        let expanded = expand_with(quote!(#args sse = events, record), request());
+
+        assert_eq!(expanded.matches(r#"cfg (feature = "ssr")"#).count(), 2);
+    }
+
+    #[test]
+    fn the_ssr_feature_can_be_renamed() {
+        let args = required_args();
+        let expanded = expand_with(
+            quote!(#args sse = events, record, ssr_feature = "server"),
+            request(),
+        );
+
+        assert_eq!(expanded.matches(r#"cfg (feature = "server")"#).count(), 2);
+        assert!(!expanded.contains(r#""ssr""#));
+    }
+
+    #[test]
+    fn markers_are_stripped_and_the_client_takes_the_handshake_fields() {
+        let expanded = expand_with(required_args(), request());
+
+        assert!(!expanded.contains("websocket (handshake)"));
+        assert!(expanded.contains(
+            "pub struct Client { uuid : :: leptos :: prelude :: StoredValue < Uuid > , }"
+        ));
+        assert!(expanded.contains("pub fn new (uuid : Uuid) -> Self"));
+        assert!(expanded.contains("Request :: Ping { id : id }"));
+    }
+
+    #[test]
+    fn optional_items_are_only_generated_when_asked_for() {
+        let expanded = expand_with(required_args(), request());
+
+        assert!(!expanded.contains("type WebSocketManager"));
+        assert!(!expanded.contains("events_open"));
+        assert!(!expanded.contains("websocket_replay"));
+        assert!(!expanded.contains("VersionedProtocol for Request"));
+        assert!(expanded.contains("RkyvEncoding"));
+
+        let args = required_args();
+        let expanded = expand_with(
+            quote!(#args manager = WebSocketManager, sse = events, record, encoding = JsonEncoding),
+            request(),
+        );
+
+        assert!(expanded.contains("pub type WebSocketManager"));
+        assert!(expanded.contains("async fn events_open"));
+        assert!(expanded.contains("async fn events_send"));
+        assert!(expanded.contains("async fn websocket_replay"));
+        assert!(expanded.contains("with_recorder_from_env :: < JsonEncoding >"));
+        assert!(!expanded.contains("RkyvEncoding"));
+    }
+
+    #[test]
+    fn a_version_field_is_filled_with_the_protocol_version() {
+        let args = required_args();
+        let item = quote! {
+            pub enum Request {
+                #[websocket(handshake)]
+                Handshake {
+                    uuid: Uuid,
+                    #[websocket(version)]
+                    version: u64,
+                },
+                #[websocket(disconnect)]
+                Disconnect,
+            }
+        };
+        let expanded = expand_with(quote!(#args upgrade_required = UpgradeRequired), item);
+
+        assert!(
+            expanded
+                .contains("impl :: websocket_trait :: protocol :: VersionedProtocol for Request")
+        );
+        assert!(expanded.contains(". with_version_check ()"));
+        // Not a client field nor an argument of `new()`
+        assert!(expanded.contains("pub fn new (uuid : Uuid) -> Self"));
+        assert!(expanded.contains(
+            "version : < Request as :: websocket_trait :: protocol :: VersionedProtocol > :: VERSION"
+        ));
+    }
+}
@@ -0,0 +1,130 @@
+//! Code generation for `websocket_trait` endpoints.
+//!
+//! This crate provides the `#[websocket_protocol]` attribute, re-exported as
+//! `websocket_trait::websocket_protocol`. Applied to a request enum, it
+//! generates everything an endpoint needs except the server-side handler:
+//!
+//! - The websocket server function, serving a `GenericWebsocketBackend`
+//! - A client type implementing `WebSocketClient` and `SharedWebSocketClient`
+//! - A `GenericWebSocketManager` type alias for that client
+//! - Protocol versioning, when the handshake carries a version field
+//! - An SSE transport for deployments where websocket upgrades fail
+//! - Opt-in session recording, for replaying bug reports
+//!
+//! It also provides `#[derive(WebSocketSchema)]`, hashing the shape of the
+//! message types into the protocol version.
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[websocket_protocol(
+//!     response = Response,
+//!     server_fn = rkyv_websocket,
+//!     handler = super::handler::RkyvWebSocketMessage,
+//!     client = RkyvWebSocketClient,
+//!     manager = WebSocketManager,
+//!     handshake_response = HandshakeResponse,
+//!     pong = Pong,
+//!     upgrade_required = UpgradeRequired,
+//!     sse = rkyv_sse,
+//!     record,
+//! )]
+//! #[derive(Debug, Clone, Archive, Deserialize, Serialize, WebSocketSchema)]
+//! pub enum Request {
+//!     #[websocket(handshake)]
+//!     Handshake {
+//!         uuid: Uuid,
+//!         #[websocket(version)]
+//!         version: u64,
+//!     },
+//!     #[websocket(disconnect)]
+//!     Disconnect { uuid: Uuid },
+//!     #[websocket(ping)]
+//!     Ping { id: u32 },
+//! }
+//! ```
+
+mod args;
+mod expand;
+mod schema;
+mod variants;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, ItemEnum};
+
+use crate::args::ProtocolArgs;
+
+/// Generates a websocket endpoint from its request enum.
+///
+/// Must be placed above the enum's `#[derive]` attributes.
+///
+/// # Arguments
+///
+/// * `response` - Response enum sent by the server (required)
+/// * `server_fn` - Name of the generated server function (required)
+/// * `handler` - `WebSocketMessage` implementation serving each connection,
+///   created with `Default::default()` (required)
+/// * `client` - Name of the generated client type (required)
+/// * `handshake_response` - Response variant acknowledging the handshake (required)
+/// * `manager` - Name of the generated manager type alias
+/// * `pong` - Response variant answering a ping, required with a `ping` variant
+/// * `upgrade_required` - Unit response variant rejecting an outdated client,
+///   required with a `version` field
+/// * `encoding` - `server_fn` codec for both directions (default: `RkyvEncoding`)
+/// * `on_response` - Function called with every response after the handshake
+///   bookkeeping, e.g. to log or update application state
+/// * `sse` - Name prefix of the SSE transport server functions (`{prefix}_open`
+///   and `{prefix}_send`). The client then connects through
+///   `websocket_trait::client::transport`, falling back to SSE when the
+///   websocket upgrade fails. Requires a `Clone` request
+/// * `record` - Flag recording every connection to the directory named by the
+///   `WEBSOCKET_RECORD_DIR` environment variable, when set (see
+///   `GenericWebsocketBackend::with_recorder`). Also generates
+///   `{server_fn}_replay(path)`, replaying a recorded session against the
+///   current handler. Requires `Debug` messages
+/// * `ssr_feature` - Cargo feature of the annotated crate enabling the
+///   server-side code (default: `"ssr"`)
+///
+/// # Variant Markers
+///
+/// * `#[websocket(handshake)]` - Sent on connect. Its fields become the fields
+///   of the client and the arguments of `new()`
+/// * `#[websocket(disconnect)]` - Sent on disconnect. Its fields are copied
+///   from the handshake fields with the same name
+/// * `#[websocket(ping)]` - Optional latency probe with a single `u32` field.
+///   The `pong` response variant must carry a field with the same name
+///
+/// # Versioning
+///
+/// Marking a `u64` field of the handshake variant `#[websocket(version)]`
+/// implements `VersionedProtocol` for the request: the client sends the
+/// version of its build, and the server answers an outdated client with the
+/// `upgrade_required` response. Both message types must derive
+/// `WebSocketSchema`.
+#[proc_macro_attribute]
+pub fn websocket_protocol(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = match ProtocolArgs::parse(attr.into()) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let item = syn::parse_macro_input!(item as ItemEnum);
+
+    match expand::expand(args, item) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derives `WebSocketSchema` from the shape of a message type.
+///
+/// The hash covers the type name, variant names, field names and field types
+/// as written, so any change to the message definition changes it.
+#[proc_macro_derive(WebSocketSchema)]
+pub fn websocket_schema(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as DeriveInput);
+
+    match schema::derive(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,74 @@
+//! `#[derive(WebSocketSchema)]`: shape hash of a message type.
+
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{Data, DeriveInput, Fields};
+
+/// Implements `WebSocketSchema` with a hash of the type's shape.
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let hash = fnv1a(shape(&input)?.as_bytes());
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::websocket_trait::protocol::WebSocketSchema for #ident #ty_generics #where_clause {
+            const SCHEMA_HASH: u64 = #hash;
+        }
+    })
+}
+
+/// Canonical description of the type: names and field types, without attributes.
+fn shape(input: &DeriveInput) -> syn::Result<String> {
+    let ident = &input.ident;
+
+    match &input.data {
+        Data::Enum(data) => {
+            let mut shape = format!("enum {ident} ");
+            shape.push('{');
+            for variant in &data.variants {
+                shape.push_str(&variant.ident.to_string());
+                shape.push_str(&fields(&variant.fields));
+                if let Some((_, discriminant)) = &variant.discriminant {
+                    shape.push_str(&format!(" = {}", discriminant.to_token_stream()));
+                }
+                shape.push(';');
+            }
+            shape.push('}');
+
+            Ok(shape)
+        }
+        Data::Struct(data) => Ok(format!("struct {ident}{}", fields(&data.fields))),
+        Data::Union(data) => Err(syn::Error::new(
+            data.union_token.span,
+            "WebSocketSchema can not be derived for unions",
+        )),
+    }
+}
+
+/// Describes the fields of a struct or variant.
+fn fields(fields: &Fields) -> String {
+    let described: Vec<_> = fields
+        .iter()
+        .map(|field| match &field.ident {
+            Some(name) => format!("{name}: {}", field.ty.to_token_stream()),
+            None => field.ty.to_token_stream().to_string(),
+        })
+        .collect();
+
+    match fields {
+        Fields::Named(_) => [" {", &described.join(", "), "}"].concat(),
+        Fields::Unnamed(_) => format!("({})", described.join(", ")),
+        Fields::Unit => String::new(),
+    }
+}
+
+/// 64-bit FNV-1a hash, stable across builds and platforms.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
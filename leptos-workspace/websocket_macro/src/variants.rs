@@ -0,0 +1,165 @@
+//! Request variants marked with `#[websocket(...)]`.
+
+use syn::spanned::Spanned;
+use syn::{Attribute, Fields, Ident, ItemEnum, Type, Variant};
+
+/// A marked request variant.
+pub(crate) struct MarkedVariant {
+    /// Variant name.
+    pub(crate) ident: Ident,
+
+    /// Named fields, in declaration order (empty for unit variants).
+    pub(crate) fields: Vec<(Ident, Type)>,
+
+    /// Whether the variant is a unit variant.
+    pub(crate) is_unit: bool,
+
+    /// Field marked `#[websocket(version)]`, filled with the protocol version.
+    pub(crate) version: Option<Ident>,
+}
+
+/// The request variants driving the connection lifecycle.
+pub(crate) struct LifecycleVariants {
+    /// Sent on connect.
+    pub(crate) handshake: MarkedVariant,
+
+    /// Sent on disconnect.
+    pub(crate) disconnect: MarkedVariant,
+
+    /// Latency probe, if supported.
+    pub(crate) ping: Option<MarkedVariant>,
+}
+
+impl LifecycleVariants {
+    /// Collects the marked variants and strips their `#[websocket]` markers.
+    pub(crate) fn take(item: &mut ItemEnum) -> syn::Result<Self> {
+        let mut handshake = None;
+        let mut disconnect = None;
+        let mut ping = None;
+
+        for variant in &mut item.variants {
+            let Some(role) = take_marker(&mut variant.attrs)? else {
+                continue;
+            };
+
+            let slot = match role.to_string().as_str() {
+                "handshake" => &mut handshake,
+                "disconnect" => &mut disconnect,
+                "ping" => &mut ping,
+                _ => {
+                    return Err(syn::Error::new(
+                        role.span(),
+                        "expected `handshake`, `disconnect` or `ping`",
+                    ));
+                }
+            };
+            if slot.is_some() {
+                return Err(syn::Error::new(
+                    role.span(),
+                    format!("more than one variant is marked `{role}`"),
+                ));
+            }
+            let marked = MarkedVariant::new(variant)?;
+            if let Some(version) = &marked.version
+                && role != "handshake"
+            {
+                return Err(syn::Error::new(
+                    version.span(),
+                    "only the handshake variant can carry the protocol version",
+                ));
+            }
+            *slot = Some(marked);
+        }
+
+        let handshake = handshake.ok_or_else(|| missing(item, "handshake"))?;
+        let disconnect = disconnect.ok_or_else(|| missing(item, "disconnect"))?;
+
+        // The client only knows the values passed to `new()`
+        for (field, _) in &disconnect.fields {
+            if !handshake.fields.iter().any(|(name, _)| name == field) {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "disconnect fields must also be handshake fields",
+                ));
+            }
+        }
+
+        if let Some(ping) = &ping
+            && ping.fields.len() != 1
+        {
+            return Err(syn::Error::new(
+                ping.ident.span(),
+                "the ping variant must have a single `u32` field",
+            ));
+        }
+
+        Ok(Self {
+            handshake,
+            disconnect,
+            ping,
+        })
+    }
+}
+
+impl MarkedVariant {
+    /// Reads the name and fields of a variant, stripping field markers.
+    fn new(variant: &mut Variant) -> syn::Result<Self> {
+        let mut version = None;
+
+        let fields = match &mut variant.fields {
+            Fields::Named(fields) => {
+                let mut named = Vec::new();
+                for field in &mut fields.named {
+                    let ident = field.ident.clone().expect("named field");
+
+                    if let Some(marker) = take_marker(&mut field.attrs)? {
+                        if marker != "version" {
+                            return Err(syn::Error::new(marker.span(), "expected `version`"));
+                        }
+                        version = Some(ident.clone());
+                    }
+
+                    named.push((ident, field.ty.clone()));
+                }
+                named
+            }
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(fields) => {
+                return Err(syn::Error::new(
+                    fields.span(),
+                    "marked variants must have named fields or none",
+                ));
+            }
+        };
+
+        Ok(Self {
+            ident: variant.ident.clone(),
+            fields,
+            is_unit: matches!(variant.fields, Fields::Unit),
+            version,
+        })
+    }
+}
+
+/// Removes a `#[websocket(role)]` attribute, returning the role.
+fn take_marker(attrs: &mut Vec<Attribute>) -> syn::Result<Option<Ident>> {
+    let Some(index) = attrs
+        .iter()
+        .position(|attr| attr.path().is_ident("websocket"))
+    else {
+        return Ok(None);
+    };
+
+    let attr = attrs.remove(index);
+    let role: Ident = attr.parse_args()?;
+
+    Ok(Some(role))
+}
+
+/// Error for a required marker found on no variant.
+fn missing(item: &ItemEnum, role: &str) -> syn::Error {
+    syn::Error::new(
+        item.ident.span(),
+        format!("one variant must be marked #[websocket({role})]"),
+    )
+}
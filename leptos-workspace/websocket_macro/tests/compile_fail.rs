@@ -0,0 +1,10 @@
+//! Errors reported by `#[websocket_protocol]` and `#[derive(WebSocketSchema)]`.
+//!
+//! The expected messages live next to each case in `tests/ui/*.stderr`;
+//! regenerate them with `TRYBUILD=overwrite cargo test -p websocket_macro`.
+
+#[test]
+fn compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
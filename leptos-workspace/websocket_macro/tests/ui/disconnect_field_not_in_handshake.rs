@@ -0,0 +1,17 @@
+use websocket_macro::websocket_protocol;
+
+#[websocket_protocol(
+    response = Response,
+    server_fn = websocket,
+    handler = Handler,
+    client = Client,
+    handshake_response = HandshakeResponse,
+)]
+pub enum Request {
+    #[websocket(handshake)]
+    Handshake { uuid: u128 },
+    #[websocket(disconnect)]
+    Disconnect { session: u128 },
+}
+
+fn main() {}
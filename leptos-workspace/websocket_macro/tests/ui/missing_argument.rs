@@ -0,0 +1,16 @@
+use websocket_macro::websocket_protocol;
+
+#[websocket_protocol(
+    response = Response,
+    server_fn = websocket,
+    handler = Handler,
+    handshake_response = HandshakeResponse,
+)]
+pub enum Request {
+    #[websocket(handshake)]
+    Handshake,
+    #[websocket(disconnect)]
+    Disconnect,
+}
+
+fn main() {}
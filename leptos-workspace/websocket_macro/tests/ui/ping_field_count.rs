@@ -0,0 +1,20 @@
+use websocket_macro::websocket_protocol;
+
+#[websocket_protocol(
+    response = Response,
+    server_fn = websocket,
+    handler = Handler,
+    client = Client,
+    handshake_response = HandshakeResponse,
+    pong = Pong,
+)]
+pub enum Request {
+    #[websocket(handshake)]
+    Handshake,
+    #[websocket(disconnect)]
+    Disconnect,
+    #[websocket(ping)]
+    Ping { id: u32, sent_at: u64 },
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use websocket_macro::websocket_protocol;
+
+#[websocket_protocol(
+    response = Response,
+    server_fn = websocket,
+    handler = Handler,
+    client = Client,
+    handshake_response = HandshakeResponse,
+)]
+pub enum Request {
+    #[websocket(handshake)]
+    Handshake,
+    #[websocket(disconnect)]
+    Disconnect,
+    #[websocket(ping)]
+    Ping { id: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use websocket_macro::WebSocketSchema;
+
+#[derive(WebSocketSchema)]
+pub union Payload {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
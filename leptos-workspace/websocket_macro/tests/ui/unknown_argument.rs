@@ -0,0 +1,18 @@
+use websocket_macro::websocket_protocol;
+
+#[websocket_protocol(
+    response = Response,
+    server_fn = websocket,
+    handler = Handler,
+    client = Client,
+    handshake_response = HandshakeResponse,
+    compress,
+)]
+pub enum Request {
+    #[websocket(handshake)]
+    Handshake,
+    #[websocket(disconnect)]
+    Disconnect,
+}
+
+fn main() {}
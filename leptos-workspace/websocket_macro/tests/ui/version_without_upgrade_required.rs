@@ -0,0 +1,20 @@
+use websocket_macro::websocket_protocol;
+
+#[websocket_protocol(
+    response = Response,
+    server_fn = websocket,
+    handler = Handler,
+    client = Client,
+    handshake_response = HandshakeResponse,
+)]
+pub enum Request {
+    #[websocket(handshake)]
+    Handshake {
+        #[websocket(version)]
+        version: u64,
+    },
+    #[websocket(disconnect)]
+    Disconnect,
+}
+
+fn main() {}
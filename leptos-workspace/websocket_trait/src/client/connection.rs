@@ -0,0 +1,149 @@
+//! Request sender and response listener of a manager's connection.
+
+use futures::future::AbortHandle;
+
+/// The connection currently held by a `GenericWebSocketManager`.
+///
+/// Pairs the sender of the request stream with the handle cancelling the
+/// task listening for responses. Kept free of browser APIs so that the
+/// `connect()` / `disconnect()` transitions can be tested natively.
+pub(super) struct ConnectionSlot<S> {
+    /// Sender of the request stream: `Some` while a connection is open or
+    /// being established, `None` when disconnected.
+    sender: Option<S>,
+
+    /// Cancels the task listening for responses of the latest connection.
+    ///
+    /// Kept after `close()`, so the listener can drain the remaining
+    /// responses until the server closes the stream.
+    listener: Option<AbortHandle>,
+}
+
+impl<S> ConnectionSlot<S> {
+    /// Whether a connection is open or being established.
+    pub(super) fn is_open(&self) -> bool {
+        self.sender.is_some()
+    }
+
+    /// Sender of the open connection, if any.
+    pub(super) fn sender(&self) -> Option<&S> {
+        self.sender.as_ref()
+    }
+
+    /// Starts holding a new connection.
+    ///
+    /// Returns `false` and leaves the slot untouched when a connection is
+    /// already open, so connecting twice keeps the first connection.
+    /// Otherwise the listener of a previous connection still draining is
+    /// cancelled, so at most one listener updates the connection state.
+    pub(super) fn open(&mut self, sender: S, listener: AbortHandle) -> bool {
+        if self.is_open() {
+            return false;
+        }
+
+        self.abort_listener();
+        self.sender = Some(sender);
+        self.listener = Some(listener);
+
+        true
+    }
+
+    /// Takes the sender so no further request can be sent on this connection.
+    ///
+    /// Returns `None` when not connected. The listener keeps running.
+    pub(super) fn close(&mut self) -> Option<S> {
+        self.sender.take()
+    }
+
+    /// Drops the sender and cancels the listener.
+    pub(super) fn abort(&mut self) {
+        self.sender = None;
+        self.abort_listener();
+    }
+
+    /// Cancels the task listening for responses, if any.
+    fn abort_listener(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            listener.abort();
+        }
+    }
+}
+
+impl<S> Default for ConnectionSlot<S> {
+    fn default() -> Self {
+        Self {
+            sender: None,
+            listener: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::AbortRegistration;
+
+    use super::*;
+
+    fn listener() -> (AbortHandle, AbortRegistration) {
+        AbortHandle::new_pair()
+    }
+
+    #[test]
+    fn connecting_twice_keeps_the_first_connection() {
+        let mut slot = ConnectionSlot::default();
+        let (first, _first_registration) = listener();
+        let (second, _second_registration) = listener();
+
+        assert!(slot.open(1, first.clone()));
+        assert!(!slot.open(2, second.clone()));
+
+        assert_eq!(slot.sender(), Some(&1));
+        assert!(!first.is_aborted());
+        assert!(!second.is_aborted());
+    }
+
+    #[test]
+    fn disconnecting_keeps_the_listener_draining() {
+        let mut slot = ConnectionSlot::default();
+        let (first, _registration) = listener();
+        slot.open(1, first.clone());
+
+        assert_eq!(slot.close(), Some(1));
+        assert!(!slot.is_open());
+        assert!(!first.is_aborted());
+
+        // Nothing left to disconnect
+        assert_eq!(slot.close(), None);
+    }
+
+    #[test]
+    fn reconnecting_after_a_disconnect_cancels_the_previous_listener() {
+        let mut slot = ConnectionSlot::default();
+        let (first, _first_registration) = listener();
+        let (second, _second_registration) = listener();
+
+        slot.open(1, first.clone());
+        slot.close();
+
+        assert!(slot.open(2, second.clone()));
+        assert_eq!(slot.sender(), Some(&2));
+        assert!(first.is_aborted());
+        assert!(!second.is_aborted());
+    }
+
+    #[test]
+    fn aborting_drops_the_sender_and_cancels_the_listener() {
+        let mut slot = ConnectionSlot::default();
+        let (first, _registration) = listener();
+        slot.open(1, first.clone());
+
+        slot.abort();
+
+        assert!(!slot.is_open());
+        assert!(first.is_aborted());
+
+        // A new connection can be opened afterwards
+        let (second, _registration) = listener();
+        assert!(slot.open(2, second));
+    }
+}
@@ -0,0 +1,128 @@
+//! Leptos context helpers for sharing a WebSocket manager.
+//!
+//! Instead of threading a `GenericWebSocketManager` through the props of every
+//! component, provide it once near the top of the tree and retrieve it where
+//! needed:
+//!
+//! ```ignore
+//! // Parent component (or `LazyRoute::view`)
+//! provide_websocket_manager(RkyvWebSocketClient::new(uuid).create_manager());
+//!
+//! // Any descendant component
+//! let manager = use_websocket_manager::<RkyvWebSocketClient>();
+//! ```
+
+use leptos::prelude::*;
+
+use super::manager::GenericWebSocketManager;
+use super::websocket_client::WebSocketClient;
+
+/// Options controlling how a provided manager is connected.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WebSocketContextOptions {
+    /// Connect automatically the first time `use_websocket_manager` is called
+    /// in the browser.
+    ///
+    /// When `false` (default), the application calls `connect()` itself.
+    pub connect_lazily: bool,
+}
+
+/// Value stored in the Leptos context for a given client type.
+#[derive(Clone)]
+struct WebSocketContext<T: WebSocketClient> {
+    /// The shared manager.
+    manager: GenericWebSocketManager<T>,
+
+    /// Whether a lazy connection is still to be started on first use.
+    connect_on_use: StoredValue<bool>,
+}
+
+/// Provides a WebSocket manager to all descendant components.
+///
+/// The manager is disconnected when the providing owner is cleaned up
+/// (e.g. when the providing component unmounts or the route changes).
+/// Equivalent to [`provide_websocket_manager_with`] with default options.
+///
+/// # Arguments
+///
+/// * `manager` - The manager to share
+pub fn provide_websocket_manager<T>(manager: GenericWebSocketManager<T>)
+where
+    T: WebSocketClient,
+{
+    provide_websocket_manager_with(manager, WebSocketContextOptions::default());
+}
+
+/// Provides a WebSocket manager to all descendant components with options.
+///
+/// # Arguments
+///
+/// * `manager` - The manager to share
+/// * `options` - Connection behavior (e.g. lazy connection on first use)
+///
+/// # Example
+///
+/// ```ignore
+/// provide_websocket_manager_with(
+///     RkyvWebSocketClient::new(uuid).create_manager(),
+///     WebSocketContextOptions { connect_lazily: true },
+/// );
+/// ```
+pub fn provide_websocket_manager_with<T>(
+    manager: GenericWebSocketManager<T>,
+    options: WebSocketContextOptions,
+) where
+    T: WebSocketClient,
+{
+    // Close the connection together with the providing owner
+    on_cleanup({
+        let manager = manager.clone();
+        move || manager.shutdown()
+    });
+
+    provide_context(WebSocketContext {
+        manager,
+        connect_on_use: StoredValue::new(options.connect_lazily),
+    });
+}
+
+/// Retrieves the WebSocket manager provided for client type `T`.
+///
+/// If the manager was provided with `connect_lazily`, the first call made in
+/// the browser starts the connection. On the server this never connects.
+///
+/// # Panics
+///
+/// Panics if no manager for `T` was provided by an ancestor component.
+///
+/// # Example
+///
+/// ```ignore
+/// #[component]
+/// fn DisconnectButton() -> impl IntoView {
+///     let manager = use_websocket_manager::<RkyvWebSocketClient>();
+///
+///     view! { <button on:click=move |_| manager.disconnect()>"Disconnect"</button> }
+/// }
+/// ```
+pub fn use_websocket_manager<T>() -> GenericWebSocketManager<T>
+where
+    T: WebSocketClient,
+{
+    let WebSocketContext {
+        manager,
+        connect_on_use,
+    } = use_context::<WebSocketContext<T>>().unwrap_or_else(|| {
+        panic!(
+            "No websocket manager provided for `{}`: call `provide_websocket_manager` in a parent component",
+            std::any::type_name::<T>()
+        )
+    });
+
+    if is_browser() && connect_on_use.get_value() {
+        connect_on_use.set_value(false);
+        manager.connect();
+    }
+
+    manager
+}
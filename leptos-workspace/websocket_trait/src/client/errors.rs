@@ -0,0 +1,31 @@
+//! Client-side WebSocket errors.
+
+use std::time::Duration;
+
+use thiserror::Error;
+
+/// Errors surfaced by `GenericWebSocketManager`.
+///
+/// Cloneable so it can be stored in reactive signals and shown in the UI.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum WebSocketError {
+    #[error("Not connected")]
+    NotConnected,
+
+    #[error("Failed to send request: {0}")]
+    Send(String),
+
+    #[error("Failed to connect websocket: {0}")]
+    Connection(String),
+
+    // ====== Timeouts =====
+    #[error("Handshake timed out after {}ms", .0.as_millis())]
+    HandshakeTimeout(Duration),
+
+    #[error("Request timed out after {}ms", .0.as_millis())]
+    RequestTimeout(Duration),
+
+    // ====== Protocol =====
+    #[error("A new version is available, reload the page")]
+    UpgradeRequired,
+}
@@ -0,0 +1,237 @@
+//! Client-side round-trip latency measurement.
+//!
+//! This module provides the `LatencyTracker` used by `GenericWebSocketManager`
+//! to time the handshake and to measure round-trip time (RTT) with periodic
+//! ping/pong messages, plus the coarse `ConnectionQuality` derived from it.
+//!
+//! Measuring from the client separates network latency from server slowness:
+//! a pong is answered by the server without doing any application work.
+
+use std::fmt::Display;
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+/// Default delay between two latency probes.
+pub const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Coarse connection quality derived from the measured round-trip time.
+///
+/// # Thresholds
+///
+/// * `Good` - RTT below 150ms
+/// * `Degraded` - RTT between 150ms and 500ms
+/// * `Bad` - RTT of 500ms or more (including pings that were never answered)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionQuality {
+    Good,
+    Degraded,
+    Bad,
+}
+
+impl ConnectionQuality {
+    /// Upper bound (exclusive) of a `Good` round-trip time.
+    const GOOD_THRESHOLD: Duration = Duration::from_millis(150);
+
+    /// Upper bound (exclusive) of a `Degraded` round-trip time.
+    const DEGRADED_THRESHOLD: Duration = Duration::from_millis(500);
+
+    /// Classifies a round-trip time.
+    pub fn from_rtt(rtt: Duration) -> Self {
+        if rtt < Self::GOOD_THRESHOLD {
+            Self::Good
+        } else if rtt < Self::DEGRADED_THRESHOLD {
+            Self::Degraded
+        } else {
+            Self::Bad
+        }
+    }
+}
+
+impl Display for ConnectionQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Self::Good => "good",
+            Self::Degraded => "degraded",
+            Self::Bad => "bad",
+        };
+
+        write!(f, "{str}")
+    }
+}
+
+/// Mutable probe state, shared by every clone of the tracker.
+#[derive(Default)]
+struct ProbeState {
+    /// Identifier of the next ping request.
+    next_id: u32,
+
+    /// Ping awaiting its pong: `(id, sent_at)` with `sent_at` in milliseconds.
+    pending: Option<(u32, f64)>,
+
+    /// Timestamp (milliseconds) at which the current connection attempt started.
+    connect_started: Option<f64>,
+
+    /// Periodic ping timer, running while connected.
+    timer: Option<IntervalHandle>,
+}
+
+/// Latency measurement for a single WebSocket manager.
+///
+/// Copyable handle: all state lives in reactive storage, so the tracker can be
+/// moved freely into the listener task and the ping timer.
+#[derive(Clone, Copy)]
+pub(super) struct LatencyTracker {
+    /// Delay between two pings.
+    interval: Duration,
+
+    /// Latest measured round-trip time.
+    rtt: RwSignal<Option<Duration>>,
+
+    /// Time between `connect()` and the handshake being acknowledged.
+    handshake_time: RwSignal<Option<Duration>>,
+
+    /// Probe bookkeeping (pending ping, timer handle, ...).
+    state: StoredValue<ProbeState>,
+}
+
+impl LatencyTracker {
+    /// Creates an idle tracker probing every `interval` once connected.
+    pub(super) fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            rtt: RwSignal::new(None),
+            handshake_time: RwSignal::new(None),
+            state: StoredValue::new(ProbeState::default()),
+        }
+    }
+
+    /// Changes the delay between two pings for future connections.
+    pub(super) fn set_ping_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Read-only view of the latest round-trip time.
+    pub(super) fn rtt(&self) -> Signal<Option<Duration>> {
+        self.rtt.into()
+    }
+
+    /// Read-only view of the handshake duration.
+    pub(super) fn handshake_time(&self) -> Signal<Option<Duration>> {
+        self.handshake_time.into()
+    }
+
+    /// Connection quality derived from the latest round-trip time.
+    pub(super) fn quality(&self) -> Signal<Option<ConnectionQuality>> {
+        let rtt = self.rtt;
+        Signal::derive(move || rtt.get().map(ConnectionQuality::from_rtt))
+    }
+
+    /// Marks the start of a connection attempt and resets previous measurements.
+    pub(super) fn connection_started(&self) {
+        self.stop();
+        self.handshake_time.set(None);
+        self.state
+            .update_value(|state| state.connect_started = Some(now()));
+    }
+
+    /// Records the handshake duration and starts periodic pings.
+    ///
+    /// `send_ping` sends a ping request with the given identifier and returns
+    /// `false` when the client does not support latency probes, which stops
+    /// the tracker.
+    pub(super) fn handshake_completed(&self, send_ping: impl Fn(u32) -> bool + 'static) {
+        if let Some(started) = self
+            .state
+            .try_update_value(|state| state.connect_started.take())
+            .flatten()
+        {
+            self.handshake_time.set(Some(elapsed_since(started)));
+        }
+
+        // First probe right away, then one per interval
+        let tracker = *self;
+        if !tracker.tick(&send_ping) {
+            return;
+        }
+
+        match set_interval_with_handle(
+            move || {
+                tracker.tick(&send_ping);
+            },
+            self.interval,
+        ) {
+            Ok(timer) => self.state.update_value(|state| state.timer = Some(timer)),
+            Err(e) => leptos::logging::error!("Failed to start latency probe: {e:?}"),
+        }
+    }
+
+    /// Records a pong, updating the round-trip time if it answers the pending ping.
+    pub(super) fn pong_received(&self, id: u32) {
+        let sent_at = self.state.try_update_value(|state| match state.pending {
+            Some((pending_id, sent_at)) if pending_id == id => {
+                state.pending = None;
+                Some(sent_at)
+            }
+            // Stale pong from a ping that was already given up on
+            _ => None,
+        });
+
+        if let Some(sent_at) = sent_at.flatten() {
+            self.rtt.set(Some(elapsed_since(sent_at)));
+        }
+    }
+
+    /// Stops periodic pings and clears the round-trip time.
+    pub(super) fn stop(&self) {
+        self.state.try_update_value(|state| {
+            if let Some(timer) = state.timer.take() {
+                timer.clear();
+            }
+            state.pending = None;
+        });
+        self.rtt.try_set(None);
+    }
+
+    /// Sends a new ping, or reports the pending one as slow if still unanswered.
+    ///
+    /// Returns `false` (and stops the tracker) when no ping could be sent.
+    fn tick(&self, send_ping: &impl Fn(u32) -> bool) -> bool {
+        let pending = self.state.with_value(|state| state.pending);
+
+        // The previous ping is still in flight: its age is a lower bound of the RTT
+        if let Some((_, sent_at)) = pending {
+            let waiting = elapsed_since(sent_at);
+            if self.rtt.get_untracked().is_none_or(|rtt| rtt < waiting) {
+                self.rtt.set(Some(waiting));
+            }
+            return true;
+        }
+
+        let id = self.state.with_value(|state| state.next_id);
+        if !send_ping(id) {
+            self.stop();
+            return false;
+        }
+
+        self.state.update_value(|state| {
+            state.next_id = state.next_id.wrapping_add(1);
+            state.pending = Some((id, now()));
+        });
+
+        true
+    }
+}
+
+/// Current high-resolution timestamp in milliseconds.
+pub(super) fn now() -> f64 {
+    window()
+        .performance()
+        .map(|performance| performance.now())
+        .unwrap_or_default()
+}
+
+/// Time elapsed since a timestamp obtained from `now()`.
+fn elapsed_since(start: f64) -> Duration {
+    Duration::from_secs_f64((now() - start).max(0.0) / 1000.0)
+}
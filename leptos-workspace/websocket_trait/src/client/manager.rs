@@ -0,0 +1,584 @@
+//! Generic client-side WebSocket manager.
+//!
+//! This module provides the `GenericWebSocketManager` struct that drives the
+//! connection lifecycle (connect, send, receive, disconnect) for any
+//! `WebSocketClient` implementation.
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use futures::channel::mpsc::{self, UnboundedSender};
+use futures::future::{AbortHandle, Abortable};
+use leptos::prelude::*;
+
+use super::connection::ConnectionSlot;
+use super::errors::WebSocketError;
+use super::latency::{ConnectionQuality, DEFAULT_PING_INTERVAL, LatencyTracker};
+use super::shared::{self, SharedSlot};
+use super::state::ConnectionState;
+use super::subscription::Subscribers;
+use super::timeout::{DEFAULT_HANDSHAKE_TIMEOUT, DEFAULT_REQUEST_TIMEOUT};
+use super::websocket_client::WebSocketClient;
+
+// ============================================================================
+// Type Aliases
+// ============================================================================
+
+/// Channel sender for WebSocket requests wrapped in Result.
+///
+/// This sender transmits requests from client to server, with each request
+/// wrapped in a Result to handle potential serialization errors.
+type RequestSender<T> = UnboundedSender<Result<T, ServerFnError>>;
+
+// ============================================================================
+// GenericWebSocketManager
+// ============================================================================
+
+/// Generic WebSocket manager for client-side connections.
+///
+/// Manages the WebSocket connection lifecycle (connect, send, disconnect) and
+/// message handling on the client side. This struct is generic over any type
+/// implementing `WebSocketClient`, enabling reusable WebSocket logic.
+///
+/// # Type Parameters
+///
+/// * `T` - The WebSocket client implementation defining message types and handlers
+///
+/// # Fields
+///
+/// * `connection` - Sender for outgoing requests and the response listener
+/// * `is_connected` - Reactive signal tracking connection state
+/// * `state` - Connection lifecycle, including the error of a failed attempt
+/// * `client` - The client implementation containing business logic
+/// * `subscribers` - Listeners registered through the subscription API
+/// * `rtt` / `handshake_time` / `quality` - Client-side latency measurements
+///
+/// # Example
+///
+/// ```ignore
+/// let manager = WebSocketManager::new(uuid);
+/// manager.connect();
+/// manager.send(Request::Ping)?;
+/// manager.disconnect();
+/// ```
+pub struct GenericWebSocketManager<T: WebSocketClient> {
+    /// Channel sender for outgoing requests and the task listening for responses.
+    ///
+    /// Stored in a reactive StoredValue to persist across re-renders. The
+    /// listener is aborted when a new connection is started or the owning
+    /// reactive scope is disposed, so at most one listener runs at a time.
+    connection: StoredValue<ConnectionSlot<RequestSender<T::Request>>>,
+
+    /// Reactive signal indicating connection status.
+    ///
+    /// - `false` - Disconnected or connection failed
+    /// - `true` - Connected and ready to send/receive
+    ///
+    /// This signal can be used in UI components to show connection state.
+    pub is_connected: RwSignal<bool>,
+
+    /// Whether a connection attempt is waiting for its handshake acknowledgement.
+    connecting: RwSignal<bool>,
+
+    /// Error of the last failed connection attempt, cleared by `connect()`.
+    error: RwSignal<Option<WebSocketError>>,
+
+    /// Connection lifecycle derived from `is_connected` and the pending attempt.
+    ///
+    /// Unlike `is_connected`, tells an attempt in progress and a failed
+    /// attempt (with its error) apart from a plain disconnected state.
+    pub state: Signal<ConnectionState>,
+
+    /// Time allowed for the handshake to be acknowledged after `connect()`.
+    handshake_timeout: Duration,
+
+    /// Cancels the attempt when the handshake deadline expires.
+    handshake_timer: StoredValue<Option<TimeoutHandle>>,
+
+    /// Time allowed for the server to answer a request sent with `request()`.
+    pub(super) request_timeout: Duration,
+
+    /// The client implementation defining message types and handlers.
+    ///
+    /// Contains the business logic for creating requests and handling responses.
+    client: T,
+
+    /// Listeners registered through `subscribe()`, `subscribe_map()` and `stream()`.
+    ///
+    /// Every incoming response is dispatched to these listeners before being
+    /// passed to `WebSocketClient::handle_response`.
+    pub(super) subscribers: StoredValue<Subscribers<T::Response>>,
+
+    /// Handshake timing and periodic ping/pong round-trip measurement.
+    latency: LatencyTracker,
+
+    /// Cross-tab sharing state, set by `with_shared_connection()`.
+    pub(super) shared: SharedSlot<T>,
+
+    /// Latest round-trip time measured with ping/pong messages.
+    ///
+    /// `None` while disconnected, before the first pong, or when the client
+    /// does not implement `WebSocketClient::create_ping_request`.
+    pub rtt: Signal<Option<Duration>>,
+
+    /// Time between `connect()` and the server acknowledging the handshake.
+    pub handshake_time: Signal<Option<Duration>>,
+
+    /// Coarse connection quality derived from `rtt`.
+    ///
+    /// Tells network slowness (degraded/bad RTT) apart from server slowness
+    /// (good RTT but slow responses).
+    pub quality: Signal<Option<ConnectionQuality>>,
+}
+
+// Implemented by hand: `#[derive(Clone)]` would require the request and
+// response types to be `Clone`, although only handles to them are stored.
+impl<T: WebSocketClient> Clone for GenericWebSocketManager<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            ..*self
+        }
+    }
+}
+
+impl<T: WebSocketClient> GenericWebSocketManager<T> {
+    /// Creates a new WebSocket manager with the given client implementation.
+    ///
+    /// This is used internally by the `create_manager()` trait method.
+    /// Prefer using type-specific constructors (e.g., `WebSocketManager::new(uuid)`)
+    /// for better ergonomics.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The WebSocket client implementation
+    ///
+    /// # Returns
+    ///
+    /// A new manager instance in disconnected state.
+    ///
+    /// The connection is torn down automatically when the reactive owner
+    /// that created the manager is disposed (e.g. the page unmounts).
+    pub(super) fn new_with_client(client: T) -> Self {
+        let latency = LatencyTracker::new(DEFAULT_PING_INTERVAL);
+        let is_connected = RwSignal::new(false);
+        let connecting = RwSignal::new(false);
+        let error = RwSignal::new(None);
+
+        let manager = Self {
+            connection: StoredValue::new(ConnectionSlot::default()),
+            is_connected,
+            connecting,
+            error,
+            state: Signal::derive(move || {
+                if is_connected.get() {
+                    ConnectionState::Connected
+                } else if connecting.get() {
+                    ConnectionState::Connecting
+                } else if let Some(e) = error.get() {
+                    ConnectionState::Failed(e)
+                } else {
+                    ConnectionState::Disconnected
+                }
+            }),
+            handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
+            handshake_timer: StoredValue::new(None),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            client,
+            subscribers: StoredValue::new(Subscribers::default()),
+            latency,
+            shared: StoredValue::new_local(None),
+            rtt: latency.rtt(),
+            handshake_time: latency.handshake_time(),
+            quality: latency.quality(),
+        };
+
+        // Disconnect and stop listening together with the owning scope
+        on_cleanup({
+            let manager = manager.clone();
+            move || manager.shutdown()
+        });
+
+        manager
+    }
+
+    /// Sets the delay between two latency probes (default: 5 seconds).
+    ///
+    /// Takes effect on the next `connect()`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let manager = RkyvWebSocketClient::new(uuid)
+    ///     .create_manager()
+    ///     .with_ping_interval(Duration::from_secs(2));
+    /// ```
+    pub fn with_ping_interval(mut self, interval: Duration) -> Self {
+        self.latency.set_ping_interval(interval);
+        self
+    }
+
+    /// Sets the time allowed for the handshake to be acknowledged (default: 10 seconds).
+    ///
+    /// When the deadline expires, the attempt is cancelled and `state` becomes
+    /// `ConnectionState::Failed(WebSocketError::HandshakeTimeout(..))`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let manager = RkyvWebSocketClient::new(uuid)
+    ///     .create_manager()
+    ///     .with_handshake_timeout(Duration::from_secs(3));
+    /// ```
+    pub fn with_handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout = timeout;
+        self
+    }
+
+    /// Sets the time allowed for a reply to a request sent with `request()` (default: 10 seconds).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let manager = RkyvWebSocketClient::new(uuid)
+    ///     .create_manager()
+    ///     .with_request_timeout(Duration::from_secs(5));
+    /// ```
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Establishes WebSocket connection and starts listening for responses.
+    ///
+    /// This method:
+    /// 1. Creates a new unbounded channel for bidirectional communication
+    /// 2. Sends a handshake request to the server
+    /// 3. Spawns an async task to listen for incoming responses
+    /// 4. Updates the `is_connected` signal based on connection state
+    ///
+    /// # Behavior
+    ///
+    /// - Non-blocking: Spawns a background task to handle responses
+    /// - Idempotent: Does nothing while a connection is open or being established
+    /// - Cancellation: Aborts the listener of a previous connection still draining
+    /// - Deadline: Cancels the attempt if the handshake is not acknowledged in time
+    /// - Error handling: Logs errors, sets `is_connected` to false and `state` to `Failed` on failure
+    /// - Versioning: Fails with `WebSocketError::UpgradeRequired` when the server rejects this
+    ///   build or sends responses it can not decode
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let manager = WebSocketManager::new(uuid);
+    /// manager.connect(); // Starts connection in background
+    /// ```
+    pub fn connect(&self) {
+        // Follower tabs ask the leader tab to connect instead
+        if self.forward_connect() {
+            return;
+        }
+
+        // A connection is already open or being established
+        if self.connection.with_value(ConnectionSlot::is_open) {
+            return;
+        }
+
+        self.error.set(None);
+
+        // Create unbounded channel for bidirectional communication
+        // tx: send requests to server
+        // rx: will be converted to stream by server function
+        let (tx, rx) = mpsc::unbounded();
+
+        // Send initial handshake request to establish connection
+        let handshake = self.client.create_handshake_request();
+        if let Err(e) = tx.unbounded_send(Ok(handshake)) {
+            leptos::logging::error!("Failed to send handshake: {e}");
+            return;
+        }
+
+        // Store the sender for future use in send() method, together with the
+        // listener spawned below. A previous listener can no longer update the
+        // connection state.
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        self.connection.update_value(|connection| {
+            connection.open(tx, abort_handle);
+        });
+        self.connecting.set(true);
+        let is_connected = self.is_connected;
+        let connecting = self.connecting;
+        let error = self.error;
+        let handshake_timer = self.handshake_timer;
+        let subscribers = self.subscribers;
+        let latency = self.latency;
+        let connection = self.connection;
+        let shared = self.shared;
+        let client = self.client.clone();
+
+        // Start timing the handshake
+        latency.connection_started();
+        self.start_handshake_timer();
+
+        // Reset the connection state once the stream is gone, so `connect()` works again
+        let connection_closed = move || {
+            clear_timer(handshake_timer);
+            latency.stop();
+            connection.try_update_value(ConnectionSlot::close);
+            connecting.try_set(false);
+            is_connected.try_set(false);
+        };
+
+        // Async task handling incoming responses
+        let listener = async move {
+            // Establish WebSocket stream via server function
+            let mut stream = match T::get_stream(rx).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let e = WebSocketError::Connection(e.to_string());
+                    leptos::logging::error!("{e}");
+                    error.try_set(Some(e));
+                    connection_closed();
+                    return;
+                }
+            };
+
+            // Set once the client acknowledged the handshake via `is_connected`
+            let mut handshake_completed = false;
+
+            // Listen for incoming responses until connection closes
+            while let Some(response) = stream.next().await {
+                let response = match response {
+                    Ok(response) => response,
+                    // Responses this build can not decode come from a newer server
+                    Err(ServerFnError::Deserialization(e)) => {
+                        leptos::logging::error!("Failed to decode response: {e}");
+                        error.try_set(Some(WebSocketError::UpgradeRequired));
+                        connection_closed();
+                        return;
+                    }
+                    Err(e) => {
+                        // Handle WebSocket closure (code 1005 = normal closure)
+                        match e.to_string().as_ref() {
+                            "error reaching server to call server function: WebSocket Closed: code: 1005, reason:" =>
+                            {
+                                leptos::logging::log!("Websocket closed: {e}");
+                                connection_closed();
+                                return;
+                            }
+                            // Log other errors but continue listening
+                            _ => {
+                                leptos::logging::error!("error: {e}");
+                                continue;
+                            }
+                        }
+                    }
+                };
+
+                // Latency probes are handled by the manager, not the client
+                if let Some(id) = T::pong_id(&response) {
+                    latency.pong_received(id);
+                    continue;
+                }
+
+                // The server rejected this build: stop instead of misreading responses
+                if T::is_upgrade_required(&response) {
+                    leptos::logging::log!("Server requires a newer client");
+                    error.try_set(Some(WebSocketError::UpgradeRequired));
+                    connection_closed();
+                    return;
+                }
+
+                // Relay to follower tabs when the connection is shared
+                shared::relay_response(shared, &response);
+
+                // Notify subscribed components first, then delegate
+                // response handling to client implementation
+                Subscribers::dispatch(subscribers, &response);
+                T::handle_response(response, is_connected);
+
+                // Handshake acknowledged: record its duration and start pinging
+                if !handshake_completed && is_connected.get_untracked() {
+                    handshake_completed = true;
+                    clear_timer(handshake_timer);
+                    connecting.set(false);
+
+                    let client = client.clone();
+                    latency.handshake_completed(move |id| {
+                        let Some(ping) = client.create_ping_request(id) else {
+                            return false;
+                        };
+
+                        // Probes are best-effort: a closed channel ends the connection anyway
+                        connection.with_value(|connection| {
+                            connection
+                                .sender()
+                                .is_some_and(|tx| tx.unbounded_send(Ok(ping)).is_ok())
+                        })
+                    });
+                }
+            }
+
+            // Stream ended without a close error
+            connection_closed();
+        };
+
+        // Spawn the listener so it can be cancelled by the next `connect()` or on cleanup
+        leptos::task::spawn_local(async move {
+            // Resolves early with `Err(Aborted)` once cancelled
+            let _ = Abortable::new(listener, abort_registration).await;
+        });
+    }
+
+    /// Sends a request through the WebSocket connection.
+    ///
+    /// Requires an active connection established via `connect()`.
+    /// Messages are queued and sent asynchronously.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request message to send to the server
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Message queued successfully
+    /// * `Err(WebSocketError)` - Connection unavailable or send failed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Not connected (no open connection): `WebSocketError::NotConnected`
+    /// - Channel is closed (server disconnected): `WebSocketError::Send`
+    ///
+    /// `send` has no deadline; use `request()` to wait for a reply with a timeout.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// manager.send(Request::Ping)?;
+    /// manager.send(Request::Message("Hello".to_string()))?;
+    /// ```
+    pub fn send(&self, request: T::Request) -> Result<(), WebSocketError> {
+        // Follower tabs send through the leader tab
+        if self.forward_request(&request) {
+            return Ok(());
+        }
+
+        let tx = self
+            .connection
+            .with_value(|connection| connection.sender().cloned());
+        match tx {
+            Some(tx) => {
+                // Send request through the channel
+                tx.unbounded_send(Ok(request))
+                    .map_err(|e| WebSocketError::Send(e.to_string()))
+            }
+            None => {
+                // Connection not established or already closed
+                leptos::logging::error!("No open connection");
+                self.is_connected.set(false);
+                Err(WebSocketError::NotConnected)
+            }
+        }
+    }
+
+    /// Gracefully disconnects the WebSocket.
+    ///
+    /// Sends a disconnect request to notify the server, then updates the
+    /// connection state. The server should clean up resources and close
+    /// the connection upon receiving the disconnect request.
+    ///
+    /// # Behavior
+    ///
+    /// 1. Sends disconnect request to server
+    /// 2. Clears the request sender, closing the client → server stream
+    /// 3. Sets `is_connected` to false
+    /// 4. Logs any errors during disconnection
+    ///
+    /// Does nothing when not connected. The listener keeps running until the
+    /// server closes the stream, unless a new connection is started first.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// manager.disconnect(); // Graceful shutdown
+    /// ```
+    pub fn disconnect(&self) {
+        // Follower tabs ask the leader tab to disconnect instead
+        if self.forward_disconnect() {
+            return;
+        }
+
+        // Take the sender so no further request can be sent on this connection
+        let Some(tx) = self
+            .connection
+            .try_update_value(|connection| connection.close())
+            .flatten()
+        else {
+            return;
+        };
+
+        // Create and send disconnect request
+        let disconnect = self.client.create_disconnect_request();
+        if let Err(e) = tx.unbounded_send(Ok(disconnect)) {
+            leptos::logging::error!("Failed to send disconnect request: {e}");
+        }
+
+        // Update connection state immediately
+        // Dropping `tx` ends the request stream, so the server closes the connection
+        clear_timer(self.handshake_timer);
+        self.latency.stop();
+        self.connecting.try_set(false);
+        self.is_connected.try_set(false);
+    }
+
+    /// Leaves the shared connection, disconnects and cancels the listener.
+    ///
+    /// Called when the reactive owner creating or providing the manager is disposed.
+    /// Only this tab's connection is closed: other tabs keep the shared one.
+    pub(super) fn shutdown(&self) {
+        self.leave_shared_connection();
+        self.disconnect();
+        self.connection.try_update_value(ConnectionSlot::abort);
+    }
+
+    /// Schedules the cancellation of the current attempt at the handshake deadline.
+    fn start_handshake_timer(&self) {
+        let manager = self.clone();
+
+        match set_timeout_with_handle(
+            move || manager.handshake_timed_out(),
+            self.handshake_timeout,
+        ) {
+            Ok(timer) => self.handshake_timer.set_value(Some(timer)),
+            Err(e) => leptos::logging::error!("Failed to start handshake timeout: {e:?}"),
+        }
+    }
+
+    /// Marks the pending attempt as failed and cancels it.
+    fn handshake_timed_out(&self) {
+        self.handshake_timer.try_set_value(None);
+
+        // Acknowledged in the meantime, or the manager was disposed
+        if self.connecting.try_get_untracked() != Some(true) {
+            return;
+        }
+
+        let e = WebSocketError::HandshakeTimeout(self.handshake_timeout);
+        leptos::logging::error!("{e}");
+        self.error.try_set(Some(e));
+
+        // Stop listening and drop the sender so the request stream ends
+        self.connection.try_update_value(ConnectionSlot::abort);
+        self.latency.stop();
+        self.connecting.try_set(false);
+        self.is_connected.try_set(false);
+    }
+}
+
+/// Cancels a pending handshake deadline, if any.
+fn clear_timer(timer: StoredValue<Option<TimeoutHandle>>) {
+    if let Some(timer) = timer.try_update_value(Option::take).flatten() {
+        timer.clear();
+    }
+}
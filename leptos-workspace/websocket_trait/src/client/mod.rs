@@ -0,0 +1,63 @@
+//! Client-side WebSocket manager trait.
+//!
+//! This module provides a trait-based abstraction for WebSocket client management
+//! with generic message handling. It enables type-safe WebSocket communication
+//! between client and server with custom message types.
+//!
+//! # Architecture
+//!
+//! - [`WebSocketClient`] - Trait defining message types and response handling
+//! - [`GenericWebSocketManager`] - Generic connection manager
+//! - [`provide_websocket_manager`] / [`use_websocket_manager`] - Share a manager through Leptos context
+//! - [`ConnectionQuality`] - Coarse quality derived from the measured round-trip time
+//! - [`ConnectionState`] / [`WebSocketError`] - Connection lifecycle and failures (e.g. timeouts,
+//!   outdated protocol version)
+//! - Subscriptions - Per-component views of the response stream
+//!   ([`GenericWebSocketManager::subscribe`], [`GenericWebSocketManager::stream`])
+//! - [`SharedWebSocketClient`] - One connection shared across browser tabs
+//!   ([`GenericWebSocketManager::with_shared_connection`])
+//! - [`Transport`] - Websocket or SSE transport, falling back to SSE when the upgrade fails
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Define your message types
+//! enum Request { Ping, Disconnect }
+//! enum Response { Pong }
+//!
+//! // Implement the WebSocketClient trait
+//! impl WebSocketClient for MyClient {
+//!     type Request = Request;
+//!     type Response = Response;
+//!     // ... implement required methods
+//! }
+//!
+//! // Create and use the manager
+//! let manager = MyClient::new().create_manager();
+//! manager.connect();
+//! ```
+
+mod connection;
+mod context;
+mod errors;
+mod latency;
+mod manager;
+mod shared;
+mod state;
+mod subscription;
+mod timeout;
+pub mod transport;
+mod websocket_client;
+
+pub use context::{
+    WebSocketContextOptions, provide_websocket_manager, provide_websocket_manager_with,
+    use_websocket_manager,
+};
+pub use errors::WebSocketError;
+pub use latency::{ConnectionQuality, DEFAULT_PING_INTERVAL};
+pub use manager::GenericWebSocketManager;
+pub use shared::SharedWebSocketClient;
+pub use state::ConnectionState;
+pub use timeout::{DEFAULT_HANDSHAKE_TIMEOUT, DEFAULT_REQUEST_TIMEOUT};
+pub use transport::{Transport, set_transport};
+pub use websocket_client::WebSocketClient;
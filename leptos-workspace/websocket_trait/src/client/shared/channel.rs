@@ -0,0 +1,64 @@
+//! Transport of the tab messages.
+
+use js_sys::Uint8Array;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+use web_sys::{BroadcastChannel, MessageEvent};
+
+use super::message::TabMessage;
+
+/// Where a tab posts its messages to the other tabs.
+///
+/// `BrowserChannel` in the browser; the election is driven through this
+/// trait so that it can be tested without one.
+pub(in crate::client) trait TabChannel {
+    /// Posts a message to every other tab.
+    fn post(&self, message: &TabMessage);
+
+    /// Stops listening and releases the channel.
+    fn close(&self);
+}
+
+/// A `BroadcastChannel` and its `onmessage` handler.
+pub(in crate::client) struct BrowserChannel {
+    channel: BroadcastChannel,
+
+    /// Kept alive as long as the channel.
+    _on_message: Closure<dyn Fn(MessageEvent)>,
+}
+
+impl BrowserChannel {
+    /// Starts delivering the messages of the other tabs to `on_message`.
+    pub(super) fn new(
+        channel: BroadcastChannel,
+        on_message: Closure<dyn Fn(MessageEvent)>,
+    ) -> Self {
+        channel.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        Self {
+            channel,
+            _on_message: on_message,
+        }
+    }
+}
+
+impl TabChannel for BrowserChannel {
+    fn post(&self, message: &TabMessage) {
+        let Some(bytes) = message.encode() else {
+            leptos::logging::error!("Failed to encode tab message");
+            return;
+        };
+
+        if let Err(e) = self
+            .channel
+            .post_message(&Uint8Array::from(bytes.as_slice()).into())
+        {
+            leptos::logging::error!("Failed to post tab message: {e:?}");
+        }
+    }
+
+    fn close(&self) {
+        self.channel.set_onmessage(None);
+        self.channel.close();
+    }
+}
@@ -0,0 +1,52 @@
+//! Messages exchanged between tabs over the `BroadcastChannel`.
+
+use rkyv::rancor;
+use rkyv::util::AlignedVec;
+use rkyv::{Archive, Deserialize, Serialize};
+
+/// Message broadcast to every other tab sharing the connection.
+///
+/// Requests and responses are carried as opaque bytes produced by the
+/// `SharedWebSocketClient` implementation.
+#[derive(Debug, Clone, Archive, Deserialize, Serialize)]
+pub(in crate::client) enum TabMessage {
+    /// Sent periodically by the leader, and whenever its connection state changes.
+    Heartbeat { leader: u64, connected: bool },
+
+    /// Candidacy of a tab during an election. The lowest identifier wins.
+    Claim { tab: u64 },
+
+    /// Sent by the leader when its tab closes, so followers elect a new leader
+    /// right away and reconnect if the connection was open.
+    Resign { leader: u64, connected: bool },
+
+    /// Follower → leader: open the shared connection.
+    Connect,
+
+    /// Follower → leader: close the shared connection.
+    Disconnect,
+
+    /// Follower → leader: encoded request to send to the server.
+    Request(Vec<u8>),
+
+    /// Leader → followers: encoded response received from the server.
+    Response(Vec<u8>),
+}
+
+impl TabMessage {
+    /// Serializes the message for `BroadcastChannel::post_message`.
+    pub(super) fn encode(&self) -> Option<Vec<u8>> {
+        rkyv::to_bytes::<rancor::Error>(self)
+            .map(|bytes| bytes.to_vec())
+            .ok()
+    }
+
+    /// Deserializes a message received from another tab.
+    pub(super) fn decode(bytes: &[u8]) -> Option<Self> {
+        // Archived data must be aligned before validation
+        let mut aligned = AlignedVec::<16>::new();
+        aligned.extend_from_slice(bytes);
+
+        rkyv::from_bytes::<Self, rancor::Error>(&aligned).ok()
+    }
+}
@@ -0,0 +1,280 @@
+//! Sharing a single WebSocket connection across browser tabs.
+//!
+//! With `GenericWebSocketManager::with_shared_connection`, every tab using the
+//! same channel name takes part in a leader election over the browser's
+//! `BroadcastChannel` API:
+//!
+//! - The leader holds the real connection and relays every response to the
+//!   other tabs, along with its connection state
+//! - Followers forward `connect()`, `disconnect()` and `send()` to the leader
+//! - The leader sends a heartbeat every `HEARTBEAT_INTERVAL`; followers elect
+//!   a new leader when it resigns (its tab closes) or stops sending heartbeats
+//!
+//! The new leader reconnects if the shared connection was open. Responses are
+//! delivered to every tab; latency probes and timeouts run in the leader only.
+
+mod channel;
+mod message;
+mod shared_client;
+mod state;
+
+use std::time::Duration;
+
+use js_sys::Uint8Array;
+use leptos::prelude::*;
+use wasm_bindgen::closure::Closure;
+use web_sys::{BroadcastChannel, MessageEvent};
+
+use super::latency::now;
+use super::manager::GenericWebSocketManager;
+use super::subscription::Subscribers;
+use super::websocket_client::WebSocketClient;
+use channel::BrowserChannel;
+use message::TabMessage;
+use state::{SharedState, TabAction};
+
+pub use shared_client::SharedWebSocketClient;
+
+/// Delay between two heartbeats of the leader, and between two election checks.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time (milliseconds) without heartbeat after which followers elect a new leader.
+const LEADER_TIMEOUT: f64 = 2000.0;
+
+/// Time (milliseconds) during which candidates collect the claims of other tabs.
+const ELECTION_WINDOW: f64 = 500.0;
+
+/// Shared connection state stored in the manager, `None` unless enabled.
+///
+/// Local storage: the channel and its callbacks are JavaScript objects.
+pub(super) type SharedSlot<T> = StoredValue<Option<SharedState<T>>, LocalStorage>;
+
+impl<T: WebSocketClient> GenericWebSocketManager<T> {
+    /// Shares one connection between every tab using the same channel name.
+    ///
+    /// Only the elected leader tab opens the real connection; the others relay
+    /// through it. Has no effect outside the browser. Call it after the other
+    /// `with_*` builders, whose settings are captured here.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the `BroadcastChannel`, identical in every tab
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let manager = RkyvWebSocketClient::new(uuid)
+    ///     .create_manager()
+    ///     .with_shared_connection("rkyv_websocket");
+    /// ```
+    pub fn with_shared_connection(self, name: &str) -> Self
+    where
+        T: SharedWebSocketClient,
+    {
+        if !is_browser() {
+            return self;
+        }
+
+        let channel = match BroadcastChannel::new(name) {
+            Ok(channel) => channel,
+            Err(e) => {
+                leptos::logging::error!("Failed to open broadcast channel: {e:?}");
+                return self;
+            }
+        };
+
+        let on_message = Closure::<dyn Fn(MessageEvent)>::new({
+            let manager = self.clone();
+            move |event: MessageEvent| {
+                let bytes = Uint8Array::new(&event.data()).to_vec();
+                match TabMessage::decode(&bytes) {
+                    Some(message) => manager.receive_tab_message(message),
+                    None => leptos::logging::error!("Failed to decode tab message"),
+                }
+            }
+        });
+
+        let tab = (js_sys::Math::random() * u64::MAX as f64) as u64;
+        let channel = BrowserChannel::new(channel, on_message);
+        self.shared
+            .set_value(Some(SharedState::new(tab, channel, now())));
+
+        // Heartbeats and elections
+        let timer = set_interval_with_handle(
+            {
+                let manager = self.clone();
+                move || manager.tab_tick()
+            },
+            HEARTBEAT_INTERVAL,
+        );
+        if let Err(e) = &timer {
+            leptos::logging::error!("Failed to start tab heartbeat: {e:?}");
+        }
+
+        // Hand leadership over when the tab closes
+        let pagehide = window_event_listener_untyped("pagehide", {
+            let manager = self.clone();
+            move |_| manager.resign_leadership()
+        });
+
+        self.shared.update_value(|shared| {
+            if let Some(shared) = shared {
+                shared.timer = timer.ok();
+                shared.pagehide = Some(pagehide);
+            }
+        });
+
+        // Followers mirror connection state changes of the leader right away
+        let is_connected = self.is_connected;
+        let shared = self.shared;
+        Effect::new(move |_| {
+            let connected = is_connected.get();
+            shared.update_value(|shared| {
+                if let Some(shared) = shared {
+                    shared.announce(connected);
+                }
+            });
+        });
+
+        self
+    }
+
+    /// Forwards `connect()` to the leader tab.
+    ///
+    /// Returns `true` when this tab is a follower, `false` when it must connect itself.
+    pub(super) fn forward_connect(&self) -> bool {
+        self.forward_to_leader(TabMessage::Connect)
+    }
+
+    /// Forwards `disconnect()` to the leader tab.
+    ///
+    /// Returns `true` when this tab is a follower, `false` when it must disconnect itself.
+    pub(super) fn forward_disconnect(&self) -> bool {
+        self.forward_to_leader(TabMessage::Disconnect)
+    }
+
+    /// Forwards a request to the leader tab.
+    ///
+    /// Returns `true` when this tab is a follower, `false` when it must send the request itself.
+    pub(super) fn forward_request(&self, request: &T::Request) -> bool {
+        self.shared
+            .try_update_value(|shared| {
+                shared
+                    .as_mut()
+                    .is_some_and(|shared| shared.forward_request(request))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Leaves the shared connection, handing leadership over if needed.
+    pub(super) fn leave_shared_connection(&self) {
+        let connected = self.is_connected.try_get_untracked().unwrap_or(false);
+
+        if let Some(mut shared) = self.shared.try_update_value(Option::take).flatten() {
+            shared.resign(connected);
+            shared.close();
+        }
+    }
+
+    /// Posts a follower operation to the leader, if this tab is a follower.
+    fn forward_to_leader(&self, message: TabMessage) -> bool {
+        self.shared
+            .try_update_value(|shared| {
+                shared
+                    .as_mut()
+                    .is_some_and(|shared| shared.forward(message))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Announces that the leader tab is closing.
+    fn resign_leadership(&self) {
+        let connected = self.is_connected.get_untracked();
+        self.shared.update_value(|shared| {
+            if let Some(shared) = shared {
+                shared.resign(connected);
+            }
+        });
+    }
+
+    /// Handles a message posted by another tab.
+    fn receive_tab_message(&self, message: TabMessage) {
+        let now = now();
+        let action = self
+            .shared
+            .try_update_value(|shared| {
+                shared
+                    .as_mut()
+                    .and_then(|shared| shared.receive(message, now))
+            })
+            .flatten();
+
+        if let Some(action) = action {
+            self.apply_tab_action(action, now);
+        }
+    }
+
+    /// Periodic heartbeat and election step.
+    fn tab_tick(&self) {
+        let now = now();
+        let connected = self.is_connected.get_untracked();
+        let action = self
+            .shared
+            .try_update_value(|shared| {
+                shared
+                    .as_mut()
+                    .and_then(|shared| shared.tick(now, connected))
+            })
+            .flatten();
+
+        if let Some(action) = action {
+            self.apply_tab_action(action, now);
+        }
+    }
+
+    /// Performs the operation decided by the shared state on this manager.
+    fn apply_tab_action(&self, action: TabAction<T>, now: f64) {
+        match action {
+            TabAction::Connect => self.connect(),
+            TabAction::Disconnect => self.disconnect(),
+            TabAction::Send(request) => {
+                if let Err(e) = self.send(request) {
+                    leptos::logging::error!("Failed to send request of a follower tab: {e}");
+                }
+            }
+            TabAction::Dispatch(response) => {
+                Subscribers::dispatch(self.subscribers, &response);
+                T::handle_response(response, self.is_connected);
+            }
+            TabAction::SetConnected(connected) => {
+                if self.is_connected.get_untracked() != connected {
+                    self.is_connected.set(connected);
+                }
+            }
+            TabAction::Lead { connect } => {
+                // The mirrored state belonged to the previous leader
+                self.is_connected.set(false);
+                if connect {
+                    self.connect();
+                }
+            }
+            TabAction::StepDown => {
+                self.disconnect();
+                self.shared.update_value(|shared| {
+                    if let Some(shared) = shared {
+                        shared.follow(now);
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Broadcasts a response received by the leader to the follower tabs.
+pub(super) fn relay_response<T: WebSocketClient>(shared: SharedSlot<T>, response: &T::Response) {
+    shared.try_with_value(|shared| {
+        if let Some(shared) = shared {
+            shared.relay_response(response);
+        }
+    });
+}
@@ -0,0 +1,34 @@
+//! Trait for clients whose connection can be shared across tabs.
+
+use super::super::websocket_client::WebSocketClient;
+
+/// Encoding of requests and responses relayed between tabs.
+///
+/// Required by `GenericWebSocketManager::with_shared_connection`: followers
+/// forward their requests to the leader tab, which broadcasts every response
+/// back, both as bytes over a `BroadcastChannel`. Returning `None` drops the
+/// message (and logs an error).
+///
+/// # Example
+///
+/// ```ignore
+/// impl SharedWebSocketClient for RkyvWebSocketClient {
+///     fn encode_request(request: &Self::Request) -> Option<Vec<u8>> {
+///         rkyv::to_bytes::<rkyv::rancor::Error>(request).ok().map(|bytes| bytes.to_vec())
+///     }
+///     // ... decode_request, encode_response, decode_response
+/// }
+/// ```
+pub trait SharedWebSocketClient: WebSocketClient {
+    /// Encodes a request forwarded from a follower to the leader.
+    fn encode_request(request: &Self::Request) -> Option<Vec<u8>>;
+
+    /// Decodes a request received by the leader.
+    fn decode_request(bytes: &[u8]) -> Option<Self::Request>;
+
+    /// Encodes a response broadcast by the leader.
+    fn encode_response(response: &Self::Response) -> Option<Vec<u8>>;
+
+    /// Decodes a response received by a follower.
+    fn decode_response(bytes: &[u8]) -> Option<Self::Response>;
+}
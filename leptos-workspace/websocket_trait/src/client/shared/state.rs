@@ -0,0 +1,568 @@
+//! Leader election state of a tab sharing a connection.
+//!
+//! `SharedState` only decides: it updates the role of the tab, posts tab
+//! messages, and returns a `TabAction` for the manager to perform on its own
+//! connection. Keeping side effects on the manager out of the state avoids
+//! re-entering its storage while it is being updated.
+
+use leptos::prelude::*;
+
+use super::super::websocket_client::WebSocketClient;
+use super::channel::{BrowserChannel, TabChannel};
+use super::message::TabMessage;
+use super::shared_client::SharedWebSocketClient;
+use super::{ELECTION_WINDOW, LEADER_TIMEOUT};
+
+/// Role of this tab for the shared connection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Role {
+    /// Election in progress since `since`; `best` is the lowest claim seen so far.
+    Candidate { since: f64, best: u64 },
+
+    /// Relaying through the leader tab.
+    Follower,
+
+    /// Holding the real connection.
+    Leader,
+}
+
+/// Operation the manager performs after the state was updated.
+pub(super) enum TabAction<T: WebSocketClient> {
+    /// Leader: open the real connection.
+    Connect,
+
+    /// Leader: close the real connection.
+    Disconnect,
+
+    /// Leader: send a request forwarded by a follower.
+    Send(T::Request),
+
+    /// Follower: handle a response relayed by the leader.
+    Dispatch(T::Response),
+
+    /// Follower: mirror the connection state of the leader.
+    SetConnected(bool),
+
+    /// Elected: take over, opening the real connection if it was open before.
+    Lead { connect: bool },
+
+    /// Another leader with a lower identifier exists: close the real connection,
+    /// then `follow()`.
+    StepDown,
+}
+
+/// Encoding functions of a `SharedWebSocketClient`, stored without the trait bound.
+struct Codec<T: WebSocketClient> {
+    encode_request: fn(&T::Request) -> Option<Vec<u8>>,
+    decode_request: fn(&[u8]) -> Option<T::Request>,
+    encode_response: fn(&T::Response) -> Option<Vec<u8>>,
+    decode_response: fn(&[u8]) -> Option<T::Response>,
+}
+
+/// Per-tab state of a shared connection.
+pub(in crate::client) struct SharedState<T: WebSocketClient, C: TabChannel = BrowserChannel> {
+    /// Random identifier of this tab.
+    tab: u64,
+
+    /// Channel shared by every tab using the same name.
+    channel: C,
+
+    /// Current role of this tab.
+    pub(super) role: Role,
+
+    /// Timestamp (milliseconds) of the last heartbeat received from the leader.
+    last_heartbeat: f64,
+
+    /// Last known state of the shared connection, used on leadership changes.
+    leader_connected: bool,
+
+    /// `connect()` was called while no leader was known.
+    pending_connect: bool,
+
+    /// Request/response encoding.
+    codec: Codec<T>,
+
+    /// Periodic heartbeat and election timer.
+    pub(super) timer: Option<IntervalHandle>,
+
+    /// `pagehide` listener announcing the resignation of the leader.
+    pub(super) pagehide: Option<WindowListenerHandle>,
+}
+
+impl<T: WebSocketClient, C: TabChannel> SharedState<T, C> {
+    /// Creates the state of a tab that just joined and starts an election.
+    ///
+    /// # Arguments
+    ///
+    /// * `tab` - Random identifier of this tab
+    /// * `channel` - Channel shared with the other tabs
+    /// * `now` - Current timestamp, in milliseconds
+    pub(super) fn new(tab: u64, channel: C, now: f64) -> Self
+    where
+        T: SharedWebSocketClient,
+    {
+        let mut state = Self {
+            tab,
+            channel,
+            role: Role::Follower,
+            last_heartbeat: now,
+            leader_connected: false,
+            pending_connect: false,
+            codec: Codec {
+                encode_request: T::encode_request,
+                decode_request: T::decode_request,
+                encode_response: T::encode_response,
+                decode_response: T::decode_response,
+            },
+            timer: None,
+            pagehide: None,
+        };
+
+        // An existing leader answers the claim with a heartbeat
+        state.start_election(now);
+
+        state
+    }
+
+    /// Whether this tab holds the real connection.
+    pub(super) fn is_leader(&self) -> bool {
+        self.role == Role::Leader
+    }
+
+    /// Forwards an operation of a follower to the leader.
+    ///
+    /// # Returns
+    ///
+    /// * `false` - This tab is the leader and performs the operation itself
+    /// * `true` - The operation was forwarded (or deferred until a leader is known)
+    pub(super) fn forward(&mut self, message: TabMessage) -> bool {
+        match self.role {
+            Role::Leader => false,
+            Role::Follower => {
+                self.post(&message);
+                true
+            }
+            Role::Candidate { .. } => {
+                match message {
+                    TabMessage::Connect => self.pending_connect = true,
+                    TabMessage::Disconnect => self.pending_connect = false,
+                    _ => {}
+                }
+                true
+            }
+        }
+    }
+
+    /// Forwards a request of a follower to the leader.
+    ///
+    /// Returns `false` when this tab is the leader. Requests sent while no
+    /// leader is known are dropped.
+    pub(super) fn forward_request(&mut self, request: &T::Request) -> bool {
+        if self.is_leader() {
+            return false;
+        }
+
+        match (self.codec.encode_request)(request) {
+            Some(bytes) => self.forward(TabMessage::Request(bytes)),
+            None => {
+                leptos::logging::error!("Failed to encode request for the leader tab");
+                true
+            }
+        }
+    }
+
+    /// Broadcasts a response received by the leader to the followers.
+    pub(super) fn relay_response(&self, response: &T::Response) {
+        if !self.is_leader() {
+            return;
+        }
+
+        match (self.codec.encode_response)(response) {
+            Some(bytes) => self.post(&TabMessage::Response(bytes)),
+            None => leptos::logging::error!("Failed to encode response for follower tabs"),
+        }
+    }
+
+    /// Announces the connection state of the leader to the followers.
+    pub(super) fn announce(&mut self, connected: bool) {
+        if self.is_leader() {
+            self.leader_connected = connected;
+            self.post(&TabMessage::Heartbeat {
+                leader: self.tab,
+                connected,
+            });
+        }
+    }
+
+    /// Becomes a follower of the leader that just sent a heartbeat.
+    pub(super) fn follow(&mut self, now: f64) {
+        self.role = Role::Follower;
+        self.last_heartbeat = now;
+    }
+
+    /// Hands leadership over before this tab goes away.
+    pub(super) fn resign(&mut self, connected: bool) {
+        if self.is_leader() {
+            self.post(&TabMessage::Resign {
+                leader: self.tab,
+                connected,
+            });
+            self.role = Role::Follower;
+        }
+    }
+
+    /// Periodic step: heartbeat as leader, detect a missing leader, or end an election.
+    pub(super) fn tick(&mut self, now: f64, connected: bool) -> Option<TabAction<T>> {
+        match self.role {
+            Role::Leader => {
+                self.announce(connected);
+                None
+            }
+            Role::Follower => {
+                if now - self.last_heartbeat > LEADER_TIMEOUT {
+                    self.start_election(now);
+                }
+                None
+            }
+            Role::Candidate { since, best } => {
+                if now - since < ELECTION_WINDOW {
+                    return None;
+                }
+
+                if best == self.tab {
+                    // Read before announcing: the new leader is not connected yet
+                    let connect = self.leader_connected || self.pending_connect;
+                    self.pending_connect = false;
+
+                    self.role = Role::Leader;
+                    self.announce(false);
+
+                    Some(TabAction::Lead { connect })
+                } else {
+                    // Wait for the heartbeat of the winner, or elect again
+                    self.follow(now);
+                    None
+                }
+            }
+        }
+    }
+
+    /// Handles a message posted by another tab.
+    pub(super) fn receive(&mut self, message: TabMessage, now: f64) -> Option<TabAction<T>> {
+        match message {
+            TabMessage::Heartbeat { leader, connected } => {
+                if self.is_leader() {
+                    // Two leaders after a split election: the lowest identifier wins
+                    return (leader < self.tab).then_some(TabAction::StepDown);
+                }
+
+                self.follow(now);
+                self.leader_connected = connected;
+
+                if self.pending_connect {
+                    self.pending_connect = false;
+                    self.post(&TabMessage::Connect);
+                }
+
+                Some(TabAction::SetConnected(connected))
+            }
+            TabMessage::Claim { tab } => {
+                match self.role {
+                    // Keep the leadership: the candidate becomes a follower
+                    Role::Leader => self.announce(self.leader_connected),
+                    Role::Candidate { since, best } => {
+                        self.role = Role::Candidate {
+                            since,
+                            best: best.min(tab),
+                        }
+                    }
+                    Role::Follower => {}
+                }
+                None
+            }
+            TabMessage::Resign { connected, .. } => {
+                if self.role == Role::Follower {
+                    self.leader_connected = connected;
+                    self.start_election(now);
+                }
+                None
+            }
+            TabMessage::Connect => self.is_leader().then_some(TabAction::Connect),
+            TabMessage::Disconnect => self.is_leader().then_some(TabAction::Disconnect),
+            TabMessage::Request(bytes) => {
+                if !self.is_leader() {
+                    return None;
+                }
+
+                let request = (self.codec.decode_request)(&bytes);
+                if request.is_none() {
+                    leptos::logging::error!("Failed to decode request from a follower tab");
+                }
+                request.map(TabAction::Send)
+            }
+            TabMessage::Response(bytes) => {
+                if self.is_leader() {
+                    return None;
+                }
+
+                let response = (self.codec.decode_response)(&bytes);
+                if response.is_none() {
+                    leptos::logging::error!("Failed to decode response from the leader tab");
+                }
+                response.map(TabAction::Dispatch)
+            }
+        }
+    }
+
+    /// Stops listening and releases the channel.
+    pub(super) fn close(&mut self) {
+        if let Some(timer) = self.timer.take() {
+            timer.clear();
+        }
+        if let Some(pagehide) = self.pagehide.take() {
+            pagehide.remove();
+        }
+        self.channel.close();
+    }
+
+    /// Announces the candidacy of this tab.
+    fn start_election(&mut self, now: f64) {
+        self.role = Role::Candidate {
+            since: now,
+            best: self.tab,
+        };
+        self.post(&TabMessage::Claim { tab: self.tab });
+    }
+
+    /// Posts a message to every other tab.
+    fn post(&self, message: &TabMessage) {
+        self.channel.post(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use futures::channel::mpsc::UnboundedReceiver;
+    use leptos::server_fn::BoxedStream;
+
+    use super::*;
+    use crate::client::shared::LEADER_TIMEOUT;
+
+    /// Client exchanging single bytes, never connected.
+    #[derive(Clone)]
+    struct TestClient;
+
+    impl WebSocketClient for TestClient {
+        type Request = u8;
+        type Response = u8;
+
+        fn create_handshake_request(&self) -> u8 {
+            0
+        }
+
+        fn create_disconnect_request(&self) -> u8 {
+            1
+        }
+
+        fn handle_response(_response: u8, _is_connected: RwSignal<bool>) {}
+
+        async fn get_stream(
+            _rx: UnboundedReceiver<Result<u8, ServerFnError>>,
+        ) -> Result<BoxedStream<u8, ServerFnError>, ServerFnError> {
+            Err(ServerFnError::new("No server in tests"))
+        }
+    }
+
+    impl SharedWebSocketClient for TestClient {
+        fn encode_request(request: &u8) -> Option<Vec<u8>> {
+            Some(vec![*request])
+        }
+
+        fn decode_request(bytes: &[u8]) -> Option<u8> {
+            bytes.first().copied()
+        }
+
+        fn encode_response(response: &u8) -> Option<Vec<u8>> {
+            Some(vec![*response])
+        }
+
+        fn decode_response(bytes: &[u8]) -> Option<u8> {
+            bytes.first().copied()
+        }
+    }
+
+    /// Keeps the posted messages instead of sending them.
+    #[derive(Default)]
+    struct RecordingChannel {
+        posted: RefCell<Vec<TabMessage>>,
+    }
+
+    impl TabChannel for RecordingChannel {
+        fn post(&self, message: &TabMessage) {
+            self.posted.borrow_mut().push(message.clone());
+        }
+
+        fn close(&self) {}
+    }
+
+    type TestState = SharedState<TestClient, RecordingChannel>;
+
+    impl TestState {
+        /// Returns the messages posted since the last call.
+        fn take_posted(&self) -> Vec<TabMessage> {
+            self.channel.posted.take()
+        }
+    }
+
+    /// A tab that joined at `now`, then followed the leader `1`.
+    fn follower(tab: u64, now: f64, leader_connected: bool) -> TestState {
+        let mut state = TestState::new(tab, RecordingChannel::default(), now);
+        let action = state.receive(
+            TabMessage::Heartbeat {
+                leader: 1,
+                connected: leader_connected,
+            },
+            now,
+        );
+
+        assert!(
+            matches!(action, Some(TabAction::SetConnected(connected)) if connected == leader_connected)
+        );
+        assert_eq!(state.role, Role::Follower);
+        state.take_posted();
+
+        state
+    }
+
+    #[test]
+    fn a_lone_tab_leads_once_the_election_window_ends() {
+        let mut state = TestState::new(7, RecordingChannel::default(), 0.0);
+        assert!(matches!(
+            state.take_posted()[..],
+            [TabMessage::Claim { tab: 7 }]
+        ));
+
+        assert!(state.tick(ELECTION_WINDOW - 1.0, false).is_none());
+        assert!(!state.is_leader());
+
+        let action = state.tick(ELECTION_WINDOW, false);
+        assert!(matches!(action, Some(TabAction::Lead { connect: false })));
+        assert!(state.is_leader());
+        assert!(matches!(
+            state.take_posted()[..],
+            [TabMessage::Heartbeat {
+                leader: 7,
+                connected: false
+            }]
+        ));
+    }
+
+    #[test]
+    fn the_lowest_claim_wins_the_election() {
+        let mut state = TestState::new(7, RecordingChannel::default(), 0.0);
+        assert!(state.receive(TabMessage::Claim { tab: 3 }, 10.0).is_none());
+        assert!(state.receive(TabMessage::Claim { tab: 9 }, 20.0).is_none());
+
+        assert!(state.tick(ELECTION_WINDOW, false).is_none());
+        assert_eq!(state.role, Role::Follower);
+    }
+
+    #[test]
+    fn followers_elect_a_new_leader_and_reconnect_when_heartbeats_stop() {
+        let mut state = follower(2, 0.0, true);
+
+        // Heartbeats are late, but not missing yet
+        assert!(state.tick(LEADER_TIMEOUT, true).is_none());
+        assert_eq!(state.role, Role::Follower);
+
+        // The leader is gone: claim the leadership
+        let since = LEADER_TIMEOUT + 1.0;
+        assert!(state.tick(since, true).is_none());
+        assert!(matches!(state.role, Role::Candidate { .. }));
+        assert!(matches!(
+            state.take_posted()[..],
+            [TabMessage::Claim { tab: 2 }]
+        ));
+
+        // Promoted: the shared connection was open, so it is opened again
+        let action = state.tick(since + ELECTION_WINDOW, false);
+        assert!(matches!(action, Some(TabAction::Lead { connect: true })));
+        assert!(state.is_leader());
+
+        // Announced as not connected until the new connection is up
+        assert!(matches!(
+            state.take_posted()[..],
+            [TabMessage::Heartbeat {
+                leader: 2,
+                connected: false
+            }]
+        ));
+    }
+
+    #[test]
+    fn a_closed_connection_stays_closed_after_a_leadership_change() {
+        let mut state = follower(2, 0.0, false);
+
+        let since = LEADER_TIMEOUT + 1.0;
+        state.tick(since, false);
+        let action = state.tick(since + ELECTION_WINDOW, false);
+
+        assert!(matches!(action, Some(TabAction::Lead { connect: false })));
+    }
+
+    #[test]
+    fn followers_take_over_right_away_when_the_leader_resigns() {
+        let mut state = follower(2, 0.0, false);
+
+        let resigned = state.receive(
+            TabMessage::Resign {
+                leader: 1,
+                connected: true,
+            },
+            100.0,
+        );
+        assert!(resigned.is_none());
+        assert!(matches!(state.role, Role::Candidate { since, .. } if since == 100.0));
+
+        let action = state.tick(100.0 + ELECTION_WINDOW, false);
+        assert!(matches!(action, Some(TabAction::Lead { connect: true })));
+    }
+
+    #[test]
+    fn a_connect_during_the_election_is_kept_for_the_winner() {
+        let mut state = TestState::new(7, RecordingChannel::default(), 0.0);
+
+        // No leader to forward to yet
+        assert!(state.forward(TabMessage::Connect));
+
+        let action = state.tick(ELECTION_WINDOW, false);
+        assert!(matches!(action, Some(TabAction::Lead { connect: true })));
+    }
+
+    #[test]
+    fn the_leader_with_the_highest_identifier_steps_down() {
+        let mut state = TestState::new(7, RecordingChannel::default(), 0.0);
+        state.tick(ELECTION_WINDOW, false);
+        assert!(state.is_leader());
+
+        let higher = state.receive(
+            TabMessage::Heartbeat {
+                leader: 9,
+                connected: true,
+            },
+            ELECTION_WINDOW,
+        );
+        assert!(higher.is_none());
+
+        let lower = state.receive(
+            TabMessage::Heartbeat {
+                leader: 3,
+                connected: true,
+            },
+            ELECTION_WINDOW,
+        );
+        assert!(matches!(lower, Some(TabAction::StepDown)));
+    }
+}
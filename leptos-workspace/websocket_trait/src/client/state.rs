@@ -0,0 +1,34 @@
+//! Connection state of a WebSocket manager.
+
+use std::fmt::Display;
+
+use super::errors::WebSocketError;
+
+/// Lifecycle state of a `GenericWebSocketManager` connection.
+///
+/// Derived from the manager's signals and exposed as `manager.state`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// No connection, and the previous one (if any) ended normally.
+    Disconnected,
+
+    /// `connect()` was called and the handshake is not acknowledged yet.
+    Connecting,
+
+    /// The handshake was acknowledged; requests can be sent.
+    Connected,
+
+    /// The last connection attempt failed (e.g. handshake timeout).
+    Failed(WebSocketError),
+}
+
+impl Display for ConnectionState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Disconnected => write!(f, "disconnected"),
+            Self::Connecting => write!(f, "connecting"),
+            Self::Connected => write!(f, "connected"),
+            Self::Failed(e) => write!(f, "failed: {e}"),
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Reactive subscriptions to incoming WebSocket responses.
+//!
+//! This module lets components observe a filtered view of the response stream
+//! without routing everything through `WebSocketClient::handle_response`.
+//! Many components can subscribe to the same manager, sharing one connection.
+//!
+//! Every subscription is bound to the reactive owner that created it and is
+//! unregistered automatically through `on_cleanup` when that owner is disposed
+//! (e.g. when the subscribing component unmounts).
+//!
+//! `request()` awaits a single reply instead: its listener lives only as long
+//! as the returned future, so dropping the future cancels the request.
+
+use std::sync::{Arc, Mutex};
+
+use futures::channel::mpsc::{self, UnboundedReceiver};
+use futures::channel::oneshot;
+use leptos::prelude::*;
+
+use super::errors::WebSocketError;
+use super::manager::GenericWebSocketManager;
+use super::timeout::with_timeout;
+use super::websocket_client::WebSocketClient;
+
+/// Callback invoked with every response received by the manager.
+///
+/// Shared so that [`Subscribers::dispatch`] can call a snapshot of the
+/// listeners outside of the registry's lock.
+type Listener<R> = Arc<dyn Fn(&R) + Send + Sync>;
+
+/// Registry of active subscriptions for a single manager.
+///
+/// Stored inside the manager and shared by every clone of it, so a
+/// subscription registered through any clone sees every response.
+pub(super) struct Subscribers<R> {
+    /// Identifier handed out to the next registered listener.
+    next_id: usize,
+
+    /// Active listeners, in registration order.
+    listeners: Vec<(usize, Listener<R>)>,
+}
+
+impl<R> Subscribers<R> {
+    /// Registers a listener and returns its identifier.
+    fn insert(&mut self, listener: Listener<R>) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.listeners.push((id, listener));
+
+        id
+    }
+
+    /// Removes the listener with the given identifier, if still registered.
+    fn remove(&mut self, id: usize) {
+        self.listeners.retain(|(listener_id, _)| *listener_id != id);
+    }
+}
+
+impl<R: 'static> Subscribers<R> {
+    /// Forwards a response to every registered listener.
+    ///
+    /// Listeners are called on a snapshot taken before the first call, with
+    /// the registry unlocked: a listener may subscribe or unsubscribe (e.g. a
+    /// `request()` completing) without deadlocking. Listeners registered
+    /// during the dispatch only see the next responses.
+    pub(super) fn dispatch(subscribers: StoredValue<Self>, response: &R) {
+        let listeners = subscribers
+            .try_with_value(|subscribers| {
+                subscribers
+                    .listeners
+                    .iter()
+                    .map(|(_, listener)| Arc::clone(listener))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        for listener in listeners {
+            listener(response);
+        }
+    }
+}
+
+impl<R> Default for Subscribers<R> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            listeners: Vec::new(),
+        }
+    }
+}
+
+impl<T: WebSocketClient> GenericWebSocketManager<T> {
+    /// Subscribes to the latest response matching a predicate.
+    ///
+    /// The returned signal starts as `None` and is updated every time a
+    /// response for which `predicate` returns `true` is received. Responses
+    /// are still passed to `WebSocketClient::handle_response` as usual.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - Selects the responses this subscription cares about
+    ///
+    /// # Returns
+    ///
+    /// A read-only signal holding the most recent matching response.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let handshake = manager.subscribe(|response| matches!(response, Response::HandshakeResponse));
+    ///
+    /// view! { <Show when=move || handshake.get().is_some()>"Connected"</Show> }
+    /// ```
+    pub fn subscribe<F>(&self, predicate: F) -> ReadSignal<Option<T::Response>>
+    where
+        T::Response: Clone + Sync,
+        F: Fn(&T::Response) -> bool + Send + Sync + 'static,
+    {
+        self.subscribe_map(move |response| predicate(response).then(|| response.clone()))
+    }
+
+    /// Subscribes to the latest value extracted from matching responses.
+    ///
+    /// Typically used to follow a single response variant and keep only its
+    /// payload: `extract` returns `Some(value)` for the variant of interest
+    /// and `None` for everything else.
+    ///
+    /// # Arguments
+    ///
+    /// * `extract` - Maps a response to the value to publish, if any
+    ///
+    /// # Returns
+    ///
+    /// A read-only signal holding the most recently extracted value.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let last_message = manager.subscribe_map(|response| match response {
+    ///     ChatResponse::Message { content } => Some(content.clone()),
+    ///     _ => None,
+    /// });
+    /// ```
+    pub fn subscribe_map<U, F>(&self, extract: F) -> ReadSignal<Option<U>>
+    where
+        U: Send + Sync + 'static,
+        F: Fn(&T::Response) -> Option<U> + Send + Sync + 'static,
+    {
+        let latest = RwSignal::new(None);
+
+        self.register(move |response| {
+            if let Some(value) = extract(response) {
+                latest.set(Some(value));
+            }
+        });
+
+        latest.read_only()
+    }
+
+    /// Returns an async stream of every response matching a predicate.
+    ///
+    /// Unlike [`subscribe`](Self::subscribe), no response is lost when
+    /// several arrive before the consumer runs. The stream ends when the
+    /// subscribing owner is cleaned up.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - Selects the responses forwarded to the stream
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut messages = manager.stream(|response| matches!(response, ChatResponse::Message { .. }));
+    ///
+    /// leptos::task::spawn_local(async move {
+    ///     while let Some(message) = messages.next().await {
+    ///         log!("{message:?}");
+    ///     }
+    /// });
+    /// ```
+    pub fn stream<F>(&self, predicate: F) -> UnboundedReceiver<T::Response>
+    where
+        T::Response: Clone,
+        F: Fn(&T::Response) -> bool + Send + Sync + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded();
+
+        self.register(move |response| {
+            if predicate(response) {
+                // The receiver may already be dropped; the listener is removed on cleanup
+                let _ = tx.unbounded_send(response.clone());
+            }
+        });
+
+        rx
+    }
+
+    /// Sends a request and waits for its reply, with a deadline.
+    ///
+    /// The reply is the first response for which `is_reply` returns `true`.
+    /// The reply is still dispatched to other subscribers and to
+    /// `WebSocketClient::handle_response` as usual. Pongs are consumed by the
+    /// manager and can not be awaited this way.
+    ///
+    /// Dropping the returned future cancels the wait.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request message to send to the server
+    /// * `is_reply` - Identifies the response answering this request
+    ///
+    /// # Errors
+    ///
+    /// * `WebSocketError::NotConnected` / `WebSocketError::Send` - The request could not be sent
+    /// * `WebSocketError::RequestTimeout` - No reply within the manager's request timeout
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let status = manager
+    ///     .request(Request::GetStatus, |response| {
+    ///         matches!(response, Response::Status { .. })
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn request<F>(
+        &self,
+        request: T::Request,
+        is_reply: F,
+    ) -> Result<T::Response, WebSocketError>
+    where
+        T::Response: Clone,
+        F: Fn(&T::Response) -> bool + Send + Sync + 'static,
+    {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        let reply_tx = Mutex::new(Some(reply_tx));
+
+        let id = self
+            .subscribers
+            .try_update_value(|subscribers| {
+                subscribers.insert(Arc::new(move |response| {
+                    if !is_reply(response) {
+                        return;
+                    }
+
+                    // Only the first matching response answers the request
+                    if let Some(reply_tx) = reply_tx.lock().ok().and_then(|mut tx| tx.take()) {
+                        let _ = reply_tx.send(response.clone());
+                    }
+                }))
+            })
+            .ok_or(WebSocketError::NotConnected)?;
+
+        // Unregistered on completion, timeout or cancellation
+        let _listener = ListenerGuard {
+            subscribers: self.subscribers,
+            id,
+        };
+
+        self.send(request)?;
+
+        match with_timeout(reply_rx, self.request_timeout).await {
+            Some(Ok(response)) => Ok(response),
+            // The manager was disposed while waiting
+            Some(Err(_)) => Err(WebSocketError::NotConnected),
+            None => Err(WebSocketError::RequestTimeout(self.request_timeout)),
+        }
+    }
+
+    /// Registers a listener and unregisters it when the current owner is cleaned up.
+    fn register(&self, listener: impl Fn(&T::Response) + Send + Sync + 'static) {
+        let subscribers = self.subscribers;
+
+        let mut id = 0;
+        subscribers.update_value(|subscribers| id = subscribers.insert(Arc::new(listener)));
+
+        on_cleanup(move || {
+            // The manager may have been disposed before the subscriber
+            subscribers.try_update_value(|subscribers| subscribers.remove(id));
+        });
+    }
+}
+
+/// Unregisters a listener when dropped.
+struct ListenerGuard<R: 'static> {
+    subscribers: StoredValue<Subscribers<R>>,
+    id: usize,
+}
+
+impl<R: 'static> Drop for ListenerGuard<R> {
+    fn drop(&mut self) {
+        let id = self.id;
+        self.subscribers
+            .try_update_value(|subscribers| subscribers.remove(id));
+    }
+}
@@ -0,0 +1,53 @@
+//! Client-side deadlines for WebSocket operations.
+//!
+//! Timers are scheduled with `setTimeout`, so deadlines work in the browser
+//! without depending on a runtime-specific timer implementation.
+
+use std::pin::pin;
+use std::time::Duration;
+
+use futures::channel::oneshot;
+use futures::future::{Either, select};
+use leptos::prelude::*;
+
+/// Default time allowed for the server to acknowledge the handshake.
+pub const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default time allowed for the server to answer a request sent with `request()`.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Awaits `future`, giving up once `duration` has elapsed.
+///
+/// # Returns
+///
+/// * `Some(output)` - The future completed in time
+/// * `None` - The deadline expired first; the future is dropped (cancelled)
+pub(super) async fn with_timeout<F: Future>(future: F, duration: Duration) -> Option<F::Output> {
+    let (expired_tx, expired_rx) = oneshot::channel::<()>();
+
+    let timer = match set_timeout_with_handle(
+        move || {
+            let _ = expired_tx.send(());
+        },
+        duration,
+    ) {
+        Ok(timer) => Some(timer),
+        Err(e) => {
+            leptos::logging::error!("Failed to start timeout: {e:?}");
+            None
+        }
+    };
+
+    let output = match select(pin!(future), expired_rx).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right((Ok(()), _)) => None,
+        // The timer could not be started: wait without deadline
+        Either::Right((Err(_), future)) => Some(future.await),
+    };
+
+    if let Some(timer) = timer {
+        timer.clear();
+    }
+
+    output
+}
@@ -0,0 +1,263 @@
+//! Transport selection: websocket, or SSE where websockets are unavailable.
+//!
+//! Endpoints generated with an `sse = ...` argument can reach their handler
+//! either over the websocket server function or over the SSE transport (see
+//! [`crate::sse`]). The transport is picked per connection from the
+//! configured [`Transport`]:
+//!
+//! - [`Transport::Auto`] tries the websocket first and falls back to SSE when
+//!   the upgrade fails. The failure is remembered, so later connections go
+//!   straight to SSE
+//! - [`Transport::WebSocket`] / [`Transport::Sse`] force one transport
+//!
+//! The manager is unaware of the transport: both deliver the same
+//! `WebSocketClient` requests and responses.
+
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use futures::channel::mpsc::{self, UnboundedReceiver};
+use futures::stream::{self, BoxStream};
+use futures::{FutureExt, StreamExt};
+use leptos::prelude::*;
+use leptos::server_fn::{BoxedStream, Decodes, Encodes};
+
+use crate::sse::{EventStream, encode_payload};
+
+/// Request channel handed to `WebSocketClient::get_stream`.
+type RequestReceiver<Req> = UnboundedReceiver<Result<Req, ServerFnError>>;
+
+/// Response stream returned by `WebSocketClient::get_stream`.
+type StreamResult<Resp> = Result<BoxedStream<Resp, ServerFnError>, ServerFnError>;
+
+/// Configured transport, as a `Transport` discriminant.
+static TRANSPORT: AtomicU8 = AtomicU8::new(Transport::Auto as u8);
+
+/// Whether a websocket upgrade already failed in this page.
+static WEBSOCKET_UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Transport used by endpoints supporting SSE.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Transport {
+    /// Websocket, falling back to SSE when the upgrade fails.
+    #[default]
+    Auto,
+
+    /// Websocket only.
+    WebSocket,
+
+    /// SSE stream for responses, server function calls for requests.
+    Sse,
+}
+
+impl FromStr for Transport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "websocket" => Ok(Self::WebSocket),
+            "sse" => Ok(Self::Sse),
+            _ => Err(format!("Unknown transport: {s}")),
+        }
+    }
+}
+
+impl Display for Transport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::WebSocket => write!(f, "websocket"),
+            Self::Sse => write!(f, "sse"),
+        }
+    }
+}
+
+/// Sets the transport of every later connection in this page.
+///
+/// Connections already open keep their transport.
+///
+/// # Example
+///
+/// ```ignore
+/// // e.g. from a `?transport=sse` query parameter
+/// set_transport(Transport::Sse);
+/// manager.connect();
+/// ```
+pub fn set_transport(transport: Transport) {
+    TRANSPORT.store(transport as u8, Ordering::Relaxed);
+}
+
+/// Returns the configured transport.
+pub fn transport() -> Transport {
+    match TRANSPORT.load(Ordering::Relaxed) {
+        1 => Transport::WebSocket,
+        2 => Transport::Sse,
+        _ => Transport::Auto,
+    }
+}
+
+// ============================================================================
+// Connection
+// ============================================================================
+
+/// Opens the response stream over the configured transport.
+///
+/// Called by generated `WebSocketClient::get_stream` implementations.
+///
+/// # Arguments
+///
+/// * `rx` - Requests from the manager, starting with the handshake
+/// * `websocket` - Opens the websocket server function
+/// * `sse` - Opens the SSE transport, e.g. with [`connect_sse`]
+pub async fn connect<Req, Resp, Ws, WsFut, Sse, SseFut>(
+    rx: RequestReceiver<Req>,
+    websocket: Ws,
+    sse: Sse,
+) -> StreamResult<Resp>
+where
+    Req: Clone + Send + 'static,
+    Resp: Send + 'static,
+    Ws: FnOnce(RequestReceiver<Req>) -> WsFut + Send,
+    WsFut: Future<Output = StreamResult<Resp>> + Send,
+    Sse: FnOnce(RequestReceiver<Req>) -> SseFut + Send,
+    SseFut: Future<Output = StreamResult<Resp>> + Send,
+{
+    match transport() {
+        Transport::WebSocket => websocket(rx).await,
+        Transport::Sse => sse(rx).await,
+        Transport::Auto if WEBSOCKET_UNAVAILABLE.load(Ordering::Relaxed) => sse(rx).await,
+        Transport::Auto => probe(rx, websocket, sse).await,
+    }
+}
+
+/// Tries the websocket with the handshake, falling back to SSE on failure.
+///
+/// The browser reports a failed upgrade as an error (or the end) of the
+/// stream, so the websocket only counts as open once the first response
+/// arrives. Until then, requests are held back so they can be replayed over
+/// SSE.
+async fn probe<Req, Resp, Ws, WsFut, Sse, SseFut>(
+    mut rx: RequestReceiver<Req>,
+    websocket: Ws,
+    sse: Sse,
+) -> StreamResult<Resp>
+where
+    Req: Clone + Send + 'static,
+    Resp: Send + 'static,
+    Ws: FnOnce(RequestReceiver<Req>) -> WsFut + Send,
+    WsFut: Future<Output = StreamResult<Resp>> + Send,
+    Sse: FnOnce(RequestReceiver<Req>) -> SseFut + Send,
+    SseFut: Future<Output = StreamResult<Resp>> + Send,
+{
+    // The manager queues the handshake before opening the stream
+    let Some(handshake) = rx.next().await else {
+        return websocket(rx).await;
+    };
+
+    let (ws_tx, ws_rx) = mpsc::unbounded();
+    let _ = ws_tx.unbounded_send(handshake.clone());
+
+    match websocket(ws_rx).await {
+        Ok(stream) => {
+            let mut stream: BoxStream<'static, Result<Resp, ServerFnError>> = stream.into();
+            match stream.next().await {
+                Some(Ok(first)) => {
+                    leptos::task::spawn_local(rx.map(Ok).forward(ws_tx).map(|_| ()));
+                    let stream = stream::once(async move { Ok(first) }).chain(stream);
+                    return Ok(stream.into());
+                }
+                Some(Err(e)) => leptos::logging::warn!("Websocket upgrade failed: {e}"),
+                None => leptos::logging::warn!("Websocket closed before the handshake response"),
+            }
+        }
+        Err(e) => leptos::logging::warn!("Websocket upgrade failed: {e}"),
+    }
+
+    WEBSOCKET_UNAVAILABLE.store(true, Ordering::Relaxed);
+    leptos::logging::warn!("Falling back to the SSE transport");
+
+    let (sse_tx, sse_rx) = mpsc::unbounded();
+    let _ = sse_tx.unbounded_send(handshake);
+    leptos::task::spawn_local(rx.map(Ok).forward(sse_tx).map(|_| ()));
+
+    sse(sse_rx).await
+}
+
+/// Connects over the SSE transport.
+///
+/// Opens the event stream, reads its session identifier, then posts every
+/// request to that session in order while decoding the streamed responses.
+///
+/// # Arguments
+///
+/// * `rx` - Requests from the manager
+/// * `open` - Calls the endpoint's `open` server function
+/// * `post` - Calls the endpoint's `send` server function with the session
+///   identifier and the encoded request
+///
+/// # Type Parameters
+///
+/// * `Enc` - Codec of the endpoint (the websocket encoding)
+pub async fn connect_sse<Req, Resp, Enc, Open, OpenFut, Post, PostFut>(
+    mut rx: RequestReceiver<Req>,
+    open: Open,
+    post: Post,
+) -> StreamResult<Resp>
+where
+    Req: Send + 'static,
+    Resp: Send + 'static,
+    Enc: Encodes<Req> + Decodes<Resp> + 'static,
+    Open: FnOnce() -> OpenFut + Send,
+    OpenFut: Future<Output = Result<EventStream, ServerFnError>> + Send,
+    Post: Fn(String, String) -> PostFut + Send + 'static,
+    PostFut: Future<Output = Result<(), ServerFnError>>,
+{
+    let mut events = open().await?;
+
+    let session = match events.next().await {
+        Some(Ok(id)) => match String::from_utf8(id.to_vec()) {
+            Ok(id) => id,
+            Err(e) => return Err(ServerFnError::Deserialization(e.to_string())),
+        },
+        Some(Err(e)) => return Err(e),
+        None => {
+            return Err(ServerFnError::Response(
+                "SSE stream closed before the session started".into(),
+            ));
+        }
+    };
+
+    // Sequential posts keep the requests in order
+    leptos::task::spawn_local(async move {
+        while let Some(request) = rx.next().await {
+            let Ok(request) = request else {
+                continue;
+            };
+            let payload = match Enc::encode(&request) {
+                Ok(bytes) => encode_payload(&bytes),
+                Err(e) => {
+                    leptos::logging::error!("Failed to encode SSE request: {e}");
+                    continue;
+                }
+            };
+            if let Err(e) = post(session.clone(), payload).await {
+                leptos::logging::error!("Failed to send SSE request: {e}");
+            }
+        }
+    });
+
+    let responses = events.map(|event| {
+        event.and_then(|bytes| {
+            Enc::decode(bytes).map_err(|e| ServerFnError::Deserialization(e.to_string()))
+        })
+    });
+
+    Ok(responses.into())
+}
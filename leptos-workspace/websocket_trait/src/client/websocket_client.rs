@@ -0,0 +1,170 @@
+//! Client-side WebSocket trait.
+//!
+//! This module defines the `WebSocketClient` trait that must be implemented
+//! by any client-side WebSocket endpoint. It describes the messages exchanged
+//! with the server and how incoming responses are handled.
+
+use leptos::prelude::*;
+use leptos::server_fn::BoxedStream;
+
+use super::manager::GenericWebSocketManager;
+
+// ============================================================================
+// WebSocketClient Trait
+// ============================================================================
+
+/// Trait for client-side WebSocket message handling.
+///
+/// Implement this trait to define client-side WebSocket behavior for different
+/// message types. This trait abstracts the WebSocket lifecycle (connect, send,
+/// receive, disconnect) while allowing custom message handling logic.
+///
+/// # Type Parameters
+///
+/// * `Request` - Messages sent from client to server
+/// * `Response` - Messages received from server to client
+///
+/// Implementations must be `Send + Sync` so the manager can live in Leptos
+/// context and be torn down from cleanup callbacks.
+///
+/// # Required Methods
+///
+/// * `create_handshake_request` - Initial connection message
+/// * `create_disconnect_request` - Graceful disconnection message
+/// * `handle_response` - Process incoming server responses
+/// * `get_stream` - Establish WebSocket connection
+///
+/// # Provided Methods
+///
+/// * `create_ping_request` / `pong_id` - Opt into round-trip latency measurement
+/// * `is_upgrade_required` - Recognize the server rejecting an outdated client
+pub trait WebSocketClient: Clone + Send + Sync + 'static {
+    /// Request type sent to server.
+    ///
+    /// Must implement Send for cross-thread safety in async contexts.
+    type Request: Send + 'static;
+
+    /// Response type received from server.
+    ///
+    /// Must implement Send for cross-thread safety in async contexts.
+    type Response: Send + 'static;
+
+    /// Create a new WebSocket manager instance from this client.
+    ///
+    /// This is a convenience method that wraps the client in a
+    /// GenericWebSocketManager for easier instantiation.
+    ///
+    /// # Returns
+    ///
+    /// A new WebSocket manager configured with this client implementation.
+    fn create_manager(self) -> GenericWebSocketManager<Self> {
+        GenericWebSocketManager::new_with_client(self)
+    }
+
+    /// Create a handshake request to establish the WebSocket connection.
+    ///
+    /// This is called automatically when `connect()` is invoked. Typically
+    /// includes authentication data, session IDs, or other initialization info.
+    ///
+    /// # Returns
+    ///
+    /// The initial request message sent to the server.
+    fn create_handshake_request(&self) -> Self::Request;
+
+    /// Create a disconnect request for graceful connection closure.
+    ///
+    /// This is called when `disconnect()` is invoked. Allows the server to
+    /// clean up resources (e.g., remove from active connections, log session end).
+    ///
+    /// # Returns
+    ///
+    /// The disconnection request message sent to the server.
+    fn create_disconnect_request(&self) -> Self::Request;
+
+    /// Handle an incoming response from the server.
+    ///
+    /// This method is called for each message received from the server.
+    /// Implement custom logic to process responses and update UI state.
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - The response message to handle
+    /// * `is_connected` - Reactive signal to update connection state
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// fn handle_response(response: Response, is_connected: RwSignal<bool>) {
+    ///     match response {
+    ///         Response::Pong => {
+    ///             is_connected.set(true);
+    ///             log!("Connection confirmed");
+    ///         }
+    ///         Response::Error(msg) => {
+    ///             log!("Error: {}", msg);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn handle_response(response: Self::Response, is_connected: RwSignal<bool>);
+
+    /// Create a ping request used to measure round-trip latency.
+    ///
+    /// Called periodically by the manager once the handshake completed. The
+    /// server is expected to answer with a pong carrying the same `id`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(request)` - The ping message sent to the server
+    /// * `None` - Latency measurement is not supported (default)
+    fn create_ping_request(&self, _id: u32) -> Option<Self::Request> {
+        None
+    }
+
+    /// Extract the ping identifier from a pong response.
+    ///
+    /// Pong responses are consumed by the manager to update its `rtt` signal;
+    /// they are neither dispatched to subscribers nor to `handle_response`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(id)` - `response` is a pong answering the ping `id`
+    /// * `None` - Any other response (default)
+    fn pong_id(_response: &Self::Response) -> Option<u32> {
+        None
+    }
+
+    /// Whether a response tells this client that its protocol is outdated.
+    ///
+    /// Such a response ends the connection with
+    /// `WebSocketError::UpgradeRequired`; it is neither dispatched to
+    /// subscribers nor to `handle_response`.
+    ///
+    /// # Returns
+    ///
+    /// * `true` - The server can not serve this build, the page must be reloaded
+    /// * `false` - Any other response (default)
+    fn is_upgrade_required(_response: &Self::Response) -> bool {
+        false
+    }
+
+    /// Get the WebSocket stream from the server.
+    ///
+    /// This method calls the actual server function that establishes the
+    /// WebSocket connection. The server function should be annotated with
+    /// `#[server(protocol = Websocket<...>)]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rx` - Receiver for outgoing requests (client → server)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BoxedStream)` - Stream of incoming responses (server → client)
+    /// * `Err(ServerFnError)` - Connection establishment failed
+    fn get_stream(
+        rx: futures::channel::mpsc::UnboundedReceiver<Result<Self::Request, ServerFnError>>,
+    ) -> impl std::future::Future<
+        Output = Result<BoxedStream<Self::Response, ServerFnError>, ServerFnError>,
+    > + Send;
+}
@@ -0,0 +1,21 @@
+//! Compression envelope errors.
+
+use thiserror::Error;
+
+/// Errors decoding a `Compressed` message.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CompressionError {
+    // ====== Envelope =====
+    #[error("Empty message")]
+    Empty,
+
+    #[error("Unknown compression method: {0}")]
+    UnknownMethod(u8),
+
+    #[error("Failed to decompress message: {0}")]
+    Decompress(String),
+
+    // ====== Inner encoding =====
+    #[error("{0}")]
+    Decode(String),
+}
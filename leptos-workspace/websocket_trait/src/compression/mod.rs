@@ -0,0 +1,227 @@
+//! Compression envelope for large messages.
+//!
+//! [`Compressed`] wraps a `server_fn` encoding: messages whose encoded size
+//! reaches the threshold are deflated, smaller ones are sent as is to save
+//! CPU. Every message starts with a one-byte header naming the method:
+//!
+//! ```text
+//! 0x00 <encoded message>            // Below the threshold, or incompressible
+//! 0x01 <deflated encoded message>
+//! ```
+//!
+//! Deflate is implemented in pure Rust (`miniz_oxide`), so the same codec
+//! runs on the server, in the browser (wasm) and in native clients.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Compress messages of 1 KiB and more
+//! #[websocket_protocol(encoding = Compressed<RkyvEncoding>, ...)]
+//!
+//! // Custom threshold, in bytes
+//! #[websocket_protocol(encoding = Compressed<RkyvEncoding, 4096>, ...)]
+//! ```
+
+mod errors;
+mod stats;
+
+use std::marker::PhantomData;
+
+use leptos::server_fn::{Bytes, ContentType, Decodes, Encodes, Format, FormatType};
+use miniz_oxide::deflate::compress_to_vec;
+use miniz_oxide::inflate::decompress_to_vec_with_limit;
+
+pub use errors::CompressionError;
+pub use stats::CompressionStats;
+
+/// Encoded size (bytes) from which messages are compressed by default.
+pub const DEFAULT_COMPRESSION_THRESHOLD: usize = 1024;
+
+/// Deflate level, trading ratio for speed (0-10).
+const COMPRESSION_LEVEL: u8 = 6;
+
+/// Largest decompressed message accepted, guarding against decompression bombs.
+const MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+/// Header of a message sent as is.
+const RAW: u8 = 0;
+
+/// Header of a deflated message.
+const DEFLATE: u8 = 1;
+
+/// Encoding compressing the messages of `Enc` from `THRESHOLD` bytes.
+///
+/// # Type Parameters
+///
+/// * `Enc` - Inner encoding, e.g. `RkyvEncoding`
+/// * `THRESHOLD` - Encoded size (bytes) from which messages are compressed
+pub struct Compressed<Enc, const THRESHOLD: usize = DEFAULT_COMPRESSION_THRESHOLD>(
+    PhantomData<Enc>,
+);
+
+impl<Enc: ContentType, const THRESHOLD: usize> ContentType for Compressed<Enc, THRESHOLD> {
+    const CONTENT_TYPE: &'static str = Enc::CONTENT_TYPE;
+}
+
+impl<Enc, const THRESHOLD: usize> FormatType for Compressed<Enc, THRESHOLD> {
+    const FORMAT_TYPE: Format = Format::Binary;
+}
+
+impl<T, Enc, const THRESHOLD: usize> Encodes<T> for Compressed<Enc, THRESHOLD>
+where
+    Enc: Encodes<T>,
+{
+    type Error = Enc::Error;
+
+    fn encode(output: &T) -> Result<Bytes, Self::Error> {
+        let encoded = Enc::encode(output)?;
+
+        // Incompressible payloads are sent as is too
+        let deflated = (encoded.len() >= THRESHOLD)
+            .then(|| compress_to_vec(&encoded, COMPRESSION_LEVEL))
+            .filter(|deflated| deflated.len() < encoded.len());
+
+        let (method, payload) = match &deflated {
+            Some(deflated) => (DEFLATE, deflated.as_slice()),
+            None => (RAW, encoded.as_ref()),
+        };
+
+        let mut message = Vec::with_capacity(payload.len() + 1);
+        message.push(method);
+        message.extend_from_slice(payload);
+
+        stats::record(encoded.len(), message.len(), deflated.is_some());
+
+        Ok(Bytes::from(message))
+    }
+}
+
+impl<T, Enc, const THRESHOLD: usize> Decodes<T> for Compressed<Enc, THRESHOLD>
+where
+    Enc: Decodes<T>,
+{
+    type Error = CompressionError;
+
+    fn decode(bytes: Bytes) -> Result<T, Self::Error> {
+        let (&method, payload) = bytes.split_first().ok_or(CompressionError::Empty)?;
+
+        let payload = match method {
+            RAW => bytes.slice(1..),
+            DEFLATE => decompress_to_vec_with_limit(payload, MAX_DECOMPRESSED_SIZE)
+                .map(Bytes::from)
+                .map_err(|e| CompressionError::Decompress(e.to_string()))?,
+            _ => return Err(CompressionError::UnknownMethod(method)),
+        };
+
+        Enc::decode(payload).map_err(|e| CompressionError::Decode(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+
+    use leptos::server_fn::codec::RkyvEncoding;
+
+    use super::*;
+
+    /// Text of `size` bytes, compressing well.
+    fn text(size: usize) -> String {
+        "The quick brown fox jumps over the lazy dog. "
+            .chars()
+            .cycle()
+            .take(size)
+            .collect()
+    }
+
+    /// Encodes and decodes `message`, returning the encoded bytes.
+    fn roundtrip<T, const THRESHOLD: usize>(message: &T) -> Bytes
+    where
+        T: Debug + PartialEq,
+        RkyvEncoding: Encodes<T> + Decodes<T>,
+    {
+        let bytes = <Compressed<RkyvEncoding, THRESHOLD> as Encodes<T>>::encode(message).unwrap();
+        let decoded =
+            <Compressed<RkyvEncoding, THRESHOLD> as Decodes<T>>::decode(bytes.clone()).unwrap();
+
+        assert_eq!(&decoded, message);
+        bytes
+    }
+
+    #[test]
+    fn messages_below_the_threshold_are_sent_as_is() {
+        let message = text(100);
+        let bytes = roundtrip::<_, DEFAULT_COMPRESSION_THRESHOLD>(&message);
+
+        assert_eq!(bytes[0], RAW);
+        assert_eq!(bytes.slice(1..), RkyvEncoding::encode(&message).unwrap());
+    }
+
+    #[test]
+    fn messages_above_the_threshold_are_deflated() {
+        let message = text(4096);
+        let bytes = roundtrip::<_, DEFAULT_COMPRESSION_THRESHOLD>(&message);
+
+        assert_eq!(bytes[0], DEFLATE);
+        assert!(bytes.len() < message.len() / 4, "{} bytes", bytes.len());
+    }
+
+    #[test]
+    fn threshold_applies_to_the_encoded_size() {
+        for size in 1000..1050 {
+            let message = text(size);
+            let encoded = RkyvEncoding::encode(&message).unwrap();
+            let bytes = roundtrip::<_, DEFAULT_COMPRESSION_THRESHOLD>(&message);
+
+            let deflated = bytes[0] == DEFLATE;
+            assert_eq!(
+                deflated,
+                encoded.len() >= DEFAULT_COMPRESSION_THRESHOLD,
+                "{} encoded bytes",
+                encoded.len()
+            );
+        }
+    }
+
+    #[test]
+    fn custom_threshold() {
+        let message = text(300);
+
+        assert_eq!(roundtrip::<_, 256>(&message)[0], DEFLATE);
+        assert_eq!(roundtrip::<_, 4096>(&message)[0], RAW);
+    }
+
+    #[test]
+    fn incompressible_messages_are_sent_as_is() {
+        // Pseudo-random bytes (xorshift), which deflate can not shrink
+        let mut state = 0x2545_f491_u32;
+        let message = (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect::<Vec<u8>>();
+
+        let bytes = roundtrip::<_, DEFAULT_COMPRESSION_THRESHOLD>(&message);
+        assert_eq!(bytes[0], RAW);
+    }
+
+    #[test]
+    fn malformed_messages_are_rejected() {
+        let decode = |bytes: &'static [u8]| {
+            <Compressed<RkyvEncoding> as Decodes<String>>::decode(Bytes::from_static(bytes))
+        };
+
+        assert!(matches!(decode(&[]), Err(CompressionError::Empty)));
+        assert!(matches!(
+            decode(&[7, 0, 0]),
+            Err(CompressionError::UnknownMethod(7))
+        ));
+        assert!(matches!(
+            decode(&[DEFLATE, 0xff, 0xff]),
+            Err(CompressionError::Decompress(_))
+        ));
+    }
+}
@@ -0,0 +1,69 @@
+//! Counters of the bytes saved by compression.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static MESSAGES: AtomicU64 = AtomicU64::new(0);
+static COMPRESSED: AtomicU64 = AtomicU64::new(0);
+static ORIGINAL_BYTES: AtomicU64 = AtomicU64::new(0);
+static ENCODED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Totals of the messages encoded by `Compressed` in this process (or page).
+///
+/// # Example
+///
+/// ```ignore
+/// let before = CompressionStats::current();
+/// // ... exchange messages ...
+/// let stats = CompressionStats::current().since(&before);
+/// println!("Saved {} bytes", stats.bytes_saved());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompressionStats {
+    /// Messages encoded.
+    pub messages: u64,
+
+    /// Messages sent compressed (above the threshold, and smaller compressed).
+    pub compressed: u64,
+
+    /// Size of the messages as encoded by the inner encoding.
+    pub original_bytes: u64,
+
+    /// Size of the messages on the wire, envelope included.
+    pub encoded_bytes: u64,
+}
+
+impl CompressionStats {
+    /// Returns the totals so far.
+    pub fn current() -> Self {
+        Self {
+            messages: MESSAGES.load(Ordering::Relaxed),
+            compressed: COMPRESSED.load(Ordering::Relaxed),
+            original_bytes: ORIGINAL_BYTES.load(Ordering::Relaxed),
+            encoded_bytes: ENCODED_BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the totals accumulated since `earlier`.
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            messages: self.messages - earlier.messages,
+            compressed: self.compressed - earlier.compressed,
+            original_bytes: self.original_bytes - earlier.original_bytes,
+            encoded_bytes: self.encoded_bytes - earlier.encoded_bytes,
+        }
+    }
+
+    /// Bytes saved on the wire; negative when the one-byte envelope of
+    /// uncompressed messages outweighs the savings.
+    pub fn bytes_saved(&self) -> i64 {
+        self.original_bytes as i64 - self.encoded_bytes as i64
+    }
+}
+
+/// Counts one encoded message.
+pub(super) fn record(original: usize, encoded: usize, compressed: bool) {
+    MESSAGES.fetch_add(1, Ordering::Relaxed);
+    COMPRESSED.fetch_add(u64::from(compressed), Ordering::Relaxed);
+    ORIGINAL_BYTES.fetch_add(original as u64, Ordering::Relaxed);
+    ENCODED_BYTES.fetch_add(encoded as u64, Ordering::Relaxed);
+}
@@ -1,4 +1,23 @@
 pub mod client;
+pub mod protocol;
+pub mod sse;
+
+#[cfg(feature = "compression")]
+pub mod compression;
 
 #[cfg(feature = "ssr")]
 pub mod server;
+
+#[cfg(feature = "native")]
+pub mod native;
+
+pub use websocket_macro::{WebSocketSchema, websocket_protocol};
+
+/// Dependencies referenced by `#[websocket_protocol]` expansions.
+#[doc(hidden)]
+pub mod __private {
+    pub use futures;
+
+    #[cfg(feature = "ssr")]
+    pub use tokio;
+}
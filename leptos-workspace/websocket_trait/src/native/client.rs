@@ -0,0 +1,173 @@
+//! Native WebSocket client speaking the server fn `Websocket` protocol.
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use futures::{SinkExt, StreamExt};
+use leptos::prelude::ServerFnError;
+use leptos::server_fn::codec::RkyvEncoding;
+use leptos::server_fn::error::FromServerFnError;
+use leptos::server_fn::{Bytes, Decodes, Encodes};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use super::errors::NativeClientError;
+
+/// Frame tag of a successfully encoded message.
+const OK_TAG: u8 = 0;
+
+/// Frame tag of an encoded `ServerFnError`.
+const ERR_TAG: u8 = 1;
+
+/// Connection to a websocket server function from native code.
+///
+/// # Type Parameters
+///
+/// * `Req` - Messages sent to the server (the server fn's input stream item)
+/// * `Resp` - Messages received from the server (the server fn's output stream item)
+/// * `Enc` - Codec of the server fn protocol, `RkyvEncoding` for `Websocket<RkyvEncoding, RkyvEncoding>`
+pub struct NativeWebSocketClient<Req, Resp, Enc = RkyvEncoding> {
+    /// Underlying WebSocket connection.
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+
+    /// Message types, not stored.
+    _messages: PhantomData<fn(Req) -> (Resp, Enc)>,
+}
+
+impl<Req, Resp, Enc> NativeWebSocketClient<Req, Resp, Enc>
+where
+    Enc: Encodes<Req> + Decodes<Resp>,
+{
+    /// Opens a connection to a websocket server function.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_url` - Base URL of the server (`http://`, `https://`, `ws://` or `wss://`)
+    /// * `path` - Path of the server function (its `ServerFn::PATH`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `NativeClientError::WebSocket` if the connection or upgrade fails.
+    pub async fn connect(server_url: &str, path: &str) -> Result<Self, NativeClientError> {
+        let url = websocket_url(server_url, path);
+        let (stream, _) = tokio_tungstenite::connect_async(url.as_str()).await?;
+
+        Ok(Self {
+            stream,
+            _messages: PhantomData,
+        })
+    }
+
+    /// Sends a request to the server.
+    ///
+    /// # Errors
+    ///
+    /// * `NativeClientError::Encode` - The request could not be encoded
+    /// * `NativeClientError::WebSocket` - The connection is closed or broken
+    pub async fn send(&mut self, request: &Req) -> Result<(), NativeClientError> {
+        let bytes = Enc::encode(request).map_err(|e| NativeClientError::Encode(e.to_string()))?;
+
+        let mut frame = Vec::with_capacity(1 + bytes.len());
+        frame.push(OK_TAG);
+        frame.extend_from_slice(&bytes);
+
+        self.stream.send(Message::Binary(frame.into())).await?;
+
+        Ok(())
+    }
+
+    /// Waits for the next response from the server.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Ok(response))` - A response was received
+    /// * `Some(Err(error))` - The server sent an error, or the frame could not be decoded
+    /// * `None` - The server closed the connection
+    pub async fn recv(&mut self) -> Option<Result<Resp, NativeClientError>> {
+        loop {
+            let frame = match self.stream.next().await? {
+                Ok(Message::Binary(frame)) => frame,
+                Ok(Message::Text(text)) => Bytes::from(text),
+                Ok(Message::Close(_)) => return None,
+                // Ping/pong frames are answered by tungstenite
+                Ok(_) => continue,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            return Some(decode_frame::<Resp, Enc>(frame));
+        }
+    }
+
+    /// Closes the connection gracefully.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NativeClientError::WebSocket` if the close handshake fails.
+    pub async fn close(mut self) -> Result<(), NativeClientError> {
+        self.stream.close(None).await?;
+
+        Ok(())
+    }
+}
+
+impl<Req, Resp, Enc> Debug for NativeWebSocketClient<Req, Resp, Enc> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NativeWebSocketClient")
+            .field("stream", &self.stream)
+            .finish()
+    }
+}
+
+/// Builds the WebSocket URL of a server function, as the browser client does.
+fn websocket_url(server_url: &str, path: &str) -> String {
+    let server_url = server_url.trim_end_matches('/');
+
+    let server_url = if let Some(postfix) = server_url.strip_prefix("http://") {
+        format!("ws://{postfix}")
+    } else if let Some(postfix) = server_url.strip_prefix("https://") {
+        format!("wss://{postfix}")
+    } else {
+        server_url.to_string()
+    };
+
+    format!("{server_url}{path}")
+}
+
+/// Decodes a `[tag][content]` frame sent by the server.
+fn decode_frame<Resp, Enc: Decodes<Resp>>(frame: Bytes) -> Result<Resp, NativeClientError> {
+    match frame.first() {
+        Some(&OK_TAG) => {
+            Enc::decode(frame.slice(1..)).map_err(|e| NativeClientError::Decode(e.to_string()))
+        }
+        Some(&ERR_TAG) => Err(NativeClientError::Server(ServerFnError::de(
+            frame.slice(1..),
+        ))),
+        _ => Err(NativeClientError::Decode("Invalid frame tag".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn http_urls_become_websocket_urls() {
+        assert_eq!(
+            websocket_url("http://127.0.0.1:3000", "/api/ws"),
+            "ws://127.0.0.1:3000/api/ws"
+        );
+        assert_eq!(
+            websocket_url("https://example.com/", "/api/ws"),
+            "wss://example.com/api/ws"
+        );
+    }
+
+    #[test]
+    fn websocket_urls_are_kept() {
+        assert_eq!(
+            websocket_url("wss://example.com", "/api/ws"),
+            "wss://example.com/api/ws"
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use leptos::prelude::ServerFnError;
+use thiserror::Error;
+use tokio_tungstenite::tungstenite;
+
+#[derive(Debug, Error)]
+pub enum NativeClientError {
+    // Boxed: `tungstenite::Error` is large
+    #[error("WebSocket: {0}")]
+    WebSocket(Box<tungstenite::Error>),
+
+    // ====== Codec =====
+    #[error("Encode: {0}")]
+    Encode(String),
+
+    #[error("Decode: {0}")]
+    Decode(String),
+
+    // ====== Server =====
+    #[error("Server: {0}")]
+    Server(ServerFnError),
+}
+
+impl From<tungstenite::Error> for NativeClientError {
+    fn from(error: tungstenite::Error) -> Self {
+        Self::WebSocket(Box::new(error))
+    }
+}
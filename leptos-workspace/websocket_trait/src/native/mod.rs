@@ -0,0 +1,37 @@
+//! Native (non-wasm) client for websocket server functions.
+//!
+//! `crate::client` relies on the Leptos reactive runtime in the browser. This
+//! module talks to the same `Websocket` server functions from plain Rust on
+//! tokio, so integration tests, CLI tools and load generators can reach a
+//! running server without a browser.
+//!
+//! # Wire Format
+//!
+//! Same framing as the server fn `Websocket` protocol: every binary frame is
+//! one tag byte (`0` = `Ok`, `1` = `Err`) followed by the message encoded with
+//! the protocol's codec (`RkyvEncoding` by default), or by the encoded
+//! `ServerFnError` for `Err`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use app::pages::home::ws::RKYV_WEBSOCKET_PATH;
+//! use app::pages::home::ws::message::{Request, Response};
+//! use websocket_trait::native::NativeWebSocketClient;
+//! use websocket_trait::protocol::VersionedProtocol;
+//!
+//! let mut client =
+//!     NativeWebSocketClient::<Request, Response>::connect("http://127.0.0.1:3000", RKYV_WEBSOCKET_PATH)
+//!         .await?;
+//!
+//! client.send(&Request::Handshake { uuid, version: Request::VERSION }).await?;
+//! assert!(matches!(client.recv().await, Some(Ok(Response::HandshakeResponse))));
+//!
+//! client.close().await?;
+//! ```
+
+mod client;
+mod errors;
+
+pub use client::NativeWebSocketClient;
+pub use errors::NativeClientError;
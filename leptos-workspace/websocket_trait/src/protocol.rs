@@ -0,0 +1,57 @@
+//! Protocol versioning shared by clients and servers.
+//!
+//! After a deploy, tabs still running the previous bundle may send messages
+//! the new server can no longer decode. To detect this, the handshake carries
+//! a protocol version derived from the message types:
+//!
+//! - [`WebSocketSchema`] - Hash of a message type's shape (`#[derive(WebSocketSchema)]`)
+//! - [`VersionedProtocol`] - Version carried by the handshake and the
+//!   "upgrade required" reply sent to outdated clients
+//!
+//! The server checks the version with
+//! `GenericWebsocketBackend::with_version_check()`, and the client manager
+//! reports `WebSocketError::UpgradeRequired` so the page can offer a reload.
+
+/// Hash of a message type's shape.
+///
+/// Derived from the type's name, variants, field names and field types as
+/// written. Types referenced by fields only contribute their name: changing
+/// their layout does not change the hash.
+pub trait WebSocketSchema {
+    /// Shape hash, identical on client and server built from the same sources.
+    const SCHEMA_HASH: u64;
+}
+
+/// Version negotiation for a request type.
+///
+/// Generated by `#[websocket_protocol]` for requests whose handshake variant
+/// has a `#[websocket(version)]` field.
+pub trait VersionedProtocol {
+    /// Response type of the endpoint.
+    type Response;
+
+    /// Protocol version of this build.
+    const VERSION: u64;
+
+    /// Version carried by the request if it is a handshake, `None` otherwise.
+    fn handshake_version(&self) -> Option<u64>;
+
+    /// Response telling an outdated client to reload.
+    fn upgrade_required() -> Self::Response;
+
+    /// Whether this server can serve clients speaking `version`.
+    ///
+    /// Defaults to an exact match with [`VERSION`](Self::VERSION).
+    fn supports_version(version: u64) -> bool {
+        version == Self::VERSION
+    }
+}
+
+/// Combines the schema hashes of a request and a response type into a protocol version.
+pub const fn protocol_version<Req, Resp>() -> u64
+where
+    Req: WebSocketSchema,
+    Resp: WebSocketSchema,
+{
+    Req::SCHEMA_HASH ^ Resp::SCHEMA_HASH.rotate_left(32)
+}
@@ -3,12 +3,27 @@
 //! This module provides the `GenericWebsocketBackend` struct that handles
 //! the server-side WebSocket connection lifecycle and event loop.
 
+use std::fmt::Debug;
+use std::path::Path;
+
 use futures::StreamExt;
 use futures::channel::mpsc::UnboundedSender;
 use leptos::prelude::ServerFnError;
-use leptos::server_fn::BoxedStream;
+use leptos::server_fn::{BoxedStream, Encodes};
 
 use super::message::WebSocketMessage;
+use super::recorder::{self, RECORD_DIR_ENV, Recorder};
+use super::response_sender::ResponseSender;
+use crate::protocol::VersionedProtocol;
+
+/// Protocol version check enabled by `with_version_check()`.
+struct VersionCheck<Req, Resp> {
+    /// Whether a request is a handshake from a client this server can not serve.
+    is_outdated: fn(&Req) -> bool,
+
+    /// Builds the reply sent to outdated clients.
+    upgrade_required: fn() -> Resp,
+}
 
 /// Generic WebSocket backend that works with any message type.
 ///
@@ -64,6 +79,12 @@ pub struct GenericWebsocketBackend<T: WebSocketMessage> {
     ///
     /// This handler processes all incoming requests and generates responses.
     handler: T,
+
+    /// Rejects outdated clients, set by `with_version_check()`.
+    version_check: Option<VersionCheck<T::Request, T::Response>>,
+
+    /// Session log, set by `with_recorder()`.
+    recorder: Option<Recorder<T::Request, T::Response>>,
 }
 
 impl<T: WebSocketMessage> GenericWebsocketBackend<T> {
@@ -97,7 +118,86 @@ impl<T: WebSocketMessage> GenericWebsocketBackend<T> {
         tx: UnboundedSender<Result<T::Response, ServerFnError>>,
         handler: T,
     ) -> Self {
-        Self { input, tx, handler }
+        Self {
+            input,
+            tx,
+            handler,
+            version_check: None,
+            recorder: None,
+        }
+    }
+
+    /// Rejects clients built with another protocol version.
+    ///
+    /// A handshake carrying an unsupported version, or a message that can
+    /// not be decoded, is answered with
+    /// `VersionedProtocol::upgrade_required()` and the connection is closed.
+    /// The request is never passed to the handler.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let backend = GenericWebsocketBackend::new(input, tx, MyHandler).with_version_check();
+    /// ```
+    pub fn with_version_check(mut self) -> Self
+    where
+        T::Request: VersionedProtocol<Response = T::Response>,
+    {
+        self.version_check = Some(VersionCheck {
+            is_outdated: |request| {
+                request
+                    .handshake_version()
+                    .is_some_and(|version| !T::Request::supports_version(version))
+            },
+            upgrade_required: T::Request::upgrade_required,
+        });
+        self
+    }
+
+    /// Records the connection to a session log in `dir`.
+    ///
+    /// Every decoded request and response is written with a timestamp, in
+    /// readable (`Debug`) form, to a new file per connection. Requests are
+    /// also stored encoded with `Enc`, so `replay()` can feed them back into
+    /// a handler. Failing to create the log only disables recording.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let backend = GenericWebsocketBackend::new(input, tx, MyHandler)
+    ///     .with_recorder::<RkyvEncoding>("recordings");
+    /// ```
+    pub fn with_recorder<Enc>(mut self, dir: impl AsRef<Path>) -> Self
+    where
+        T::Request: Debug,
+        T::Response: Debug,
+        Enc: Encodes<T::Request>,
+    {
+        match Recorder::create::<Enc>(dir.as_ref(), &mut self.tx) {
+            Ok(recorder) => {
+                tracing::info!(
+                    "Recording websocket session to {}",
+                    recorder.path().display()
+                );
+                self.recorder = Some(recorder);
+            }
+            Err(e) => tracing::warn!("Failed to start websocket recording: {e}"),
+        }
+        self
+    }
+
+    /// Records the connection when the `WEBSOCKET_RECORD_DIR` environment
+    /// variable names a directory, see `with_recorder()`.
+    pub fn with_recorder_from_env<Enc>(self) -> Self
+    where
+        T::Request: Debug,
+        T::Response: Debug,
+        Enc: Encodes<T::Request>,
+    {
+        match std::env::var_os(RECORD_DIR_ENV) {
+            Some(dir) => self.with_recorder::<Enc>(dir),
+            None => self,
+        }
     }
 
     /// Starts the WebSocket message processing loop.
@@ -142,8 +242,20 @@ impl<T: WebSocketMessage> GenericWebsocketBackend<T> {
                         break;
                     }
                 }
+
+                // Responses pass through the recorder when recording
+                Some(response) = recorder::next_response(&mut self.recorder) => {
+                    if let Some(recorder) = &mut self.recorder {
+                        recorder.forward(response);
+                    }
+                }
             }
         }
+
+        // Deliver (and record) the responses sent right before closing
+        if let Some(recorder) = &mut self.recorder {
+            recorder.finish().await;
+        }
         // Implicit cleanup: tx and input are dropped here
         // This closes the response channel and releases resources
     }
@@ -167,7 +279,9 @@ impl<T: WebSocketMessage> GenericWebsocketBackend<T> {
     ///
     /// # Error Handling
     ///
-    /// - Deserialization errors: Logged and connection closed
+    /// - Deserialization errors: Logged and connection closed (after an
+    ///   "upgrade required" reply when the version check is enabled)
+    /// - Outdated handshake: "upgrade required" reply and connection closed
     /// - Handler returns false: Connection closed gracefully
     /// - Stream ends: Connection closed (client disconnected)
     async fn handle_input_result(
@@ -177,6 +291,19 @@ impl<T: WebSocketMessage> GenericWebsocketBackend<T> {
         match input_result {
             // Successfully received and deserialized a request
             Some(Ok(request)) => {
+                if let Some(recorder) = &mut self.recorder {
+                    recorder.request(&request);
+                }
+
+                // Outdated client: ask it to reload instead of misreading its messages
+                if let Some(check) = &self.version_check
+                    && (check.is_outdated)(&request)
+                {
+                    tracing::info!("Outdated client protocol, upgrade required");
+                    self.tx.send_response((check.upgrade_required)());
+                    return false;
+                }
+
                 // Delegate to the trait implementation
                 // Returns true to continue, false to close connection
                 self.handler.handle_request(request, &self.tx).await
@@ -185,6 +312,18 @@ impl<T: WebSocketMessage> GenericWebsocketBackend<T> {
             // Error deserializing or receiving the message
             Some(Err(e)) => {
                 tracing::info!("Error receiving message: {e}");
+
+                if let Some(recorder) = &mut self.recorder {
+                    recorder.error(&e);
+                }
+
+                // Undecodable message: most likely a client built from other message types
+                if let Some(check) = &self.version_check
+                    && matches!(e, ServerFnError::Deserialization(_))
+                {
+                    self.tx.send_response((check.upgrade_required)());
+                }
+
                 // Close connection on errors (could be network issue or bad data)
                 false
             }
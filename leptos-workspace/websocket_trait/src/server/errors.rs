@@ -0,0 +1,17 @@
+//! Server-side WebSocket errors.
+
+use thiserror::Error;
+
+/// Errors returned by `replay`.
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("Io: {0}")]
+    Io(#[from] std::io::Error),
+
+    // ====== Session log =====
+    #[error("Line {line}: not a recorded event")]
+    InvalidLine { line: usize },
+
+    #[error("Line {line}: failed to decode request: {message}")]
+    Decode { line: usize, message: String },
+}
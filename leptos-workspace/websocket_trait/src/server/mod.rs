@@ -9,6 +9,9 @@
 //! - [`ResponseSender`] - Extension trait for convenient response sending
 //! - [`WebSocketMessage`] - Trait defining message handling logic
 //! - [`GenericWebsocketBackend`] - Generic server implementation
+//! - [`sse`] - Serving a backend over the SSE transport
+//! - [`replay`] - Replaying sessions recorded with
+//!   [`GenericWebsocketBackend::with_recorder`]
 //!
 //! # Example
 //!
@@ -47,9 +50,16 @@
 //! ```
 
 mod backend;
+mod errors;
 mod message;
+mod recorder;
+mod replay;
 mod response_sender;
+pub mod sse;
 
 pub use backend::GenericWebsocketBackend;
+pub use errors::ReplayError;
 pub use message::WebSocketMessage;
+pub use recorder::RECORD_DIR_ENV;
+pub use replay::{ReplayReport, replay};
 pub use response_sender::ResponseSender;
@@ -0,0 +1,347 @@
+//! Per-connection session recording.
+//!
+//! Enabled with `GenericWebsocketBackend::with_recorder()`, the recorder
+//! writes one log file per connection, with one line per event:
+//!
+//! ```text
+//! # websocket session 1760781234567-0
+//! 1760781234567 > Handshake { uuid: 67e55044-..., version: 1234 }    AAECAwQ
+//! 1760781234568 < HandshakeResponse
+//! 1760781236570 > Ping { id: 1 }    AQAAAA
+//! 1760781236571 < Pong { id: 1 }
+//! 1760781240012 ! Deserialization("...")
+//! 1760781240012 # closed
+//! ```
+//!
+//! Each line starts with a Unix timestamp in milliseconds and a marker:
+//! `>` request (its `Debug` output, a tab, then the encoded request),
+//! `<` response, `!` receive error, `#` comment. The encoded requests are
+//! what `replay` feeds back into a handler; the rest is for humans.
+//!
+//! Lines are written by a separate task, so a slow disk never stalls the
+//! connection.
+
+use std::fmt::{Debug, Display};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures::StreamExt;
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use leptos::prelude::ServerFnError;
+use leptos::server_fn::{Bytes, Encodes};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::task::JoinHandle;
+
+use crate::sse::encode_payload;
+
+/// Environment variable read by `with_recorder_from_env()`: the directory
+/// receiving the session logs.
+pub const RECORD_DIR_ENV: &str = "WEBSOCKET_RECORD_DIR";
+
+/// Distinguishes sessions starting in the same millisecond.
+static SESSION_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Channel of responses, as sent by handlers.
+type ResponseTx<Resp> = UnboundedSender<Result<Resp, ServerFnError>>;
+
+/// Records the requests and responses of one connection.
+///
+/// Sits between the handler and the client: the handler sends into a channel
+/// read by the backend, which records each response before forwarding it.
+pub(super) struct Recorder<Req, Resp> {
+    /// Lines for the writer task.
+    log: UnboundedSender<String>,
+
+    /// Task writing the session log, awaited by `finish()`.
+    writer: Option<JoinHandle<()>>,
+
+    /// Path of the session log.
+    path: PathBuf,
+
+    /// Responses sent by the handler.
+    responses: UnboundedReceiver<Result<Resp, ServerFnError>>,
+
+    /// Channel to the client.
+    client: ResponseTx<Resp>,
+
+    /// Encodes a request for replay.
+    encode_request: fn(&Req) -> Option<Bytes>,
+
+    /// Readable form of a request.
+    describe_request: fn(&Req) -> String,
+
+    /// Readable form of a response.
+    describe_response: fn(&Result<Resp, ServerFnError>) -> String,
+}
+
+impl<Req, Resp> Recorder<Req, Resp> {
+    /// Creates the session log in `dir` and interposes on `tx`.
+    ///
+    /// After this call, `tx` feeds the recorder instead of the client.
+    /// Must be called within a Tokio runtime, which runs the writer task.
+    ///
+    /// # Errors
+    ///
+    /// No Tokio runtime is running, or the directory or the log file can not
+    /// be created.
+    pub(super) fn create<Enc>(dir: &Path, tx: &mut ResponseTx<Resp>) -> io::Result<Self>
+    where
+        Req: Debug,
+        Resp: Debug,
+        Enc: Encodes<Req>,
+    {
+        let runtime = tokio::runtime::Handle::try_current().map_err(io::Error::other)?;
+        fs::create_dir_all(dir)?;
+
+        let session = format!(
+            "{}-{}",
+            timestamp(),
+            SESSION_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = dir.join(format!("{session}.log"));
+        let file = File::create(&path)?;
+
+        let (log, lines) = mpsc::unbounded();
+        let _ = log.unbounded_send(format!("# websocket session {session}\n"));
+        let writer = runtime.spawn(write_log(file, path.clone(), lines));
+
+        let (recorded_tx, responses) = mpsc::unbounded();
+        let client = std::mem::replace(tx, recorded_tx);
+
+        Ok(Self {
+            log,
+            writer: Some(writer),
+            path,
+            responses,
+            client,
+            encode_request: |request| Enc::encode(request).ok(),
+            describe_request: |request| format!("{request:?}"),
+            describe_response,
+        })
+    }
+
+    /// Returns the path of the session log.
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records a request received from the client.
+    pub(super) fn request(&mut self, request: &Req) {
+        let payload = (self.encode_request)(request)
+            .map(|bytes| encode_payload(&bytes))
+            .unwrap_or_default();
+        let description = (self.describe_request)(request);
+        self.write(LogEvent::Request {
+            description: &description,
+            payload: &payload,
+        });
+    }
+
+    /// Records an error receiving a request.
+    pub(super) fn error(&mut self, error: &ServerFnError) {
+        self.write(LogEvent::Error(&format!("{error:?}")));
+    }
+
+    /// Waits for the next response sent by the handler.
+    pub(super) async fn next_response(&mut self) -> Option<Result<Resp, ServerFnError>> {
+        self.responses.next().await
+    }
+
+    /// Records a response and forwards it to the client.
+    pub(super) fn forward(&mut self, response: Result<Resp, ServerFnError>) {
+        let description = (self.describe_response)(&response);
+        self.write(LogEvent::Response(&description));
+
+        // A closed client channel ends the connection on its own
+        let _ = self.client.unbounded_send(response);
+    }
+
+    /// Forwards the responses sent right before closing, ends the log, and
+    /// waits until it is written.
+    pub(super) async fn finish(&mut self) {
+        while let Ok(response) = self.responses.try_recv() {
+            self.forward(response);
+        }
+        self.write(LogEvent::Comment("closed"));
+
+        self.log.close_channel();
+        if let Some(writer) = self.writer.take()
+            && let Err(e) = writer.await
+        {
+            tracing::warn!("Recording to {} stopped: {e}", self.path.display());
+        }
+    }
+
+    /// Queues a timestamped line for the writer task.
+    fn write(&mut self, event: LogEvent<'_>) {
+        let line = format!("{} {event}\n", timestamp());
+
+        // A stopped writer already logged why
+        let _ = self.log.unbounded_send(line);
+    }
+}
+
+/// Writes the queued lines, flushing once the queue is drained.
+///
+/// Stops at the first failure, logging (not propagating) it.
+async fn write_log(file: File, path: PathBuf, lines: UnboundedReceiver<String>) {
+    let mut log = BufWriter::new(tokio::fs::File::from_std(file));
+    let mut batches = lines.ready_chunks(64);
+
+    while let Some(batch) = batches.next().await {
+        for line in batch {
+            if let Err(e) = log.write_all(line.as_bytes()).await {
+                tracing::warn!("Failed to record to {}: {e}", path.display());
+                return;
+            }
+        }
+
+        if let Err(e) = log.flush().await {
+            tracing::warn!("Failed to record to {}: {e}", path.display());
+            return;
+        }
+    }
+}
+
+/// Event of a session log line, after its timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum LogEvent<'a> {
+    /// `>` request: its readable form, and its encoding (empty when it can
+    /// not be encoded).
+    Request {
+        description: &'a str,
+        payload: &'a str,
+    },
+
+    /// `<` response, in its readable form.
+    Response(&'a str),
+
+    /// `!` error receiving a request.
+    Error(&'a str),
+
+    /// `#` comment.
+    Comment(&'a str),
+}
+
+impl<'a> LogEvent<'a> {
+    /// Parses a timestamped line, as written by the recorder.
+    ///
+    /// Returns `None` for a malformed line. The session header has no
+    /// timestamp and is not an event.
+    pub(super) fn parse(line: &'a str) -> Option<Self> {
+        let (timestamp, event) = line.split_once(' ')?;
+        timestamp.parse::<u128>().ok()?;
+        let (marker, text) = event.split_once(' ')?;
+
+        match marker {
+            ">" => {
+                // The readable form may contain tabs, the payload never does
+                let (description, payload) = text.rsplit_once('\t')?;
+                Some(Self::Request {
+                    description,
+                    payload,
+                })
+            }
+            "<" => Some(Self::Response(text)),
+            "!" => Some(Self::Error(text)),
+            "#" => Some(Self::Comment(text)),
+            _ => None,
+        }
+    }
+}
+
+/// Formats the event as written after the timestamp.
+impl Display for LogEvent<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Request {
+                description,
+                payload,
+            } => write!(f, "> {description}\t{payload}"),
+            Self::Response(text) => write!(f, "< {text}"),
+            Self::Error(text) => write!(f, "! {text}"),
+            Self::Comment(text) => write!(f, "# {text}"),
+        }
+    }
+}
+
+/// Waits for the next recorded response; never resolves without a recorder.
+pub(super) async fn next_response<Req, Resp>(
+    recorder: &mut Option<Recorder<Req, Resp>>,
+) -> Option<Result<Resp, ServerFnError>> {
+    match recorder {
+        Some(recorder) => recorder.next_response().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Readable form of a response, as compared by `replay`.
+pub(super) fn describe_response<Resp: Debug>(response: &Result<Resp, ServerFnError>) -> String {
+    match response {
+        Ok(response) => format!("{response:?}"),
+        Err(e) => format!("Err({e:?})"),
+    }
+}
+
+/// Milliseconds since the Unix epoch.
+fn timestamp() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(event: LogEvent<'_>) {
+        let line = format!("1760781234567 {event}");
+        assert_eq!(LogEvent::parse(&line), Some(event), "{line}");
+    }
+
+    #[test]
+    fn written_events_parse_back() {
+        roundtrip(LogEvent::Request {
+            description: "Ping { id: 1 }",
+            payload: "AQAAAA",
+        });
+        roundtrip(LogEvent::Response("Pong { id: 1 }"));
+        roundtrip(LogEvent::Error("Deserialization(\"bad data\")"));
+        roundtrip(LogEvent::Comment("closed"));
+    }
+
+    #[test]
+    fn request_description_may_contain_tabs() {
+        roundtrip(LogEvent::Request {
+            description: "Echo { text: \"a\tb\" }",
+            payload: "AAECAwQ",
+        });
+    }
+
+    #[test]
+    fn request_without_payload_parses_with_an_empty_one() {
+        assert_eq!(
+            LogEvent::parse("1760781234567 > Ping { id: 1 }\t"),
+            Some(LogEvent::Request {
+                description: "Ping { id: 1 }",
+                payload: "",
+            })
+        );
+    }
+
+    #[test]
+    fn malformed_lines_are_rejected() {
+        // Session header, no timestamp
+        assert_eq!(LogEvent::parse("# websocket session 1760781234567-0"), None);
+        // Unknown marker
+        assert_eq!(LogEvent::parse("1760781234567 ? Ping"), None);
+        // No marker
+        assert_eq!(LogEvent::parse("1760781234567"), None);
+        // Request without payload separator
+        assert_eq!(LogEvent::parse("1760781234567 > Ping { id: 1 }"), None);
+    }
+}
@@ -0,0 +1,170 @@
+//! Replaying recorded sessions.
+//!
+//! Feeds the requests of a session log (see `with_recorder()`) back into a
+//! backend, and compares the responses with the recorded ones. Useful to
+//! reproduce a reported bug, or to check that a handler change keeps the
+//! behavior of real sessions.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let report = replay::<MyHandler, RkyvEncoding>("recordings/1760781234567-0.log", |input, tx| {
+//!     GenericWebsocketBackend::new(input, tx, MyHandler::default())
+//! })
+//! .await?;
+//!
+//! if !report.is_match() {
+//!     eprintln!("{report}");
+//! }
+//! ```
+
+use std::fmt::{Debug, Display};
+use std::path::Path;
+
+use futures::channel::mpsc::{self, UnboundedSender};
+use leptos::prelude::ServerFnError;
+use leptos::server_fn::{BoxedStream, Decodes};
+
+use super::backend::GenericWebsocketBackend;
+use super::errors::ReplayError;
+use super::message::WebSocketMessage;
+use super::recorder::{LogEvent, describe_response};
+use crate::sse::decode_payload;
+
+/// Outcome of a replay: recorded and replayed responses, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Number of requests replayed.
+    pub requests: usize,
+
+    /// Responses found in the session log.
+    pub expected: Vec<String>,
+
+    /// Responses produced by the replay.
+    pub actual: Vec<String>,
+}
+
+impl ReplayReport {
+    /// Whether the replay produced exactly the recorded responses.
+    pub fn is_match(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+/// Lists the differing responses, `-` recorded and `+` replayed.
+impl Display for ReplayReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Replayed {} requests: {} recorded responses, {} replayed",
+            self.requests,
+            self.expected.len(),
+            self.actual.len()
+        )?;
+
+        let len = self.expected.len().max(self.actual.len());
+        for index in 0..len {
+            let expected = self.expected.get(index);
+            let actual = self.actual.get(index);
+            if expected == actual {
+                continue;
+            }
+
+            writeln!(f, "Response {}:", index + 1)?;
+            if let Some(expected) = expected {
+                writeln!(f, "  - {expected}")?;
+            }
+            if let Some(actual) = actual {
+                writeln!(f, "  + {actual}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Replays a session log through a fresh backend.
+///
+/// The recorded requests are sent in order, then the request stream ends,
+/// like a client disconnecting. Responses still in flight once the backend
+/// stops are not collected.
+///
+/// # Arguments
+///
+/// * `path` - Session log written by the recorder
+/// * `backend` - Builds the backend from the request stream and response
+///   sender, e.g. with the same `with_*` options as in production
+///
+/// # Type Parameters
+///
+/// * `T` - The message handler
+/// * `Enc` - Codec of the requests (the endpoint's encoding)
+///
+/// # Errors
+///
+/// * `ReplayError::Io` - The log can not be read
+/// * `ReplayError::InvalidLine` / `ReplayError::Decode` - The log is malformed,
+///   or was recorded with other message types
+pub async fn replay<T, Enc>(
+    path: impl AsRef<Path>,
+    backend: impl FnOnce(
+        BoxedStream<T::Request, ServerFnError>,
+        UnboundedSender<Result<T::Response, ServerFnError>>,
+    ) -> GenericWebsocketBackend<T>,
+) -> Result<ReplayReport, ReplayError>
+where
+    T: WebSocketMessage,
+    T::Response: Debug,
+    Enc: Decodes<T::Request>,
+{
+    let log = std::fs::read_to_string(path)?;
+
+    let (request_tx, request_rx) = mpsc::unbounded();
+    let mut requests = 0;
+    let mut expected = Vec::new();
+
+    for (index, line) in log.lines().enumerate() {
+        let line_number = index + 1;
+        let invalid = || ReplayError::InvalidLine { line: line_number };
+
+        // Session header
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+
+        match LogEvent::parse(line).ok_or_else(invalid)? {
+            LogEvent::Request { payload, .. } => {
+                let request = decode_payload(payload)
+                    .and_then(|bytes| {
+                        Enc::decode(bytes)
+                            .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+                    })
+                    .map_err(|e| ReplayError::Decode {
+                        line: line_number,
+                        message: e.to_string(),
+                    })?;
+
+                let _ = request_tx.unbounded_send(Ok(request));
+                requests += 1;
+            }
+            LogEvent::Response(text) => expected.push(text.to_string()),
+            // Receive errors and comments are not replayed
+            LogEvent::Error(_) | LogEvent::Comment(_) => {}
+        }
+    }
+    drop(request_tx);
+
+    let (tx, mut rx) = mpsc::unbounded();
+    backend(request_rx.into(), tx).serve().await;
+
+    let mut actual = Vec::new();
+    while let Ok(response) = rx.try_recv() {
+        actual.push(describe_response(&response));
+    }
+
+    Ok(ReplayReport {
+        requests,
+        expected,
+        actual,
+    })
+}
@@ -0,0 +1,184 @@
+//! Server side of the SSE transport.
+//!
+//! Serves a `WebSocketMessage` handler over two server functions instead of a
+//! websocket: one streaming responses as SSE events (`open`), one receiving
+//! requests (`send`). A session identifier, sent as the first event, ties the
+//! requests to the stream.
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[cfg(feature = "ssr")]
+//! static SESSIONS: SseSessions<Request> = SseSessions::new();
+//!
+//! #[server(output = EventStreamEncoding)]
+//! pub async fn my_sse_open() -> Result<EventStream, ServerFnError> {
+//!     Ok(sse::open::<MyHandler, RkyvEncoding>(&SESSIONS, |input, tx| {
+//!         GenericWebsocketBackend::new(input, tx, MyHandler::default())
+//!     }))
+//! }
+//!
+//! #[server]
+//! pub async fn my_sse_send(session: String, payload: String) -> Result<(), ServerFnError> {
+//!     sse::send::<Request, RkyvEncoding>(&SESSIONS, &session, &payload)
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+
+use futures::channel::mpsc::{self, UnboundedSender};
+use futures::{StreamExt, stream};
+use leptos::prelude::ServerFnError;
+use leptos::server_fn::{BoxedStream, Bytes, Decodes, Encodes};
+
+use super::backend::GenericWebsocketBackend;
+use super::message::WebSocketMessage;
+use crate::sse::{EventStream, decode_payload};
+
+/// Sender feeding the requests of one session to its backend.
+type RequestSender<Req> = UnboundedSender<Result<Req, ServerFnError>>;
+
+/// Open SSE sessions of one endpoint, by session identifier.
+///
+/// Meant to live in a `static` shared by the endpoint's `open` and `send`
+/// server functions.
+pub struct SseSessions<Req> {
+    sessions: Mutex<BTreeMap<String, RequestSender<Req>>>,
+}
+
+impl<Req> SseSessions<Req> {
+    /// Creates an empty registry.
+    pub const fn new() -> Self {
+        Self {
+            sessions: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Registers the request sender of a new session.
+    fn insert(&self, id: String, tx: RequestSender<Req>) {
+        self.sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id, tx);
+    }
+
+    /// Unregisters a session, ending its request stream.
+    fn remove(&self, id: &str) {
+        self.sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(id);
+    }
+
+    /// Returns the request sender of a session.
+    fn sender(&self, id: &str) -> Option<RequestSender<Req>> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(id)
+            .cloned()
+    }
+}
+
+impl<Req> Default for SseSessions<Req> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Unregisters a session when its event stream is dropped.
+struct SessionGuard<Req: 'static> {
+    sessions: &'static SseSessions<Req>,
+    id: String,
+}
+
+impl<Req: 'static> Drop for SessionGuard<Req> {
+    fn drop(&mut self) {
+        self.sessions.remove(&self.id);
+    }
+}
+
+/// Opens a session and returns its event stream.
+///
+/// The backend built by `backend` is served like a websocket connection. The
+/// session ends when the client goes away (the stream is dropped) or the
+/// handler closes the connection.
+///
+/// # Arguments
+///
+/// * `sessions` - Registry shared with the endpoint's `send` server function
+/// * `backend` - Builds the backend from the request stream and response sender
+///
+/// # Type Parameters
+///
+/// * `T` - The message handler
+/// * `Enc` - Codec of the responses (the endpoint's websocket encoding)
+pub fn open<T, Enc>(
+    sessions: &'static SseSessions<T::Request>,
+    backend: impl FnOnce(
+        BoxedStream<T::Request, ServerFnError>,
+        UnboundedSender<Result<T::Response, ServerFnError>>,
+    ) -> GenericWebsocketBackend<T>,
+) -> EventStream
+where
+    T: WebSocketMessage,
+    Enc: Encodes<T::Response> + 'static,
+{
+    let id = uuid::Uuid::new_v4().to_string();
+
+    let (request_tx, request_rx) = mpsc::unbounded();
+    let (response_tx, response_rx) = mpsc::unbounded();
+    sessions.insert(id.clone(), request_tx);
+
+    let backend = backend(request_rx.into(), response_tx);
+    tokio::spawn(async move {
+        backend.serve().await;
+    });
+
+    let guard = SessionGuard {
+        sessions,
+        id: id.clone(),
+    };
+    let responses = response_rx.map(move |response| {
+        // Keeps the session registered as long as the client listens
+        let _ = &guard;
+
+        response.and_then(|response| {
+            Enc::encode(&response).map_err(|e| ServerFnError::Serialization(e.to_string()))
+        })
+    });
+
+    // The session identifier comes first, then every response
+    EventStream::new(stream::once(async move { Ok(Bytes::from(id)) }).chain(responses))
+}
+
+/// Delivers a request posted by the client to its session's backend.
+///
+/// A payload that can not be decoded is passed on as a deserialization
+/// error, so the backend handles it like an undecodable websocket message.
+///
+/// # Errors
+///
+/// * `ServerFnError::Request` - The session is unknown or already closed
+pub fn send<Req, Enc>(
+    sessions: &SseSessions<Req>,
+    session: &str,
+    payload: &str,
+) -> Result<(), ServerFnError>
+where
+    Enc: Decodes<Req>,
+{
+    let Some(tx) = sessions.sender(session) else {
+        return Err(ServerFnError::Request(format!(
+            "Unknown SSE session: {session}"
+        )));
+    };
+
+    let request = decode_payload(payload).and_then(|bytes| {
+        Enc::decode(bytes).map_err(|e| ServerFnError::Deserialization(e.to_string()))
+    });
+
+    tx.unbounded_send(request)
+        .map_err(|_| ServerFnError::Request(format!("SSE session closed: {session}")))
+}
@@ -0,0 +1,269 @@
+//! Server-Sent Events encoding shared by the SSE transport.
+//!
+//! Some proxies break websocket upgrades but pass `text/event-stream`
+//! responses through. The SSE transport streams server → client messages as
+//! SSE events from a server function using [`EventStreamEncoding`], while
+//! client → server messages are posted with ordinary server function calls.
+//!
+//! # Wire Format
+//!
+//! Every payload is base64 encoded (URL-safe, no padding) in a `data:` line:
+//!
+//! ```text
+//! data: <encoded message>
+//!
+//! event: error
+//! data: <encoded ServerFnError>
+//!
+//! ```
+
+use std::fmt::Debug;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64;
+use futures::{Stream, StreamExt, stream};
+use leptos::prelude::ServerFnError;
+use leptos::server_fn::codec::{Encoding, FromRes, IntoRes};
+use leptos::server_fn::error::FromServerFnError;
+use leptos::server_fn::response::{ClientRes, TryRes};
+use leptos::server_fn::{Bytes, ContentType};
+
+/// Server function output encoding producing a `text/event-stream` response.
+///
+/// A server function using it as its output encoding returns an [`EventStream`]:
+///
+/// ```ignore
+/// #[server(output = EventStreamEncoding)]
+/// pub async fn my_events() -> Result<EventStream, ServerFnError> { ... }
+/// ```
+pub struct EventStreamEncoding;
+
+impl ContentType for EventStreamEncoding {
+    const CONTENT_TYPE: &'static str = "text/event-stream";
+}
+
+impl Encoding for EventStreamEncoding {
+    const METHOD: http::Method = http::Method::POST;
+}
+
+/// Stream of binary SSE events.
+///
+/// On the server, every item becomes one event; on the client, every
+/// received event becomes one item, in order.
+pub struct EventStream(Pin<Box<dyn Stream<Item = Result<Bytes, ServerFnError>> + Send>>);
+
+impl EventStream {
+    /// Creates an event stream from binary payloads.
+    pub fn new(stream: impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static) -> Self {
+        Self(Box::pin(stream))
+    }
+}
+
+impl Debug for EventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EventStream").finish()
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<Bytes, ServerFnError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.as_mut().poll_next(cx)
+    }
+}
+
+impl<E, Response> IntoRes<EventStreamEncoding, Response, E> for EventStream
+where
+    Response: TryRes<E>,
+    E: FromServerFnError,
+{
+    async fn into_res(self) -> Result<Response, E> {
+        Response::try_from_stream(
+            EventStreamEncoding::CONTENT_TYPE,
+            self.map(|event| Ok(frame(event))),
+        )
+    }
+}
+
+impl<E, Response> FromRes<EventStreamEncoding, Response, E> for EventStream
+where
+    Response: ClientRes<E> + Send,
+    E: FromServerFnError,
+{
+    async fn from_res(res: Response) -> Result<Self, E> {
+        let chunks = res.try_into_stream()?;
+
+        // Events may be split across (or share) network chunks
+        let mut buffer = String::new();
+        let events = chunks.flat_map(move |chunk| {
+            let events = match chunk {
+                Ok(chunk) => {
+                    buffer.push_str(&String::from_utf8_lossy(&chunk));
+                    drain_events(&mut buffer)
+                }
+                Err(e) => vec![Err(ServerFnError::de(e))],
+            };
+            stream::iter(events)
+        });
+
+        Ok(Self::new(events))
+    }
+}
+
+/// Encodes a payload for a `data:` line or a request argument.
+pub(crate) fn encode_payload(bytes: &[u8]) -> String {
+    BASE64.encode(bytes)
+}
+
+/// Decodes a payload produced by `encode_payload`.
+pub(crate) fn decode_payload(payload: &str) -> Result<Bytes, ServerFnError> {
+    BASE64
+        .decode(payload)
+        .map(Bytes::from)
+        .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+}
+
+/// Formats one SSE event.
+fn frame(event: Result<Bytes, ServerFnError>) -> Bytes {
+    match event {
+        Ok(data) => format!("data: {}\n\n", encode_payload(&data)),
+        Err(e) => format!("event: error\ndata: {}\n\n", encode_payload(&e.ser())),
+    }
+    .into()
+}
+
+/// Removes every complete event from the buffer and decodes it.
+///
+/// An event is complete once its blank line arrived. The rest stays in the
+/// buffer for the next chunk, and is dropped if the stream ends first, as
+/// `EventSource` does with a truncated event.
+fn drain_events(buffer: &mut String) -> Vec<Result<Bytes, ServerFnError>> {
+    let mut events = Vec::new();
+
+    while let Some(end) = buffer.find("\n\n") {
+        let block: String = buffer.drain(..end + 2).collect();
+        if let Some(event) = parse_event(&block) {
+            events.push(event);
+        }
+    }
+
+    events
+}
+
+/// Decodes a single event block, ignoring comments and events without data.
+///
+/// Several `data:` lines are joined without separator: line breaks carry no
+/// data in a base64 payload.
+fn parse_event(block: &str) -> Option<Result<Bytes, ServerFnError>> {
+    let mut is_error = false;
+    let mut data: Option<String> = None;
+
+    for line in block.lines() {
+        let Some((field, value)) = line.split_once(':') else {
+            continue;
+        };
+        // A single space may follow the colon
+        let value = value.strip_prefix(' ').unwrap_or(value);
+
+        match field {
+            "event" => is_error = value == "error",
+            "data" => data.get_or_insert_default().push_str(value),
+            // Comments (empty field name) and unsupported fields
+            _ => {}
+        }
+    }
+
+    let event = decode_payload(&data?).and_then(|bytes| match is_error {
+        true => Err(ServerFnError::de(bytes)),
+        false => Ok(bytes),
+    });
+
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Frames a successful event, as the server does.
+    fn event(data: &[u8]) -> String {
+        String::from_utf8(frame(Ok(Bytes::copy_from_slice(data))).to_vec()).unwrap()
+    }
+
+    fn payloads(events: Vec<Result<Bytes, ServerFnError>>) -> Vec<Vec<u8>> {
+        events
+            .into_iter()
+            .map(|event| event.expect("successful event").to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn events_sharing_a_chunk_are_decoded_in_order() {
+        let mut buffer = [event(b"first"), event(b"second")].concat();
+
+        assert_eq!(
+            payloads(drain_events(&mut buffer)),
+            [b"first".to_vec(), b"second".to_vec()]
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn an_event_split_across_chunks_is_decoded_once_complete() {
+        let framed = event(b"split");
+        let mut buffer = String::new();
+
+        // Cut inside the payload, then between the two line breaks
+        for chunk in [&framed[..8], &framed[8..framed.len() - 1]] {
+            buffer.push_str(chunk);
+            assert!(drain_events(&mut buffer).is_empty());
+        }
+
+        buffer.push('\n');
+        assert_eq!(payloads(drain_events(&mut buffer)), [b"split".to_vec()]);
+    }
+
+    #[test]
+    fn multi_line_data_is_joined() {
+        let payload = encode_payload(b"multi-line payload");
+        let (head, tail) = payload.split_at(6);
+        let mut buffer = format!("data: {head}\ndata:{tail}\n\n");
+
+        assert_eq!(
+            payloads(drain_events(&mut buffer)),
+            [b"multi-line payload".to_vec()]
+        );
+    }
+
+    #[test]
+    fn error_events_are_decoded_as_errors() {
+        let error = ServerFnError::ServerError("boom".into());
+        let mut buffer = String::from_utf8(frame(Err(error.clone())).to_vec()).unwrap();
+
+        let events = drain_events(&mut buffer);
+        assert!(matches!(&events[..], [Err(e)] if *e == error));
+    }
+
+    #[test]
+    fn comments_and_events_without_data_are_skipped() {
+        let mut buffer = [": keep-alive\n\n", "event: ping\n\n", &event(b"data")].concat();
+
+        assert_eq!(payloads(drain_events(&mut buffer)), [b"data".to_vec()]);
+    }
+
+    #[test]
+    fn a_trailing_event_without_a_blank_line_is_kept_back() {
+        let mut buffer = event(b"complete");
+        buffer.push_str(&format!("data: {}\n", encode_payload(b"truncated")));
+
+        assert_eq!(payloads(drain_events(&mut buffer)), [b"complete".to_vec()]);
+        assert_eq!(buffer, format!("data: {}\n", encode_payload(b"truncated")));
+
+        // The blank line completes it
+        buffer.push('\n');
+        assert_eq!(payloads(drain_events(&mut buffer)), [b"truncated".to_vec()]);
+    }
+}
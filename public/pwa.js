@@ -0,0 +1,52 @@
+// Registers the service worker, and shows the update prompt (`app/src/pwa`)
+// once a new version is installed.
+(() => {
+  if (!("serviceWorker" in navigator)) {
+    return;
+  }
+
+  // Without a controller, this is the first install: nothing to update
+  const hadController = Boolean(navigator.serviceWorker.controller);
+  let reloading = false;
+
+  const showPrompt = (worker) => {
+    const prompt = document.getElementById("update-prompt");
+    if (!prompt) {
+      return;
+    }
+
+    prompt.hidden = false;
+    prompt.querySelector("button").addEventListener("click", () => worker.postMessage("skip-waiting"), {
+      once: true,
+    });
+  };
+
+  // The new worker took over: reload to use its assets
+  navigator.serviceWorker.addEventListener("controllerchange", () => {
+    if (!hadController || reloading) {
+      return;
+    }
+
+    reloading = true;
+    window.location.reload();
+  });
+
+  window.addEventListener("load", async () => {
+    const registration = await navigator.serviceWorker.register("/sw.js", { scope: "/" });
+
+    // Installed during a previous visit
+    if (registration.waiting && hadController) {
+      showPrompt(registration.waiting);
+    }
+
+    registration.addEventListener("updatefound", () => {
+      const worker = registration.installing;
+
+      worker.addEventListener("statechange", () => {
+        if (worker.state === "installed" && hadController) {
+          showPrompt(worker);
+        }
+      });
+    });
+  });
+})();
@@ -8,4 +8,76 @@ export default defineConfig({
     },
   },
   presets: [presetMini()],
+  // Theme colors, selected by `<html data-theme>` (`app/src/theme`), as in `style/main.scss`
+  preflights: [
+    {
+      getCSS: () => `
+        :root {
+          --background: #ffffff;
+          --text: #1c2024;
+          --muted: #60646c;
+          --accent: #0d74ce;
+        }
+        :root[data-theme="dark"] {
+          --background: #111113;
+          --text: #edeef0;
+          --muted: #b0b4ba;
+          --accent: #70b8ff;
+        }
+        @media (prefers-color-scheme: dark) {
+          :root[data-theme="system"] {
+            --background: #111113;
+            --text: #edeef0;
+            --muted: #b0b4ba;
+            --accent: #70b8ff;
+          }
+        }
+        body {
+          background: var(--background);
+          color: var(--text);
+        }
+        a {
+          color: var(--accent);
+        }
+        footer {
+          display: flex;
+          flex-wrap: wrap;
+          justify-content: center;
+          gap: 1rem;
+        }
+        .theme-toggle button,
+        .locale-switcher button {
+          border: 1px solid var(--muted);
+          border-radius: 0.25rem;
+          background: transparent;
+          color: var(--text);
+          cursor: pointer;
+        }
+        .theme-toggle button[aria-pressed="true"],
+        .locale-switcher button[aria-pressed="true"] {
+          border-color: var(--accent);
+          color: var(--accent);
+        }
+        .update-prompt[hidden] {
+          display: none;
+        }
+      `,
+    },
+  ],
+  // Classes shared with `style/main.scss`
+  shortcuts: {
+    "theme-toggle": "flex justify-center gap-1 p-2",
+    "locale-switcher": "flex justify-center gap-1 p-2",
+    "update-prompt": "flex items-center gap-2 p-2",
+    "ssr-modes": "flex flex-wrap justify-center gap-4",
+    uploader: "flex flex-wrap items-center justify-center gap-2 p-2",
+    "upload-progress": "flex items-center justify-center gap-2 p-2",
+    files: "p-0 list-none",
+    "file-size": "text-[var(--muted)]",
+    "error-page": "flex flex-col items-center gap-4 px-4 py-16",
+    "error-status": "m-0 text-6xl font-bold",
+    "error-reason": "m-0 text-2xl",
+    "error-detail": "p-0 text-[var(--muted)]",
+    "field-error": "my-1 text-sm text-red-500",
+  },
 });
@@ -17,10 +17,18 @@ template_test!(
     all_feature_template,
     CargoGenerate {
         websocket: true,
+        compression: true,
         style: Style::Unocss,
         docker: true,
         cucumber: true,
-        benchmark: true
+        benchmark: true,
+        auth: true,
+        database: true,
+        i18n: true,
+        // Hydrated: islands are covered by the islands_* templates
+        islands: false,
+        pwa: true,
+        uploads: true,
     }
 );
 
@@ -32,6 +40,17 @@ template_test!(
     }
 );
 
+template_test!(
+    websocket_compression_and_cucumber_benchmark,
+    CargoGenerate {
+        websocket: true,
+        compression: true,
+        cucumber: true,
+        benchmark: true,
+        ..Default::default()
+    }
+);
+
 template_test!(
     style_unocss_only,
     CargoGenerate {
@@ -84,3 +103,116 @@ template_test!(
         ..Default::default()
     }
 );
+
+template_test!(
+    auth_only,
+    CargoGenerate {
+        auth: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    auth_and_cucumber,
+    CargoGenerate {
+        auth: true,
+        cucumber: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    database_only,
+    CargoGenerate {
+        database: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    database_and_cucumber,
+    CargoGenerate {
+        database: true,
+        cucumber: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    i18n_only,
+    CargoGenerate {
+        i18n: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    i18n_and_cucumber,
+    CargoGenerate {
+        i18n: true,
+        cucumber: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    islands_only,
+    CargoGenerate {
+        islands: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    islands_and_websocket,
+    CargoGenerate {
+        websocket: true,
+        islands: true,
+        cucumber: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    islands_and_auth,
+    CargoGenerate {
+        auth: true,
+        islands: true,
+        cucumber: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    pwa_only,
+    CargoGenerate {
+        pwa: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    pwa_and_cucumber,
+    CargoGenerate {
+        pwa: true,
+        cucumber: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    uploads_only,
+    CargoGenerate {
+        uploads: true,
+        ..Default::default()
+    }
+);
+
+template_test!(
+    uploads_and_cucumber,
+    CargoGenerate {
+        uploads: true,
+        cucumber: true,
+        ..Default::default()
+    }
+);
@@ -14,10 +14,17 @@ pub use generate_result::GenerateResult;
 #[derive(Debug, Default)]
 pub struct CargoGenerate {
     pub websocket: bool,
+    pub compression: bool,
     pub style: Style,
     pub docker: bool,
     pub cucumber: bool,
     pub benchmark: bool,
+    pub auth: bool,
+    pub database: bool,
+    pub i18n: bool,
+    pub islands: bool,
+    pub pwa: bool,
+    pub uploads: bool,
 }
 
 #[derive(Debug, Default)]
@@ -43,10 +50,17 @@ impl CargoGenerate {
         eprintln!("{self:#?}");
         let Self {
             websocket,
+            compression,
             style,
             docker,
             cucumber,
             benchmark,
+            auth,
+            database,
+            i18n,
+            islands,
+            pwa,
+            uploads,
         } = &self;
 
         let tempfile = TempDir::new()?;
@@ -70,6 +84,11 @@ impl CargoGenerate {
                 websocket.to_string().to_lowercase()
             ))
             .arg("-d")
+            .arg(format!(
+                "compression={}",
+                compression.to_string().to_lowercase()
+            ))
+            .arg("-d")
             .arg(format!("style={}", style))
             .arg("-d")
             .arg(format!("docker={}", docker.to_string().to_lowercase()))
@@ -79,7 +98,19 @@ impl CargoGenerate {
             .arg(format!(
                 "benchmark={}",
                 benchmark.to_string().to_lowercase()
-            ));
+            ))
+            .arg("-d")
+            .arg(format!("auth={}", auth.to_string().to_lowercase()))
+            .arg("-d")
+            .arg(format!("database={}", database.to_string().to_lowercase()))
+            .arg("-d")
+            .arg(format!("i18n={}", i18n.to_string().to_lowercase()))
+            .arg("-d")
+            .arg(format!("islands={}", islands.to_string().to_lowercase()))
+            .arg("-d")
+            .arg(format!("pwa={}", pwa.to_string().to_lowercase()))
+            .arg("-d")
+            .arg(format!("uploads={}", uploads.to_string().to_lowercase()));
 
         unsafe {
             cmd.pre_exec(move || {